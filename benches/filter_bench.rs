@@ -9,13 +9,8 @@ use std::{str::FromStr, sync::Arc};
 use sieve::prelude::*;
 
 fn generate_random_address() -> String {
-    let mut rng = thread_rng();
-    let mut address = String::with_capacity(42);
-    address.push_str("0x");
-    for _ in 0..40 {
-        address.push(rng.gen_range('0'..='f'));
-    }
-    address
+    let bytes: [u8; 20] = thread_rng().gen();
+    format!("{:#x}", Address::from(bytes))
 }
 
 fn generate_best_case_filter() -> Filter {
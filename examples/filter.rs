@@ -38,15 +38,25 @@ fn main() {
     let _monitoring_filter = FilterBuilder::new().event(|f| {
         // Monitor multiple tokens & DEX
         f.any_of(|e| {
-            e.contract().exact("TetherToken");
-            e.contract().exact("UniswapV2Factory");
-            e.contract().exact("FiatTokenProxy");
+            // TetherToken
+            e.contract()
+                .exact("0xdAC17F958D2ee523a2206206994597C13D831ec7");
+            // UniswapV2Factory
+            e.contract()
+                .exact("0x5C69bEe701ef814a2B6a3EDD4B1652CB9cc5aA6f");
+            // FiatTokenProxy (USDC)
+            e.contract()
+                .exact("0xA0b86991c6218b36c1d19D4a2e9Eb0cE3606eB48");
         });
 
         // Monitor lending protocols
         f.any_of(|e| {
-            e.contract().exact("Comp");
-            e.contract().exact("InitializableAdminUpgradeabilityProxy");
+            // Comp
+            e.contract()
+                .exact("0xc00e94Cb662C3520282E6f5717214004A7f26888");
+            // InitializableAdminUpgradeabilityProxy (Aave)
+            e.contract()
+                .exact("0x7Fc66500c84A76Ad7e9c93437bFc5Ac33E2DDaE9");
             e.contract()
                 .exact("0xdAC17F958D2ee523a2206206994597C13D831ec7");
             e.topics().contains(
@@ -116,9 +126,16 @@ fn main() {
     let _block_filter = FilterBuilder::new().block_header(|f| {
         f.or(|b| {
             b.gas_limit().gt(100);
-            b.hash().exact("0x742d35Cc6634C0532925a3b844Bc454e4438f44e");
+            b.hash()
+                .exact("0x883f974b17ca7b28cb970798d1c80f4d4bb427473dc6d39b2a7fe24edc02902d");
             b.state_root()
-                .contains("0x742d35Cc6634C0532925a3b844Bc454e4438f44e");
+                .contains("742d35cc6634c0532925a3b844bc454e4438f44e");
+            // Blocks built by beaverbuild carrying blobs
+            b.and(|b| {
+                b.miner()
+                    .exact("0x95222290DD7278Aa3Ddd389Cc1E1d165CC4BAfe5");
+                b.blob_gas_used().gt(0);
+            });
 
             b.receipts_root().starts_with("0xdead");
             b.base_fee().gt(100);
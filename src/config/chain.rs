@@ -1,8 +1,19 @@
+use std::time::Duration;
+
+/// Default number of recently seen blocks and transactions remembered to deduplicate chain data.
+const DEFAULT_DEDUP_CACHE_SIZE: usize = 10_000;
+
 /// Chain configuration settings.
 #[allow(dead_code)]
 pub struct ChainConfig {
-    /// RPC endpoint URL for the chain
-    rpc_url: String,
+    /// RPC endpoints of the chain
+    rpc_endpoints: Vec<RpcEndpoint>,
+
+    /// Delay after which a request is also sent to the next RPC endpoint
+    hedge_delay: Option<Duration>,
+
+    /// Backoff of RPC polling after failed polls
+    rpc_backoff: Backoff,
 
     /// WebSocket endpoint URL for real-time communication
     ws_url: String,
@@ -15,11 +26,35 @@ pub struct ChainConfig {
 
     /// Chain
     chain: Chain,
+
+    /// Number of recently seen blocks and transactions remembered to deduplicate chain data
+    dedup_cache_size: usize,
+
+    /// Where pending transactions come from
+    mempool_source: MempoolSource,
 }
 
 impl ChainConfig {
+    /// URL of the RPC endpoint with the best priority, empty without RPC endpoints.
+    #[deprecated(note = "chains can have several RPC endpoints, use `rpc_endpoints` instead")]
     pub fn rpc_url(&self) -> &String {
-        &self.rpc_url
+        static NO_URL: String = String::new();
+        self.rpc_endpoints
+            .iter()
+            .min_by_key(|endpoint| endpoint.priority)
+            .map_or(&NO_URL, |endpoint| &endpoint.url)
+    }
+
+    pub fn rpc_endpoints(&self) -> &[RpcEndpoint] {
+        &self.rpc_endpoints
+    }
+
+    pub fn hedge_delay(&self) -> Option<Duration> {
+        self.hedge_delay
+    }
+
+    pub fn rpc_backoff(&self) -> Backoff {
+        self.rpc_backoff
     }
 
     pub fn ws_url(&self) -> &String {
@@ -36,6 +71,89 @@ impl ChainConfig {
     pub fn chain(&self) -> Chain {
         self.chain.clone()
     }
+
+    pub fn dedup_cache_size(&self) -> usize {
+        self.dedup_cache_size
+    }
+
+    pub fn mempool_source(&self) -> MempoolSource {
+        self.mempool_source
+    }
+}
+
+/// An RPC endpoint of a chain, endpoints with lower priority values are preferred.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct RpcEndpoint {
+    url: String,
+    priority: u32,
+    rate_limit: Option<u32>,
+}
+
+impl RpcEndpoint {
+    pub fn new(url: &str, priority: u32) -> Self {
+        Self {
+            url: url.to_string(),
+            priority,
+            rate_limit: None,
+        }
+    }
+
+    /// Limits the requests sent to the endpoint to `requests_per_second`.
+    pub fn with_rate_limit(mut self, requests_per_second: u32) -> Self {
+        self.rate_limit = Some(requests_per_second);
+        self
+    }
+
+    pub fn url(&self) -> &str {
+        &self.url
+    }
+
+    pub fn priority(&self) -> u32 {
+        self.priority
+    }
+
+    pub fn rate_limit(&self) -> Option<u32> {
+        self.rate_limit
+    }
+}
+
+/// Exponential backoff of RPC polling: the delay before the next poll doubles after every failed
+/// poll, from `initial` up to `max`, and polling resumes at its usual interval once a poll
+/// succeeds.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct Backoff {
+    initial: Duration,
+    max: Duration,
+}
+
+impl Backoff {
+    pub fn new(initial: Duration, max: Duration) -> Self {
+        Self { initial, max }
+    }
+
+    /// Delay before the next poll after `failures` consecutive failed polls.
+    pub fn delay(&self, failures: u32) -> Duration {
+        let factor = 1u32 << failures.saturating_sub(1).min(31);
+        self.initial.saturating_mul(factor).min(self.max)
+    }
+}
+
+impl Default for Backoff {
+    fn default() -> Self {
+        Self::new(Duration::from_secs(1), Duration::from_secs(60))
+    }
+}
+
+/// Source of the pending transactions of a chain.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum MempoolSource {
+    /// Pending transaction filters over RPC or `newPendingTransactions` subscriptions over
+    /// WebSocket
+    #[default]
+    PendingTransactions,
+    /// Periodic `txpool_content` snapshots over RPC, diffed to find the transactions entering and
+    /// leaving the pool. Far more complete, but requires a node exposing the `txpool` namespace.
+    TxPool,
 }
 
 #[derive(Clone, Debug, Hash, PartialEq, Eq)]
@@ -49,8 +167,17 @@ pub enum Chain {
 /// Builder for creating a Chain configuration.
 #[allow(dead_code)]
 pub struct ChainConfigBuilder {
-    /// Optional RPC endpoint URL
-    rpc_url: Option<String>,
+    /// RPC endpoints
+    rpc_endpoints: Vec<RpcEndpoint>,
+
+    /// Optional delay before hedging RPC requests
+    hedge_delay: Option<Duration>,
+
+    /// Backoff of RPC polling
+    rpc_backoff: Backoff,
+
+    /// Optional requests per second limit of each RPC endpoint
+    rpc_rate_limit: Option<u32>,
 
     /// Optional WebSocket endpoint URL
     ws_url: Option<String>,
@@ -63,6 +190,12 @@ pub struct ChainConfigBuilder {
 
     /// Chain
     chain: Option<Chain>,
+
+    /// Optional size of the deduplication caches
+    dedup_cache_size: Option<usize>,
+
+    /// Source of pending transactions
+    mempool_source: MempoolSource,
 }
 
 #[allow(dead_code)]
@@ -71,21 +204,67 @@ impl ChainConfigBuilder {
         ChainConfigBuilder {
             gossipsub_url: None,
             ws_url: None,
-            rpc_url: None,
+            rpc_endpoints: vec![],
+            hedge_delay: None,
+            rpc_backoff: Backoff::default(),
+            rpc_rate_limit: None,
             peers: vec![],
             chain: None,
+            dedup_cache_size: None,
+            mempool_source: MempoolSource::default(),
         }
     }
-    /// Sets the RPC endpoint URL for the chain configuration
+    /// Adds an RPC endpoint URL with the highest priority to the chain configuration, requests
+    /// are load balanced across endpoints of the same priority
     ///
     /// # Arguments
     /// * `rpc_url` - The RPC endpoint URL as a string
     pub fn rpc(&mut self, rpc_url: &str) -> &mut ChainConfigBuilder {
-        self.rpc_url = Some(rpc_url.to_string());
+        self.rpc_endpoint(rpc_url, 0)
+    }
+
+    /// Adds an RPC endpoint URL to the chain configuration, endpoints with a lower priority value
+    /// are preferred and the others are only used when they are unhealthy
+    ///
+    /// # Arguments
+    /// * `rpc_url` - The RPC endpoint URL as a string
+    /// * `priority` - Priority of the endpoint, `0` being the highest
+    pub fn rpc_endpoint(&mut self, rpc_url: &str, priority: u32) -> &mut ChainConfigBuilder {
+        self.rpc_endpoints.push(RpcEndpoint::new(rpc_url, priority));
+        self
+    }
+
+    /// Sends RPC requests that did not complete within `delay` to the next endpoint as well,
+    /// using whichever response comes first. Trades extra load on the endpoints for latency.
+    ///
+    /// # Arguments
+    /// * `delay` - Delay before a request is sent to the next endpoint
+    pub fn hedge_requests(&mut self, delay: Duration) -> &mut ChainConfigBuilder {
+        self.hedge_delay = Some(delay);
         self
     }
 
-    /// Sets the WebSocket endpoint URL for the chain configuration
+    /// Sets the backoff of RPC polling after failed polls, e.g. when endpoints are down or rate
+    /// limit requests
+    ///
+    /// # Arguments
+    /// * `backoff` - Exponential backoff of failed polls
+    pub fn rpc_backoff(&mut self, backoff: Backoff) -> &mut ChainConfigBuilder {
+        self.rpc_backoff = backoff;
+        self
+    }
+
+    /// Limits the requests sent to each RPC endpoint of the chain
+    ///
+    /// # Arguments
+    /// * `requests_per_second` - Maximum number of requests per second of each endpoint
+    pub fn rpc_rate_limit(&mut self, requests_per_second: u32) -> &mut ChainConfigBuilder {
+        self.rpc_rate_limit = Some(requests_per_second);
+        self
+    }
+
+    /// Sets the WebSocket endpoint URL for the chain configuration. Data is then received over
+    /// WebSocket subscriptions instead of polling the RPC endpoints, unless the txpool is snapshot.
     ///
     /// # Arguments
     /// * `ws_url` - The WebSocket endpoint URL as a string
@@ -121,18 +300,52 @@ impl ChainConfigBuilder {
         self
     }
 
+    /// Sets how many recently seen blocks and transactions are remembered to drop duplicates,
+    /// busy mempools need a larger cache
+    ///
+    /// # Arguments
+    /// * `size` - Number of entries of each deduplication cache
+    pub fn dedup_cache_size(&mut self, size: usize) -> &mut ChainConfigBuilder {
+        self.dedup_cache_size = Some(size);
+        self
+    }
+
+    /// Sets where pending transactions come from, chains using [`MempoolSource::TxPool`] are
+    /// polled over RPC
+    ///
+    /// # Arguments
+    /// * `source` - Source of pending transactions
+    pub fn mempool_source(&mut self, source: MempoolSource) -> &mut ChainConfigBuilder {
+        self.mempool_source = source;
+        self
+    }
+
     /// Builds the final Chain configuration
     pub fn build(&mut self) -> ChainConfig {
-        if self.gossipsub_url.is_none() && self.rpc_url.is_none() && self.ws_url.is_none() {
+        if self.gossipsub_url.is_none() && self.rpc_endpoints.is_empty() && self.ws_url.is_none() {
             panic!("at least one url is required.")
         }
+        if self.mempool_source == MempoolSource::TxPool && self.rpc_endpoints.is_empty() {
+            panic!("an rpc url is required to snapshot the txpool.")
+        }
 
         ChainConfig {
-            rpc_url: self.rpc_url.clone().unwrap_or_default(),
+            rpc_endpoints: self
+                .rpc_endpoints
+                .iter()
+                .map(|endpoint| match self.rpc_rate_limit {
+                    Some(rate_limit) => endpoint.clone().with_rate_limit(rate_limit),
+                    None => endpoint.clone(),
+                })
+                .collect(),
+            hedge_delay: self.hedge_delay,
+            rpc_backoff: self.rpc_backoff,
             ws_url: self.ws_url.clone().unwrap_or_default(),
             gossipsub_url: self.gossipsub_url.clone().unwrap_or_default(),
             peers: self.peers.clone(),
             chain: self.chain.clone().expect("chain is required."),
+            dedup_cache_size: self.dedup_cache_size.unwrap_or(DEFAULT_DEDUP_CACHE_SIZE),
+            mempool_source: self.mempool_source,
         }
     }
 }
@@ -153,11 +366,16 @@ impl Chain {
     /// ```
     pub fn builder() -> ChainConfigBuilder {
         ChainConfigBuilder {
-            rpc_url: None,
+            rpc_endpoints: vec![],
+            hedge_delay: None,
+            rpc_backoff: Backoff::default(),
+            rpc_rate_limit: None,
             ws_url: None,
             gossipsub_url: None,
             peers: vec![],
             chain: None,
+            dedup_cache_size: None,
+            mempool_source: MempoolSource::default(),
         }
     }
 }
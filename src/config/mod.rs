@@ -1,2 +1,2 @@
 mod chain;
-pub use chain::{Chain, ChainConfig, ChainConfigBuilder};
+pub use chain::{Backoff, Chain, ChainConfig, ChainConfigBuilder, MempoolSource, RpcEndpoint};
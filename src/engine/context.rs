@@ -52,7 +52,7 @@ where
             return self.data.evaluate(condition, None);
         }
 
-        let key = self.data.cache_key();
+        let key = self.data.cache_key(condition);
         let decoded = self.entry(&key).or_else(|| {
             self.data.decode_data(condition).inspect(|decoded| {
                 self.insert(&key, decoded.clone());
@@ -1,11 +1,16 @@
-use super::{state::CacheKey, DecodedData};
+use super::{
+    state::{CacheKey, DecodedContractCall},
+    DecodedData,
+};
 use crate::filter::conditions::{
     BlockHeaderCondition, EventCondition, FilterCondition, PoolCondition, TransactionCondition,
 };
-use crate::{filter::evaluate::Evaluable, utils::decoder::EventDefinition};
+use crate::filter::evaluate::Evaluable;
+use crate::network::orchestrator::PendingTransaction;
+use crate::utils::decoder::{CallDefinition, EventDefinition};
 use alloy_consensus::{BlockHeader, Transaction, Typed2718};
 use alloy_network::{AnyHeader, AnyRpcTransaction};
-use alloy_primitives::{keccak256, Selector};
+use alloy_primitives::Selector;
 use alloy_rpc_types::{Header, Log};
 use std::sync::Arc;
 
@@ -20,8 +25,8 @@ pub trait EvaluableData {
         true
     }
 
-    /// Generates a unique key for caching decoded data
-    fn cache_key(&self) -> CacheKey;
+    /// Generates a unique key for caching the data decoded for a condition
+    fn cache_key(&self, condition: &FilterCondition) -> CacheKey;
 
     /// Evaluates the data against a filter condition
     fn evaluate(&self, condition: &FilterCondition, decoded_data: Option<Arc<DecodedData>>)
@@ -32,17 +37,23 @@ pub trait EvaluableData {
 }
 
 impl EvaluableData for AnyRpcTransaction {
-    fn cache_key(&self) -> CacheKey {
+    fn cache_key(&self, condition: &FilterCondition) -> CacheKey {
         // TODO: revisit this, unwrap_or_default is just to prevent panic in the meantime but
         // can lead to unexpected result for cache key.
-        let hash = self.info().hash.unwrap_or_default().to_string();
-        CacheKey::ContractCall(hash)
+        let hash = self.info().hash.unwrap_or_default();
+        // The same calldata decodes differently depending on the signature it is decoded with
+        match condition {
+            FilterCondition::Transaction(TransactionCondition::CallData { signature, .. }) => {
+                CacheKey::ContractCall(format!("{hash}:{signature}"))
+            }
+            _ => CacheKey::ContractCall(hash.to_string()),
+        }
     }
 
     fn evaluate(
         &self,
         filter_condition: &FilterCondition,
-        _decoded_data: Option<Arc<DecodedData>>,
+        decoded_data: Option<Arc<DecodedData>>,
     ) -> bool {
         // TODO: Handle chain-specific transaction fields
         // - `AnyRpcTransaction`  contains fields specific to different chains (Base, Optimism, etc.)
@@ -55,7 +66,7 @@ impl EvaluableData for AnyRpcTransaction {
                 TransactionCondition::GasPrice(condition) => {
                     condition.evaluate(&self.gas_price().unwrap_or_default())
                 }
-                TransactionCondition::From(condition) => condition.evaluate(&self.from.to_string()),
+                TransactionCondition::From(condition) => condition.evaluate(&self.from),
                 TransactionCondition::MaxFeePerGas(condition) => {
                     condition.evaluate(&self.max_fee_per_gas())
                 }
@@ -66,13 +77,13 @@ impl EvaluableData for AnyRpcTransaction {
                     condition.evaluate(&self.block_number.unwrap_or_default())
                 }
                 TransactionCondition::BlockHash(condition) => {
-                    condition.evaluate(&self.block_hash.unwrap_or_default().to_string())
+                    condition.evaluate(&self.block_hash.unwrap_or_default())
                 }
                 TransactionCondition::ChainId(condition) => {
                     condition.evaluate(&self.chain_id().unwrap_or_default())
                 }
                 TransactionCondition::To(condition) => {
-                    condition.evaluate(&self.to().unwrap_or_default().to_string())
+                    condition.evaluate(&self.to().unwrap_or_default())
                 }
                 TransactionCondition::Nonce(condition) => condition.evaluate(&self.nonce()),
                 TransactionCondition::Type(condition) => condition.evaluate(&self.ty()),
@@ -80,12 +91,22 @@ impl EvaluableData for AnyRpcTransaction {
                     condition.evaluate(&self.transaction_index.unwrap_or_default())
                 }
                 TransactionCondition::Hash(condition) => {
-                    condition.evaluate(&self.info().hash.unwrap_or_default().to_string())
+                    condition.evaluate(&self.info().hash.unwrap_or_default())
                 }
                 TransactionCondition::DynField(dyn_condition) => {
                     let json_value = serde_json::to_value(self).unwrap_or_default();
                     dyn_condition.evaluate(&json_value)
                 }
+                TransactionCondition::CallData {
+                    parameters, paths, ..
+                } => match decoded_data.as_deref() {
+                    Some(DecodedData::ContractCall(call)) => parameters
+                        .iter()
+                        .chain(paths)
+                        .all(|condition| condition.evaluate(&call.parameters)),
+                    // The selector already matched in `pre_evaluate`
+                    _ => parameters.is_empty() && paths.is_empty(),
+                },
                 _ => false,
             },
             FilterCondition::Pool(pool_condition) => match pool_condition {
@@ -93,15 +114,17 @@ impl EvaluableData for AnyRpcTransaction {
                 PoolCondition::GasPrice(condition) => {
                     condition.evaluate(&self.gas_price().unwrap_or_default())
                 }
-                PoolCondition::From(condition) => condition.evaluate(&self.from.to_string()),
+                PoolCondition::From(condition) => condition.evaluate(&self.from),
                 PoolCondition::Nonce(condition) => condition.evaluate(&self.nonce()),
                 PoolCondition::GasLimit(condition) => condition.evaluate(&self.gas_limit()),
                 PoolCondition::Hash(condition) => {
-                    condition.evaluate(&self.info().hash.unwrap_or_default().to_string())
-                }
-                PoolCondition::To(condition) => {
-                    condition.evaluate(&self.to().unwrap_or_default().to_string())
+                    condition.evaluate(&self.info().hash.unwrap_or_default())
                 }
+                PoolCondition::To(condition) => condition.evaluate(&self.to().unwrap_or_default()),
+                // Only known for transactions seen in the mempool, see [`PendingTransaction`]
+                PoolCondition::Timestamp(_)
+                | PoolCondition::Age(_)
+                | PoolCondition::IsReplacement => false,
             },
             FilterCondition::DynField(dyn_condition) => {
                 // TODO: all common fields are supported by defualt , which means
@@ -114,8 +137,25 @@ impl EvaluableData for AnyRpcTransaction {
         }
     }
 
-    fn decode_data(&self, _condition: &FilterCondition) -> Option<Arc<DecodedData>> {
-        unimplemented!()
+    fn decode_data(&self, condition: &FilterCondition) -> Option<Arc<DecodedData>> {
+        let FilterCondition::Transaction(TransactionCondition::CallData { signature, abi, .. }) =
+            condition
+        else {
+            return None;
+        };
+
+        let call = match abi {
+            Some(function) => CallDefinition::from_abi(function),
+            None => CallDefinition::from_signature(signature),
+        }
+        .ok()?;
+        // Arguments are encoded right after the 4-byte selector
+        let decoded = call.decode_call_data(self.input().get(4..)?).ok()?;
+
+        Some(Arc::new(DecodedData::ContractCall(DecodedContractCall {
+            method: decoded.name,
+            parameters: decoded.parameters,
+        })))
     }
 
     fn pre_evaluate(&self, condition: &FilterCondition) -> bool {
@@ -136,8 +176,39 @@ impl EvaluableData for AnyRpcTransaction {
     }
 }
 
+// Pending transactions are evaluated like any other transaction, except for the conditions on
+// when they were first seen.
+impl EvaluableData for PendingTransaction {
+    fn pre_evaluate(&self, condition: &FilterCondition) -> bool {
+        self.transaction.pre_evaluate(condition)
+    }
+
+    fn cache_key(&self, condition: &FilterCondition) -> CacheKey {
+        self.transaction.cache_key(condition)
+    }
+
+    fn evaluate(
+        &self,
+        condition: &FilterCondition,
+        decoded_data: Option<Arc<DecodedData>>,
+    ) -> bool {
+        match condition {
+            FilterCondition::Pool(PoolCondition::Timestamp(condition)) => {
+                condition.evaluate(&self.timestamp())
+            }
+            FilterCondition::Pool(PoolCondition::Age(condition)) => condition.evaluate(&self.age()),
+            FilterCondition::Pool(PoolCondition::IsReplacement) => self.replaces.is_some(),
+            _ => self.transaction.evaluate(condition, decoded_data),
+        }
+    }
+
+    fn decode_data(&self, condition: &FilterCondition) -> Option<Arc<DecodedData>> {
+        self.transaction.decode_data(condition)
+    }
+}
+
 impl EvaluableData for Header<AnyHeader> {
-    fn cache_key(&self) -> CacheKey {
+    fn cache_key(&self, _condition: &FilterCondition) -> CacheKey {
         CacheKey::ContractCall(self.number().to_string())
     }
 
@@ -155,6 +226,30 @@ impl EvaluableData for Header<AnyHeader> {
                 BlockHeaderCondition::Timestamp(condition) => condition.evaluate(&self.timestamp),
                 BlockHeaderCondition::GasUsed(condition) => condition.evaluate(&self.gas_used),
                 BlockHeaderCondition::GasLimit(condition) => condition.evaluate(&self.gas_limit),
+                // Fields introduced by later forks never match blocks that don't have them
+                BlockHeaderCondition::BlobGasUsed(condition) => self
+                    .blob_gas_used
+                    .is_some_and(|blob_gas_used| condition.evaluate(&blob_gas_used)),
+                BlockHeaderCondition::ExcessBlobGas(condition) => self
+                    .excess_blob_gas
+                    .is_some_and(|excess_blob_gas| condition.evaluate(&excess_blob_gas)),
+                BlockHeaderCondition::Size(condition) => {
+                    self.size.is_some_and(|size| condition.evaluate(&size))
+                }
+                BlockHeaderCondition::Hash(condition) => condition.evaluate(&self.hash),
+                BlockHeaderCondition::Miner(condition) => condition.evaluate(&self.beneficiary),
+                BlockHeaderCondition::WithdrawalsRoot(condition) => self
+                    .withdrawals_root
+                    .is_some_and(|root| condition.evaluate(&root)),
+                BlockHeaderCondition::ParentBeaconBlockRoot(condition) => self
+                    .parent_beacon_block_root
+                    .is_some_and(|root| condition.evaluate(&root)),
+                BlockHeaderCondition::MixHash(condition) => self
+                    .mix_hash
+                    .is_some_and(|mix_hash| condition.evaluate(&mix_hash)),
+                BlockHeaderCondition::ExtraData(condition) => {
+                    condition.evaluate(&self.extra_data.to_string())
+                }
                 BlockHeaderCondition::ParentHash(condition) => {
                     condition.evaluate(&self.parent_hash.to_string())
                 }
@@ -182,8 +277,19 @@ impl EvaluableData for Header<AnyHeader> {
 }
 
 impl EvaluableData for Log {
-    fn cache_key(&self) -> CacheKey {
-        CacheKey::Event(format!("{:?}", self.log_index))
+    fn cache_key(&self, condition: &FilterCondition) -> CacheKey {
+        // Log index is only unique within a block, so the block hash is part of the key.
+        let key = format!(
+            "{}:{}",
+            self.block_hash.unwrap_or_default(),
+            self.log_index.unwrap_or_default()
+        );
+        match condition {
+            FilterCondition::Event(EventCondition::EventData { signature, .. }) => {
+                CacheKey::Event(format!("{key}:{signature}"))
+            }
+            _ => CacheKey::Event(key),
+        }
     }
 
     fn evaluate(
@@ -193,9 +299,7 @@ impl EvaluableData for Log {
     ) -> bool {
         match condition {
             FilterCondition::Event(event_condition) => match event_condition {
-                EventCondition::Contract(condition) => {
-                    condition.evaluate(&self.address().to_string())
-                }
+                EventCondition::Contract(condition) => condition.evaluate(&self.address()),
                 EventCondition::BlockHash(condition) => {
                     condition.evaluate(&self.block_hash.unwrap_or_default().to_string())
                 }
@@ -225,13 +329,14 @@ impl EvaluableData for Log {
                             parameters.iter().all(|(param, condition)| {
                                 decoded_log
                                     .get_parameter(param)
-                                    .map_or(false, |value| condition.evaluate(value))
+                                    .is_some_and(|value| condition.evaluate(value))
                             })
                         } else {
                             false
                         }
                     }
-                    None => false,
+                    // The topic already matched in `pre_evaluate`
+                    None => parameters.is_empty(),
                 },
                 EventCondition::DynField(dyn_condition) => {
                     let json_value = serde_json::to_value(self).unwrap_or_default();
@@ -243,25 +348,25 @@ impl EvaluableData for Log {
     }
     fn pre_evaluate(&self, condition: &FilterCondition) -> bool {
         match condition {
-            FilterCondition::Event(EventCondition::EventData { signature, .. }) => {
-                let data = self.data();
-                let topic_0 = data.topics()[0];
-                // Calculate the method selector from the signature
-                let method_selector = Selector::from_slice(&keccak256(signature.as_bytes())[0..4]);
-                let topic_selector = &topic_0[0..4];
-                // Compare the selector with the first 4 bytes of topic0
-                topic_selector == method_selector.as_slice()
+            FilterCondition::Event(EventCondition::EventData { topic_0, .. }) => {
+                // Anonymous events have no topic0 to compare against
+                self.topic0() == Some(topic_0)
             }
-            _ => false,
+            _ => true,
         }
     }
 
     fn decode_data(&self, condition: &FilterCondition) -> Option<Arc<DecodedData>> {
-        let FilterCondition::Event(EventCondition::EventData { signature, .. }) = condition else {
+        let FilterCondition::Event(EventCondition::EventData { signature, abi, .. }) = condition
+        else {
             return None;
         };
 
-        let event = EventDefinition::from_signature(signature).ok()?;
+        let event = match abi {
+            Some(event) => EventDefinition::from_abi(event),
+            None => EventDefinition::from_signature(signature),
+        }
+        .ok()?;
         let event_log = event.decode_log(&self.inner.data).ok()?;
 
         Some(Arc::new(DecodedData::Event(event_log)))
@@ -44,7 +44,7 @@ impl FilterEngine {
     {
         match &filter.value {
             Some(condition) => ctx.evaluate(condition),
-            None => filter.children.as_ref().map_or(false, |(op, nodes)| {
+            None => filter.children.as_ref().is_some_and(|(op, nodes)| {
                 let parallel_iter = nodes.par_iter();
 
                 match op {
@@ -73,13 +73,24 @@ impl FilterEngine {
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::filter::conditions::{BytesCondition, DynFieldCondition, ValueCondition};
     use crate::filter::conditions::{
         FilterCondition, FilterNode, LogicalOp, NumericCondition, StringCondition,
         TransactionCondition,
     };
-    use alloy_network::{AnyRpcTransaction, AnyTxEnvelope};
-    use alloy_primitives::U256;
-    use alloy_rpc_types::Transaction as RpcTransaction;
+    use crate::filter::{ArrayOps, FilterBuilder, FilterError, NumericOps, StringOps};
+    use crate::utils::decoder::CallDefinition;
+    use alloy_dyn_abi::DynSolValue;
+    use alloy_json_abi::JsonAbi;
+    use alloy_network::{AnyHeader, AnyRpcTransaction, AnyTxEnvelope};
+    use alloy_primitives::{
+        address, fixed_bytes, keccak256, Address, Bytes, LogData, Selector, B256, I256, U256,
+    };
+    use alloy_rpc_types::{Header, Log, Transaction as RpcTransaction};
+    use serde_json::json;
+
+    const TRANSFER_SIGNATURE: &str = "transfer(address to,uint256 amount)";
+    const TRANSFER_SELECTOR: Selector = fixed_bytes!("a9059cbb");
 
     fn create_test_transaction() -> RpcTransaction<AnyTxEnvelope> {
         let rpc_tx = r#"{
@@ -107,6 +118,395 @@ mod tests {
         serde_json::from_str::<RpcTransaction<AnyTxEnvelope>>(rpc_tx).unwrap()
     }
 
+    /// Same transaction as [`create_test_transaction`] calling `transfer(to, amount)` instead
+    fn create_transfer_transaction(to: Address, amount: U256) -> AnyRpcTransaction {
+        let call = CallDefinition::from_signature(TRANSFER_SIGNATURE).unwrap();
+        let input = call
+            .call
+            .abi_encode_input(&[DynSolValue::Address(to), DynSolValue::Uint(amount, 256)])
+            .unwrap();
+        create_call_transaction(input)
+    }
+
+    /// Log of `event` emitted by a test contract, `data` being its abi encoded non indexed params
+    fn create_log(event: &str, indexed: Vec<B256>, data: Vec<u8>) -> Log {
+        let mut topics = vec![keccak256(event)];
+        topics.extend(indexed);
+        Log {
+            inner: alloy_primitives::Log {
+                address: Address::repeat_byte(0x11),
+                data: LogData::new_unchecked(topics, data.into()),
+            },
+            ..Default::default()
+        }
+    }
+
+    /// Same transaction as [`create_test_transaction`] with the given call data
+    fn create_call_transaction(input: Vec<u8>) -> AnyRpcTransaction {
+        let mut rpc_tx = serde_json::to_value(create_test_transaction()).unwrap();
+        rpc_tx["input"] = json!(Bytes::from(input));
+        AnyRpcTransaction::new(serde_json::from_value(rpc_tx).unwrap())
+    }
+
+    fn call_data_filter(
+        parameters: Vec<DynFieldCondition>,
+        paths: Vec<DynFieldCondition>,
+    ) -> FilterNode {
+        FilterNode {
+            children: None,
+            value: Some(FilterCondition::Transaction(
+                TransactionCondition::CallData {
+                    signature: TRANSFER_SIGNATURE.to_string(),
+                    abi: None,
+                    paths,
+                    method_selector: TRANSFER_SELECTOR,
+                    parameters,
+                },
+            )),
+        }
+    }
+
+    #[test]
+    fn test_call_data_parameters() {
+        let engine = FilterEngine::new();
+        let to = Address::repeat_byte(0xaa);
+        let tx = Arc::new(create_transfer_transaction(to, U256::from(1000)));
+
+        let amount_gt = |threshold: u64| DynFieldCondition {
+            path: "amount".to_string(),
+            condition: ValueCondition::U256(NumericCondition::GreaterThan(U256::from(threshold))),
+        };
+        let to_eq = DynFieldCondition {
+            path: "to".to_string(),
+            condition: ValueCondition::String(StringCondition::EqualTo(format!("{to:#x}"))),
+        };
+
+        let filter = call_data_filter(vec![amount_gt(500), to_eq.clone()], vec![]);
+        assert!(engine.evaluate_with_context(&filter, tx.clone()));
+
+        let filter = call_data_filter(vec![amount_gt(5000)], vec![to_eq]);
+        assert!(!engine.evaluate_with_context(&filter, tx.clone()));
+
+        // Calldata of another method never matches
+        let filter = call_data_filter(vec![amount_gt(500)], vec![]);
+        let tx = Arc::new(AnyRpcTransaction::new(create_test_transaction()));
+        assert!(!engine.evaluate_with_context(&filter, tx));
+    }
+
+    #[test]
+    fn test_call_data_filter_builder() {
+        let engine = FilterEngine::new();
+        let to = Address::repeat_byte(0xaa);
+        let tx = Arc::new(create_transfer_transaction(to, U256::from(1000)));
+
+        let filter = FilterBuilder::new().transaction(|tx| {
+            tx.call_data(TRANSFER_SIGNATURE)
+                .params("amount")
+                .gt(U256::from(500));
+        });
+        assert!(engine.evaluate_with_context(&filter.filter_node(), tx.clone()));
+
+        let filter = FilterBuilder::new().transaction(|tx| {
+            tx.call_data("0xa9059cbb");
+        });
+        assert!(engine.evaluate_with_context(&filter.filter_node(), tx));
+    }
+
+    #[test]
+    fn test_call_data_array_parameters() {
+        let engine = FilterEngine::new();
+        let signature = "swapExactTokensForTokens(uint256 amountIn,uint256 amountOutMin,address[] path,address to,uint256 deadline)";
+        let (token_in, token_out) = (Address::repeat_byte(0xaa), Address::repeat_byte(0xbb));
+
+        let call = CallDefinition::from_signature(signature).unwrap();
+        let input = call
+            .call
+            .abi_encode_input(&[
+                DynSolValue::Uint(U256::from(1000), 256),
+                DynSolValue::Uint(U256::from(900), 256),
+                DynSolValue::Array(vec![
+                    DynSolValue::Address(token_in),
+                    DynSolValue::Address(token_out),
+                ]),
+                DynSolValue::Address(token_in),
+                DynSolValue::Uint(U256::from(1), 256),
+            ])
+            .unwrap();
+        let tx = Arc::new(create_call_transaction(input));
+
+        let filter = FilterBuilder::new().transaction(|tx| {
+            let mut call = tx.call_data(signature);
+            call.array_params("path")
+                .contains(token_out.to_string().to_lowercase());
+            call.path("path.0").exact(&token_in.to_string());
+        });
+        assert!(engine.evaluate_with_context(&filter.filter_node(), tx.clone()));
+
+        let filter = FilterBuilder::new().transaction(|tx| {
+            tx.call_data(signature)
+                .array_params("path")
+                .not_in(vec![token_out.to_string()]);
+        });
+        assert!(!engine.evaluate_with_context(&filter.filter_node(), tx));
+    }
+
+    #[test]
+    fn test_abi_call_filter() {
+        let engine = FilterEngine::new();
+        let abi = serde_json::from_str::<JsonAbi>(
+            r#"[{
+                "type": "function",
+                "name": "exactInputSingle",
+                "stateMutability": "payable",
+                "inputs": [{
+                    "name": "params",
+                    "type": "tuple",
+                    "components": [
+                        { "name": "tokenIn", "type": "address" },
+                        { "name": "fee", "type": "uint24" },
+                        { "name": "amountIn", "type": "uint256" }
+                    ]
+                }],
+                "outputs": [{ "name": "amountOut", "type": "uint256" }]
+            }]"#,
+        )
+        .unwrap();
+
+        let token = Address::repeat_byte(0xaa);
+        let call = CallDefinition::from_abi(&abi.function("exactInputSingle").unwrap()[0]).unwrap();
+        let input = call
+            .call
+            .abi_encode_input(&[DynSolValue::Tuple(vec![
+                DynSolValue::Address(token),
+                DynSolValue::Uint(U256::from(3000), 24),
+                DynSolValue::Uint(U256::from(1000), 256),
+            ])])
+            .unwrap();
+        let tx = Arc::new(create_call_transaction(input));
+
+        let filter = FilterBuilder::new().abi(abi.clone()).transaction(|tx| {
+            tx.abi_call("exactInputSingle")
+                .path("params.amountIn")
+                .gt(U256::from(500));
+        });
+        assert!(engine.evaluate_with_context(&filter.filter_node(), tx.clone()));
+
+        let filter = FilterBuilder::new().abi(abi).transaction(|tx| {
+            tx.abi_call("exactInputSingle")
+                .path("params.tokenIn")
+                .exact(&Address::repeat_byte(0xbb).to_string());
+        });
+        assert!(!engine.evaluate_with_context(&filter.filter_node(), tx));
+    }
+
+    #[test]
+    fn test_decoded_addresses_ignore_case() {
+        let engine = FilterEngine::new();
+        let to = address!("68b3465833fb72A70ecDF485E0e4C7bD8665Fc45");
+        let lowercase = "0x68b3465833fb72a70ecdf485e0e4c7bd8665fc45";
+        let tx = Arc::new(create_transfer_transaction(to, U256::from(1000)));
+
+        let filters = [
+            FilterBuilder::new().transaction(|tx| {
+                tx.call_data(TRANSFER_SIGNATURE)
+                    .params("to")
+                    .exact(lowercase);
+            }),
+            FilterBuilder::new().transaction(|tx| {
+                StringOps::in_set(
+                    tx.call_data(TRANSFER_SIGNATURE).path("to"),
+                    [to.to_string()],
+                );
+            }),
+        ];
+        for filter in filters {
+            assert!(engine.evaluate_with_context(&filter.filter_node(), tx.clone()));
+        }
+
+        let signature = "Transfer(address indexed from,address indexed to,uint256 value)";
+        let log = Arc::new(create_log(
+            "Transfer(address,address,uint256)",
+            vec![Address::ZERO.into_word(), to.into_word()],
+            U256::from(1000).to_be_bytes_vec(),
+        ));
+        let filters = [
+            FilterBuilder::new().event(|ev| {
+                ev.signature(signature).params("to").exact(lowercase);
+            }),
+            FilterBuilder::new().event(|ev| {
+                ev.signature(signature).params("to").exact(&to.to_string());
+            }),
+        ];
+        for filter in filters {
+            assert!(engine.evaluate_with_context(&filter.filter_node(), log.clone()));
+        }
+    }
+
+    #[test]
+    fn test_abi_event_numeric_parameters() {
+        let engine = FilterEngine::new();
+        let abi = JsonAbi::parse([
+            "event Swap(address indexed sender, address indexed recipient, int256 amount0, int256 amount1, uint160 sqrtPriceX96, uint128 liquidity, int24 tick)",
+        ])
+        .unwrap();
+        let sqrt_price = U256::from(1) << 96;
+        let data = DynSolValue::Tuple(vec![
+            DynSolValue::Int(I256::try_from(-5000).unwrap(), 256),
+            DynSolValue::Int(I256::try_from(2000).unwrap(), 256),
+            DynSolValue::Uint(sqrt_price, 160),
+            DynSolValue::Uint(U256::from(100), 128),
+            DynSolValue::Int(I256::try_from(-887).unwrap(), 24),
+        ])
+        .abi_encode_params();
+        let log = Arc::new(create_log(
+            "Swap(address,address,int256,int256,uint160,uint128,int24)",
+            vec![Address::ZERO.into_word(), Address::ZERO.into_word()],
+            data,
+        ));
+
+        let mut builder = FilterBuilder::new().abi(abi);
+        let matching = [
+            builder.event(|ev| {
+                ev.abi_event("Swap")
+                    .params("amount0")
+                    .lt(I256::try_from(-1000).unwrap());
+            }),
+            builder.event(|ev| {
+                ev.abi_event("Swap").params("amount1").gt(I256::ZERO);
+            }),
+            builder.event(|ev| {
+                ev.abi_event("Swap").params("sqrtPriceX96").gte(sqrt_price);
+            }),
+            builder.event(|ev| {
+                ev.abi_event("Swap")
+                    .params("tick")
+                    .between(I256::try_from(-1000).unwrap(), I256::ZERO);
+            }),
+            // Unsigned values are compared to signed conditions when they fit
+            builder.event(|ev| {
+                ev.abi_event("Swap")
+                    .params("liquidity")
+                    .eq(I256::try_from(100).unwrap());
+            }),
+        ];
+        for filter in matching {
+            assert!(engine.evaluate_with_context(&filter.filter_node(), log.clone()));
+        }
+
+        let unmatching = [
+            builder.event(|ev| {
+                ev.abi_event("Swap").params("amount0").gt(I256::ZERO);
+            }),
+            builder.event(|ev| {
+                ev.abi_event("Swap").params("sqrtPriceX96").gt(sqrt_price);
+            }),
+            builder.event(|ev| {
+                ev.abi_event("Swap").params("tick").gt(I256::ZERO);
+            }),
+            // Signed values are never compared to unsigned conditions
+            builder.event(|ev| {
+                ev.abi_event("Swap").params("tick").lt(U256::MAX);
+            }),
+        ];
+        for filter in unmatching {
+            assert!(!engine.evaluate_with_context(&filter.filter_node(), log.clone()));
+        }
+    }
+
+    #[test]
+    fn test_address_and_hash_ignore_case() {
+        let engine = FilterEngine::new();
+        let tx = Arc::new(AnyRpcTransaction::new(create_test_transaction()));
+
+        let filters = [
+            FilterBuilder::new().transaction(|tx| {
+                tx.to().exact("0x68b3465833fb72a70ecdf485e0e4c7bd8665fc45");
+            }),
+            FilterBuilder::new().transaction(|tx| {
+                tx.to().exact("0x68b3465833fb72A70ecDF485E0e4C7bD8665Fc45");
+                tx.from().starts_with("0x3CF412");
+            }),
+            FilterBuilder::new().transaction(|tx| {
+                tx.hash().matches("^0x0E07D8");
+                tx.block_hash()
+                    .exact("0x883F974B17CA7B28CB970798D1C80F4D4BB427473DC6D39B2A7FE24EDC02902D");
+            }),
+        ];
+        for filter in filters {
+            assert!(engine.evaluate_with_context(&filter.filter_node(), tx.clone()));
+        }
+
+        let result = FilterBuilder::new().try_transaction(|tx| {
+            tx.to().exact("0x68b3465833");
+        });
+        assert!(matches!(result, Err(FilterError::InvalidAddress { .. })));
+    }
+
+    #[test]
+    fn test_set_membership() {
+        let engine = FilterEngine::new();
+        let tx = Arc::new(AnyRpcTransaction::new(create_test_transaction()));
+
+        let mut watchlist: Vec<String> = (0..50_000u64)
+            .map(|i| format!("{:#x}", Address::left_padding_from(&i.to_be_bytes())))
+            .collect();
+
+        let filter = FilterBuilder::new().transaction(|tx| {
+            tx.to().in_set(&watchlist);
+        });
+        assert!(!engine.evaluate_with_context(&filter.filter_node(), tx.clone()));
+
+        watchlist.push("0x68b3465833fb72A70ecDF485E0e4C7bD8665Fc45".to_string());
+        let filter = FilterBuilder::new().transaction(|tx| {
+            tx.to().in_set(&watchlist);
+            tx.nonce().not_in_set([0, 1, 2]);
+        });
+        assert!(engine.evaluate_with_context(&filter.filter_node(), tx.clone()));
+    }
+
+    #[test]
+    fn test_block_header_fields() {
+        let engine = FilterEngine::new();
+        let header = Arc::new(Header {
+            hash: B256::repeat_byte(0xab),
+            inner: AnyHeader {
+                number: 21_000_000,
+                beneficiary: address!("95222290DD7278Aa3Ddd389Cc1E1d165CC4BAfe5"),
+                extra_data: Bytes::from_static(b"beaverbuild.org"),
+                mix_hash: Some(B256::repeat_byte(0x01)),
+                blob_gas_used: Some(393_216),
+                excess_blob_gas: Some(0),
+                ..Default::default()
+            },
+            size: Some(U256::from(120_000)),
+            ..Default::default()
+        });
+
+        let filter = FilterBuilder::new().block_header(|b| {
+            b.miner()
+                .exact("0x95222290dd7278aa3ddd389cc1e1d165cc4bafe5");
+            b.hash().starts_with("0xABAB");
+            b.blob_gas_used().gte(131_072);
+            b.excess_blob_gas().eq(0);
+            b.size().gt(U256::from(100_000));
+            // "beaverbuild" in hex
+            b.extra_data().starts_with("0x6265617665726275696c64");
+            b.prevrandao().exact(&B256::repeat_byte(0x01).to_string());
+        });
+        assert!(engine.evaluate_with_context(&filter.filter_node(), header.clone()));
+
+        // Pre-Cancun blocks have no blob gas fields and never match them
+        let pre_cancun = Arc::new(Header {
+            inner: AnyHeader::default(),
+            ..Default::default()
+        });
+        let filter = FilterBuilder::new().block_header(|b| {
+            b.blob_gas_used().lt(u64::MAX);
+        });
+        assert!(engine.evaluate_with_context(&filter.filter_node(), header));
+        assert!(!engine.evaluate_with_context(&filter.filter_node(), pre_cancun));
+    }
+
     #[test]
     fn test_matching_complex_condition() {
         let engine = FilterEngine::new();
@@ -125,9 +525,8 @@ mod tests {
                                 FilterNode {
                                     children: None,
                                     value: Some(FilterCondition::Transaction(
-                                        TransactionCondition::To(StringCondition::EqualTo(
-                                            "0x68b3465833fb72a70ecdf485e0e4c7bd8665fc45"
-                                                .to_string(),
+                                        TransactionCondition::To(BytesCondition::EqualTo(
+                                            address!("68b3465833fb72a70ecdf485e0e4c7bd8665fc45"),
                                         )),
                                     )),
                                 },
@@ -188,9 +587,9 @@ mod tests {
                     FilterNode {
                         children: None,
                         value: Some(FilterCondition::Transaction(TransactionCondition::To(
-                            StringCondition::EqualTo(
-                                "0x68b3465833fb72a70ecdf485e0e4c7bd8665fc45".to_string(),
-                            ),
+                            BytesCondition::EqualTo(address!(
+                                "7a250d5630b4cf539739df2c5dacb4c659f2488d"
+                            )),
                         ))),
                     },
                     FilterNode {
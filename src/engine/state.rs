@@ -1,4 +1,3 @@
-use alloy_primitives::map::HashMap;
 use dashmap::DashMap;
 use serde_json::Value;
 use std::sync::Arc;
@@ -24,8 +23,8 @@ pub enum DecodedData {
 pub struct DecodedContractCall {
     /// Name of the called contract method
     pub(crate) method: String,
-    /// Decoded parameters as key-value pairs
-    pub(crate) parameters: HashMap<String, Value>,
+    /// Decoded parameters as a json object keyed by parameter name
+    pub(crate) parameters: Value,
 }
 
 /// Holds shared state for decoded data caching
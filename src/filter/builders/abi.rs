@@ -0,0 +1,82 @@
+use alloy_json_abi::{Event, Function, JsonAbi};
+
+use crate::filter::error::FilterError;
+
+/// Looks up a function of the registered ABI by name, or by signature (e.g.
+/// `swap(uint256,address)`) for overloaded functions.
+pub(crate) fn abi_function(abi: Option<&JsonAbi>, name: &str) -> Result<Function, FilterError> {
+    let abi = abi.ok_or_else(|| FilterError::MissingAbi {
+        name: name.to_string(),
+    })?;
+    lookup(abi.functions(), name, |f| &f.name, Function::signature)
+}
+
+/// Looks up an event of the registered ABI by name, or by signature (e.g.
+/// `Transfer(address,address,uint256)`) for overloaded events.
+pub(crate) fn abi_event(abi: Option<&JsonAbi>, name: &str) -> Result<Event, FilterError> {
+    let abi = abi.ok_or_else(|| FilterError::MissingAbi {
+        name: name.to_string(),
+    })?;
+    lookup(abi.events(), name, |e| &e.name, Event::signature)
+}
+
+fn lookup<'a, T: Clone + 'a>(
+    items: impl Iterator<Item = &'a T>,
+    name: &str,
+    item_name: impl Fn(&T) -> &str,
+    signature: impl Fn(&T) -> String,
+) -> Result<T, FilterError> {
+    let matches = items
+        .filter(|item| item_name(item) == name || signature(item) == name)
+        .collect::<Vec<_>>();
+
+    match matches.as_slice() {
+        [item] => Ok((*item).clone()),
+        [] => Err(FilterError::AbiItemNotFound {
+            name: name.to_string(),
+        }),
+        [item, ..] => Err(FilterError::AmbiguousAbiItem {
+            name: name.to_string(),
+            signature: signature(item),
+        }),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn abi() -> JsonAbi {
+        JsonAbi::parse([
+            "function swap(uint256 amount)",
+            "function swap(uint256 amount, address to)",
+            "function exactInputSingle((address, uint256) params)",
+            "event Transfer(address indexed from, address indexed to, uint256 value)",
+        ])
+        .unwrap()
+    }
+
+    #[test]
+    fn test_abi_lookup() {
+        let abi = abi();
+
+        let function = abi_function(Some(&abi), "exactInputSingle").unwrap();
+        assert_eq!(function.signature(), "exactInputSingle((address,uint256))");
+        let function = abi_function(Some(&abi), "swap(uint256,address)").unwrap();
+        assert_eq!(function.inputs.len(), 2);
+        assert!(abi_event(Some(&abi), "Transfer").is_ok());
+
+        assert!(matches!(
+            abi_function(Some(&abi), "swap"),
+            Err(FilterError::AmbiguousAbiItem { .. })
+        ));
+        assert!(matches!(
+            abi_event(Some(&abi), "Approval"),
+            Err(FilterError::AbiItemNotFound { .. })
+        ));
+        assert!(matches!(
+            abi_event(None, "Transfer"),
+            Err(FilterError::MissingAbi { .. })
+        ));
+    }
+}
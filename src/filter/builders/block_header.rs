@@ -1,9 +1,10 @@
 // Block Header builder
 use crate::filter::{
     conditions::{BlockHeaderCondition, FilterCondition, FilterNode, NodeBuilder},
+    error::FilterError,
     field::{
-        BlockField, DynField, DynValueFieldType, FieldWrapper, StringFieldType, U256FieldType,
-        U64FieldType,
+        AddressFieldType, BlockField, DynField, DynValueFieldType, FieldWrapper, HashFieldType,
+        StringFieldType, U256FieldType, U64FieldType,
     },
 };
 
@@ -12,6 +13,7 @@ use super::builder_ops::FilterBuilderOps;
 // ===== BlockHeader Builder =====
 pub struct BlockHeaderBuilder {
     pub(crate) nodes: Vec<FilterNode>,
+    pub(crate) errors: Vec<FilterError>,
 }
 
 impl NodeBuilder for BlockHeaderBuilder {
@@ -23,6 +25,10 @@ impl NodeBuilder for BlockHeaderBuilder {
             value: Some(FilterCondition::BlockHeader(condition)),
         })
     }
+
+    fn append_error(&mut self, error: FilterError) {
+        self.errors.push(error);
+    }
 }
 
 #[allow(dead_code)]
@@ -34,7 +40,10 @@ impl Default for BlockHeaderBuilder {
 
 impl BlockHeaderBuilder {
     pub fn new() -> Self {
-        Self { nodes: Vec::new() }
+        Self {
+            nodes: Vec::new(),
+            errors: Vec::new(),
+        }
     }
     pub fn number(&mut self) -> FieldWrapper<'_, U64FieldType<BlockField>, Self> {
         FieldWrapper {
@@ -78,9 +87,23 @@ impl BlockHeaderBuilder {
         }
     }
 
-    pub fn hash(&mut self) -> FieldWrapper<'_, StringFieldType<BlockField>, Self> {
+    pub fn blob_gas_used(&mut self) -> FieldWrapper<'_, U64FieldType<BlockField>, Self> {
+        FieldWrapper {
+            field: U64FieldType(BlockField::BlobGasUsed),
+            parent: self,
+        }
+    }
+
+    pub fn excess_blob_gas(&mut self) -> FieldWrapper<'_, U64FieldType<BlockField>, Self> {
+        FieldWrapper {
+            field: U64FieldType(BlockField::ExcessBlobGas),
+            parent: self,
+        }
+    }
+
+    pub fn hash(&mut self) -> FieldWrapper<'_, HashFieldType<BlockField>, Self> {
         FieldWrapper {
-            field: StringFieldType(BlockField::Hash),
+            field: HashFieldType(BlockField::Hash),
             parent: self,
         }
     }
@@ -92,9 +115,10 @@ impl BlockHeaderBuilder {
         }
     }
 
-    pub fn miner(&mut self) -> FieldWrapper<'_, StringFieldType<BlockField>, Self> {
+    /// Address receiving the block rewards, i.e. the `beneficiary` / fee recipient
+    pub fn miner(&mut self) -> FieldWrapper<'_, AddressFieldType<BlockField>, Self> {
         FieldWrapper {
-            field: StringFieldType(BlockField::Miner),
+            field: AddressFieldType(BlockField::Miner),
             parent: self,
         }
     }
@@ -120,6 +144,42 @@ impl BlockHeaderBuilder {
         }
     }
 
+    pub fn withdrawals_root(&mut self) -> FieldWrapper<'_, HashFieldType<BlockField>, Self> {
+        FieldWrapper {
+            field: HashFieldType(BlockField::WithdrawalsRoot),
+            parent: self,
+        }
+    }
+
+    pub fn parent_beacon_block_root(
+        &mut self,
+    ) -> FieldWrapper<'_, HashFieldType<BlockField>, Self> {
+        FieldWrapper {
+            field: HashFieldType(BlockField::ParentBeaconBlockRoot),
+            parent: self,
+        }
+    }
+
+    /// Extra data of the block as a `0x` prefixed lowercase hex string
+    pub fn extra_data(&mut self) -> FieldWrapper<'_, StringFieldType<BlockField>, Self> {
+        FieldWrapper {
+            field: StringFieldType(BlockField::ExtraData),
+            parent: self,
+        }
+    }
+
+    pub fn mix_hash(&mut self) -> FieldWrapper<'_, HashFieldType<BlockField>, Self> {
+        FieldWrapper {
+            field: HashFieldType(BlockField::MixHash),
+            parent: self,
+        }
+    }
+
+    /// Alias for `mix_hash`, which holds the beacon chain randomness since the merge
+    pub fn prevrandao(&mut self) -> FieldWrapper<'_, HashFieldType<BlockField>, Self> {
+        self.mix_hash()
+    }
+
     pub fn field(&mut self, path: &str) -> FieldWrapper<'_, DynValueFieldType<DynField>, Self> {
         FieldWrapper {
             field: DynValueFieldType(DynField(path.to_string())),
@@ -130,21 +190,29 @@ impl BlockHeaderBuilder {
 
 impl FilterBuilderOps for BlockHeaderBuilder {
     fn new() -> Self {
-        Self { nodes: Vec::new() }
+        Self {
+            nodes: Vec::new(),
+            errors: Vec::new(),
+        }
     }
 
     fn take_nodes(&mut self) -> Vec<FilterNode> {
         std::mem::take(&mut self.nodes)
     }
+
+    fn take_errors(&mut self) -> Vec<FilterError> {
+        std::mem::take(&mut self.errors)
+    }
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
     use crate::filter::{
-        conditions::{FilterCondition, NumericCondition, StringCondition},
+        conditions::{BytesCondition, FilterCondition, NumericCondition, StringCondition},
         NumericOps, StringOps,
     };
+    use alloy_primitives::U256;
 
     const NUMBER: u64 = 1;
     const GAS_USED: u64 = 2000;
@@ -152,6 +220,8 @@ mod tests {
     const TIMESTAMP: u64 = 5000;
 
     const HASH: &str = "0x123";
+    const MINER: &str = "0x95222290DD7278Aa3Ddd389Cc1E1d165CC4BAfe5";
+    const ROOT: &str = "0x4f3a1c9e2b7d8a6f5e0c1b2a3d4e5f60718293a4b5c6d7e8f90a1b2c3d4e5f6a";
     const PREFIX: &str = "0x";
     const SUFFIX: &str = "def";
 
@@ -242,6 +312,34 @@ mod tests {
         assert_eq!(builder.nodes, expected_nodes);
     }
 
+    #[test]
+    fn test_typed_field_operations() {
+        let mut builder = BlockHeaderBuilder::new();
+        builder.miner().exact(MINER);
+        builder.hash().starts_with("0xABC");
+        builder.prevrandao().exact(ROOT);
+        builder.blob_gas_used().gt(0);
+        builder.size().lt(U256::from(1024));
+        builder.extra_data().contains("6265617665");
+
+        let expected_nodes = [
+            BlockHeaderCondition::Miner(BytesCondition::EqualTo(MINER.parse().unwrap())),
+            BlockHeaderCondition::Hash(BytesCondition::Hex(StringCondition::StartsWith(
+                "0xabc".to_string(),
+            ))),
+            BlockHeaderCondition::MixHash(BytesCondition::EqualTo(ROOT.parse().unwrap())),
+            BlockHeaderCondition::BlobGasUsed(NumericCondition::GreaterThan(0)),
+            BlockHeaderCondition::Size(NumericCondition::LessThan(U256::from(1024))),
+            BlockHeaderCondition::ExtraData(StringCondition::Contains("6265617665".to_string())),
+        ]
+        .map(|condition| FilterNode {
+            children: None,
+            value: Some(FilterCondition::BlockHeader(condition)),
+        });
+
+        assert_eq!(builder.nodes, expected_nodes);
+    }
+
     #[test]
     fn builder_new() {
         let builder = BlockHeaderBuilder::new();
@@ -1,17 +1,23 @@
 use std::sync::Arc;
 
+use alloy_json_abi::JsonAbi;
+
 use super::{
-    block_header::BlockHeaderBuilder, event::EventBuilder, pool::PoolBuilder,
-    transaction::TxBuilder,
+    block_header::BlockHeaderBuilder, builder_ops::FilterBuilderOps, event::EventBuilder,
+    pool::PoolBuilder, transaction::TxBuilder,
 };
 use crate::{
     config::Chain,
-    filter::conditions::{EventType, Filter, FilterNode, LogicalOp},
+    filter::{
+        conditions::{EventType, Filter, FilterNode, LogicalOp},
+        error::FilterError,
+    },
 };
 
 /// FilterBuilder allows constructing complex filter conditions using a builder pattern.
 pub struct FilterBuilder {
     chain: Chain,
+    abi: Option<Arc<JsonAbi>>,
 }
 
 #[allow(dead_code)]
@@ -27,98 +33,138 @@ impl FilterBuilder {
         Self {
             // default chain is `Ethereum`
             chain: Chain::Ethereum,
+            abi: None,
         }
     }
     /// Adds transaction conditions to the filter.
     ///
     /// Returns a [`Filter`] after  configuration is completed.
+    ///
+    /// # Panics
+    /// Panics if a condition is invalid, see [`FilterBuilder::try_transaction`].
     pub fn transaction<F>(&mut self, f: F) -> Filter
     where
         F: FnOnce(&mut TxBuilder),
     {
-        let mut builder = TxBuilder::new();
-        f(&mut builder);
-
-        let filter_node = FilterNode {
-            children: Some((LogicalOp::And, builder.nodes)),
-            value: None,
-        }
-        .optimize();
+        self.try_transaction(f)
+            .unwrap_or_else(|e| panic!("invalid transaction filter: {e}"))
+    }
 
-        Filter::new(
-            self.chain.clone(),
-            Arc::new(filter_node),
-            Some(EventType::Transaction),
-        )
+    /// Adds transaction conditions to the filter.
+    ///
+    /// Returns a [`Filter`] after configuration is completed, or the first [`FilterError`]
+    /// raised by its conditions (e.g. a malformed call data signature).
+    pub fn try_transaction<F>(&mut self, f: F) -> Result<Filter, FilterError>
+    where
+        F: FnOnce(&mut TxBuilder),
+    {
+        let mut builder = TxBuilder::with_abi(self.abi.clone());
+        f(&mut builder);
+        self.build(builder, EventType::Transaction)
     }
 
     /// Adds event(logs) conditions to the filter.
     ///
     /// Returns a [`Filter`] after  configuration is completed.
+    ///
+    /// # Panics
+    /// Panics if a condition is invalid, see [`FilterBuilder::try_event`].
     pub fn event<F>(&mut self, f: F) -> Filter
     where
         F: FnOnce(&mut EventBuilder),
     {
-        let mut builder = EventBuilder::new();
-        f(&mut builder);
-
-        let filter_node = FilterNode {
-            children: Some((LogicalOp::And, builder.nodes)),
-            value: None,
-        }
-        .optimize();
+        self.try_event(f)
+            .unwrap_or_else(|e| panic!("invalid event filter: {e}"))
+    }
 
-        Filter::new(
-            self.chain.clone(),
-            Arc::new(filter_node),
-            Some(EventType::Transaction),
-        )
+    /// Adds event(logs) conditions to the filter.
+    ///
+    /// Returns a [`Filter`] after configuration is completed, or the first [`FilterError`]
+    /// raised by its conditions (e.g. a malformed event signature).
+    pub fn try_event<F>(&mut self, f: F) -> Result<Filter, FilterError>
+    where
+        F: FnOnce(&mut EventBuilder),
+    {
+        let mut builder = EventBuilder::with_abi(self.abi.clone());
+        f(&mut builder);
+        self.build(builder, EventType::LogEvent)
     }
 
     /// Adds pool conditions to the filter.
     ///
     /// Returns a [`Filter`] after  configuration is completed.
+    ///
+    /// # Panics
+    /// Panics if a condition is invalid, see [`FilterBuilder::try_pool`].
     pub fn pool<F>(&mut self, f: F) -> Filter
+    where
+        F: FnOnce(&mut PoolBuilder),
+    {
+        self.try_pool(f)
+            .unwrap_or_else(|e| panic!("invalid pool filter: {e}"))
+    }
+
+    /// Adds pool conditions to the filter.
+    ///
+    /// Returns a [`Filter`] after configuration is completed, or the first [`FilterError`]
+    /// raised by its conditions (e.g. a malformed regex).
+    pub fn try_pool<F>(&mut self, f: F) -> Result<Filter, FilterError>
     where
         F: FnOnce(&mut PoolBuilder),
     {
         let mut builder = PoolBuilder::new();
         f(&mut builder);
-
-        let filter_node = FilterNode {
-            children: Some((LogicalOp::And, builder.nodes)),
-            value: None,
-        }
-        .optimize();
-
-        Filter::new(
-            self.chain.clone(),
-            Arc::new(filter_node),
-            Some(EventType::Pool),
-        )
+        self.build(builder, EventType::Pool)
     }
 
     /// Adds block header conditions to the filter.
     ///
     /// Returns a [`Filter`] after  configuration is completed.
+    ///
+    /// # Panics
+    /// Panics if a condition is invalid, see [`FilterBuilder::try_block_header`].
     pub fn block_header<F>(&mut self, f: F) -> Filter
+    where
+        F: FnOnce(&mut BlockHeaderBuilder),
+    {
+        self.try_block_header(f)
+            .unwrap_or_else(|e| panic!("invalid block header filter: {e}"))
+    }
+
+    /// Adds block header conditions to the filter.
+    ///
+    /// Returns a [`Filter`] after configuration is completed, or the first [`FilterError`]
+    /// raised by its conditions (e.g. an operation a field doesn't support).
+    pub fn try_block_header<F>(&mut self, f: F) -> Result<Filter, FilterError>
     where
         F: FnOnce(&mut BlockHeaderBuilder),
     {
         let mut builder = BlockHeaderBuilder::new();
         f(&mut builder);
+        self.build(builder, EventType::BlockHeader)
+    }
+
+    /// Builds the [`Filter`] from the conditions of a builder, failing on the first error it raised.
+    fn build<B: FilterBuilderOps>(
+        &self,
+        mut builder: B,
+        event_type: EventType,
+    ) -> Result<Filter, FilterError> {
+        if let Some(error) = builder.take_errors().into_iter().next() {
+            return Err(error);
+        }
 
         let filter_node = FilterNode {
-            children: Some((LogicalOp::And, builder.nodes)),
+            children: Some((LogicalOp::And, builder.take_nodes())),
             value: None,
         }
         .optimize();
 
-        Filter::new(
+        Ok(Filter::new(
             self.chain.clone(),
             Arc::new(filter_node),
-            Some(EventType::BlockHeader),
-        )
+            Some(event_type),
+        ))
     }
 
     /// ====== Layer 1 & Layer 2 context ========
@@ -127,6 +173,13 @@ impl FilterBuilder {
         self.chain = chain;
         self
     }
+
+    /// Registers a contract JSON ABI, replacing any previously registered one, so methods and
+    /// events can be filtered by name with `abi_call` and `abi_event`.
+    pub fn abi(mut self, abi: JsonAbi) -> Self {
+        self.abi = Some(Arc::new(abi));
+        self
+    }
 }
 
 #[cfg(test)]
@@ -134,14 +187,15 @@ mod tests {
     use super::*;
     use crate::filter::{
         conditions::{
-            BlockHeaderCondition, EventCondition, FilterCondition, NumericCondition, PoolCondition,
-            StringCondition, TransactionCondition,
+            BlockHeaderCondition, BytesCondition, EventCondition, FilterCondition,
+            NumericCondition, PoolCondition, StringCondition, TransactionCondition,
         },
-        NumericOps, StringOps,
+        field::{BlockField, PoolField, StringFieldCondition, U64FieldCondition},
+        LogicalOps, NumericOps, StringOps,
     };
-    use alloy_primitives::U256;
+    use alloy_primitives::{I256, U256};
 
-    const ADDRESS: &str = "0x123456789";
+    const ADDRESS: &str = "0x742d35Cc6634C0532925a3b844Bc454e4438f44e";
     const BLOCK_HASH: &str = "0xabcdef123456";
     const BASE_VALUE: u64 = 100;
 
@@ -163,7 +217,7 @@ mod tests {
 
                 match &nodes[0].value {
                     Some(FilterCondition::Transaction(TransactionCondition::From(cond))) => {
-                        assert_eq!(*cond, StringCondition::EqualTo(ADDRESS.to_string()));
+                        assert_eq!(*cond, BytesCondition::EqualTo(ADDRESS.parse().unwrap()));
                     }
                     _ => panic!("Expected Transaction From condition"),
                 }
@@ -183,12 +237,13 @@ mod tests {
     fn test_event_filter() {
         let mut builder = FilterBuilder::new();
 
-        let node = builder
-            .event(|ev| {
-                ev.contract().exact(ADDRESS);
-                ev.block_number().gt(BASE_VALUE);
-            })
-            .filter_node();
+        let filter = builder.event(|ev| {
+            ev.contract().exact(ADDRESS);
+            ev.block_number().gt(BASE_VALUE);
+        });
+        assert_eq!(filter.event_type(), Some(EventType::LogEvent));
+
+        let node = filter.filter_node();
 
         match &node.children {
             Some((op, nodes)) => {
@@ -197,7 +252,7 @@ mod tests {
 
                 match &nodes[0].value {
                     Some(FilterCondition::Event(EventCondition::Contract(cond))) => {
-                        assert_eq!(*cond, StringCondition::EqualTo(ADDRESS.to_string()));
+                        assert_eq!(*cond, BytesCondition::EqualTo(ADDRESS.parse().unwrap()));
                     }
                     _ => unreachable!(),
                 }
@@ -236,7 +291,7 @@ mod tests {
 
                 match &nodes[0].value {
                     Some(FilterCondition::Pool(PoolCondition::From(cond))) => {
-                        assert_eq!(*cond, StringCondition::EqualTo(ADDRESS.to_string()));
+                        assert_eq!(*cond, BytesCondition::EqualTo(ADDRESS.parse().unwrap()));
                     }
                     _ => unreachable!(),
                 }
@@ -308,6 +363,162 @@ mod tests {
         }
     }
 
+    #[test]
+    fn test_invalid_signature() {
+        let mut builder = FilterBuilder::new();
+
+        let result = builder.try_transaction(|tx| {
+            tx.value().gt(U256::from(BASE_VALUE));
+            tx.any_of(|tx| {
+                tx.call_data("transfer(address,uint256")
+                    .params("1")
+                    .gt(U256::from(1));
+            });
+        });
+        assert!(matches!(result, Err(FilterError::InvalidSignature { .. })));
+
+        let result = builder.try_event(|ev| {
+            ev.signature("Transfer(address indexed from,address indexed to,uint256 value)");
+        });
+        assert!(result.is_ok());
+    }
+
+    #[test]
+    fn test_invalid_regex() {
+        let mut builder = FilterBuilder::new();
+
+        let filter = builder
+            .try_transaction(|tx| {
+                tx.to().matches("^0x(dead|beef)");
+            })
+            .unwrap();
+        match &filter.filter_node().value {
+            Some(FilterCondition::Transaction(TransactionCondition::To(BytesCondition::Hex(
+                StringCondition::Matches(pattern),
+            )))) => assert!(pattern.is_match("0xDEAD")),
+            _ => panic!("Expected Transaction To condition"),
+        }
+
+        let result = builder.try_transaction(|tx| {
+            tx.or(|tx| {
+                tx.to().matches("^0x(dead");
+                tx.from().exact(ADDRESS);
+            });
+        });
+        assert!(matches!(result, Err(FilterError::InvalidRegex { .. })));
+    }
+
+    #[test]
+    fn test_unsupported_operation() {
+        let mut builder = FilterBuilder::new();
+
+        let result = BlockHeaderCondition::try_from(StringFieldCondition(
+            BlockField::Miner,
+            StringCondition::EqualTo("0xdead".to_string()),
+        ));
+        assert_eq!(
+            result.err(),
+            Some(FilterError::UnsupportedOperation {
+                field: "BlockField::Miner".to_string(),
+                operation: "string".to_string(),
+            })
+        );
+
+        let result = PoolCondition::try_from(U64FieldCondition(
+            PoolField::Age,
+            NumericCondition::GreaterThan(100),
+        ));
+        assert_eq!(
+            result.err().map(|e| e.to_string()),
+            Some("`PoolField::Age` does not support u64 numeric conditions".to_string())
+        );
+
+        let result = builder.try_pool(|p| {
+            p.timestamp().gt(100);
+        });
+        assert!(result.is_ok());
+
+        let result = builder.try_block_header(|b| {
+            b.parent_hash().starts_with("0x");
+        });
+        assert!(result.is_ok());
+    }
+
+    #[test]
+    #[should_panic(expected = "invalid pool filter")]
+    fn test_invalid_regex_panics() {
+        FilterBuilder::new().pool(|pool| {
+            pool.from().matches("[0-9");
+        });
+    }
+
+    #[test]
+    #[should_panic(expected = "invalid event filter")]
+    fn test_invalid_signature_panics() {
+        FilterBuilder::new().event(|ev| {
+            ev.signature("Transfer(address indexed from,,uint256 value)");
+        });
+    }
+
+    #[test]
+    fn test_abi_filter() {
+        let abi = JsonAbi::parse([
+            "function exactInputSingle((address, uint24, uint256) params)",
+            "event Swap(address indexed sender, int256 amount0, int256 amount1)",
+        ])
+        .unwrap();
+        let mut builder = FilterBuilder::new().abi(abi);
+
+        let filter = builder
+            .try_event(|ev| {
+                ev.abi_event("Swap").params("amount0").gt(I256::ONE);
+            })
+            .unwrap();
+        match &filter.filter_node().value {
+            Some(FilterCondition::Event(EventCondition::EventData {
+                abi: Some(event),
+                topic_0,
+                parameters,
+                ..
+            })) => {
+                assert_eq!(*topic_0, event.selector());
+                assert_eq!(parameters.len(), 1);
+            }
+            _ => panic!("Expected event data condition"),
+        }
+
+        // The ABI is available in nested groups
+        let filter = builder
+            .try_transaction(|tx| {
+                tx.any_of(|tx| {
+                    tx.abi_call("exactInputSingle")
+                        .path("params.2")
+                        .gt(U256::from(BASE_VALUE));
+                    tx.value().gt(U256::from(BASE_VALUE));
+                });
+            })
+            .unwrap();
+        match &filter.filter_node().children {
+            Some((LogicalOp::Or, nodes)) => assert!(matches!(
+                &nodes[0].value,
+                Some(FilterCondition::Transaction(
+                    TransactionCondition::CallData { abi: Some(_), .. }
+                ))
+            )),
+            _ => panic!("Expected group in node"),
+        }
+
+        let result = builder.try_event(|ev| {
+            ev.abi_event("Transfer");
+        });
+        assert!(matches!(result, Err(FilterError::AbiItemNotFound { .. })));
+
+        let result = FilterBuilder::new().try_transaction(|tx| {
+            tx.abi_call("exactInputSingle");
+        });
+        assert!(matches!(result, Err(FilterError::MissingAbi { .. })));
+    }
+
     #[test]
     fn test_empty_filter() {
         let mut builder = FilterBuilder::new();
@@ -1,5 +1,13 @@
-use crate::filter::conditions::FilterNode;
+use crate::filter::{conditions::FilterNode, error::FilterError};
 pub trait FilterBuilderOps {
     fn new() -> Self;
+    /// Creates the builder of a nested logical group, sharing the context of this builder.
+    fn new_group(&self) -> Self
+    where
+        Self: Sized,
+    {
+        Self::new()
+    }
     fn take_nodes(&mut self) -> Vec<FilterNode>;
+    fn take_errors(&mut self) -> Vec<FilterError>;
 }
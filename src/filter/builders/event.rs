@@ -1,16 +1,24 @@
 use crate::filter::{
     conditions::{ContractCondition, EventCondition, FilterCondition, FilterNode, NodeBuilder},
+    error::FilterError,
     field::{
-        ArrayFieldType, ContractField, DynValueFieldType, EventField, FieldWrapper,
-        StringFieldType, U64FieldType,
+        AddressFieldType, ArrayFieldType, ContractField, DynValueFieldType, EventField,
+        FieldWrapper, StringFieldType, U64FieldType,
     },
 };
 
-use super::builder_ops::FilterBuilderOps;
+use super::{abi::abi_event, builder_ops::FilterBuilderOps};
+use crate::utils::decoder::EventDefinition;
+use alloy_json_abi::{Event, JsonAbi};
+use alloy_primitives::B256;
+use std::sync::Arc;
 
 // ===== Event Builder ========
 pub struct EventBuilder {
     pub(crate) nodes: Vec<FilterNode>,
+    pub(crate) errors: Vec<FilterError>,
+    /// Contract ABI used to look up events by name
+    pub(crate) abi: Option<Arc<JsonAbi>>,
 }
 
 impl NodeBuilder for EventBuilder {
@@ -22,6 +30,10 @@ impl NodeBuilder for EventBuilder {
             value: Some(FilterCondition::Event(condition)),
         })
     }
+
+    fn append_error(&mut self, error: FilterError) {
+        self.errors.push(error);
+    }
 }
 
 #[allow(dead_code)]
@@ -33,12 +45,20 @@ impl Default for EventBuilder {
 
 impl EventBuilder {
     pub fn new() -> Self {
-        Self { nodes: Vec::new() }
+        Self::with_abi(None)
     }
 
-    pub fn contract(&mut self) -> FieldWrapper<'_, StringFieldType<EventField>, Self> {
+    pub(crate) fn with_abi(abi: Option<Arc<JsonAbi>>) -> Self {
+        Self {
+            nodes: Vec::new(),
+            errors: Vec::new(),
+            abi,
+        }
+    }
+
+    pub fn contract(&mut self) -> FieldWrapper<'_, AddressFieldType<EventField>, Self> {
         FieldWrapper {
-            field: StringFieldType(EventField::Contract),
+            field: AddressFieldType(EventField::Contract),
             parent: self,
         }
     }
@@ -92,25 +112,42 @@ impl EventBuilder {
         }
     }
 
-    pub fn signature(&mut self, signature: &str) -> SignatureEventBuilder<Self> {
+    pub fn signature(&mut self, signature: &str) -> SignatureEventBuilder<'_, Self> {
         SignatureEventBuilder::new(self, signature.to_string())
     }
+
+    /// Matches logs of an event of the registered contract ABI, by name or by signature
+    /// (e.g. `Transfer(address,address,uint256)`) for overloaded events.
+    pub fn abi_event(&mut self, name: &str) -> SignatureEventBuilder<'_, Self> {
+        SignatureEventBuilder::from_abi(self, name)
+    }
 }
 
 impl FilterBuilderOps for EventBuilder {
     fn new() -> Self {
-        Self { nodes: Vec::new() }
+        Self::with_abi(None)
+    }
+
+    fn new_group(&self) -> Self {
+        Self::with_abi(self.abi.clone())
     }
 
     fn take_nodes(&mut self) -> Vec<FilterNode> {
         std::mem::take(&mut self.nodes)
     }
+
+    fn take_errors(&mut self) -> Vec<FilterError> {
+        std::mem::take(&mut self.errors)
+    }
 }
 
+/// [`SignatureEventBuilder`] matches logs of the event with the given signature, optionally
+/// with conditions on its decoded parameters.
 #[allow(dead_code)]
 pub struct SignatureEventBuilder<'a, B> {
     parent: &'a mut B,
     signature: String,
+    /// Index of the event data node in the parent, `None` if the signature is invalid
     parameter_current_index: Option<usize>,
 }
 
@@ -120,35 +157,88 @@ impl NodeBuilder for SignatureEventBuilder<'_, EventBuilder> {
     fn append_node(&mut self, condition: ContractCondition) {
         match condition {
             ContractCondition::Parameter(param, parameter_condition) => {
-                if let Some(idx) = self.parameter_current_index {
-                    if let Some(node) = self.parent.nodes.get_mut(idx) {
-                        if let Some(FilterCondition::Event(EventCondition::EventData {
-                            parameters,
-                            ..
-                        })) = node.value.as_mut()
-                        {
-                            parameters.push((param, parameter_condition));
-                        }
+                let Some(idx) = self.parameter_current_index else {
+                    return;
+                };
+                if let Some(node) = self.parent.nodes.get_mut(idx) {
+                    if let Some(FilterCondition::Event(EventCondition::EventData {
+                        parameters,
+                        ..
+                    })) = node.value.as_mut()
+                    {
+                        parameters.push((param, parameter_condition));
                     }
-                } else {
-                    self.parent.append_node(EventCondition::EventData {
-                        signature: self.signature.clone(),
-                        parameters: vec![(param, parameter_condition)],
-                    });
-                    self.parameter_current_index = Some(self.parent.nodes.len() - 1);
                 }
             }
             ContractCondition::Path(_, _) => (),
         };
     }
+
+    fn append_error(&mut self, error: FilterError) {
+        self.parent.append_error(error);
+    }
 }
 
 impl<'a> SignatureEventBuilder<'a, EventBuilder> {
     pub fn new(parent: &'a mut EventBuilder, signature: String) -> Self {
+        let topic_0 = EventDefinition::from_signature(&signature)
+            .map(|definition| (definition.event.topic_0().unwrap_or_default(), None))
+            .map_err(|e| e.to_string());
+
+        Self::with_event(parent, signature, topic_0)
+    }
+
+    /// Creates a [`SignatureEventBuilder`] for an event of the ABI registered on the parent.
+    pub fn from_abi(parent: &'a mut EventBuilder, name: &str) -> Self {
+        let event = match abi_event(parent.abi.as_deref(), name) {
+            Ok(event) => event,
+            Err(error) => {
+                parent.append_error(error);
+                return Self {
+                    parent,
+                    signature: name.to_string(),
+                    parameter_current_index: None,
+                };
+            }
+        };
+
+        let topic_0 = match EventDefinition::from_abi(&event) {
+            _ if event.anonymous => Err("anonymous events have no topic to match".to_string()),
+            Ok(_) => Ok((event.selector(), Some(Arc::new(event.clone())))),
+            Err(e) => Err(e.to_string()),
+        };
+
+        Self::with_event(parent, event.full_signature(), topic_0)
+    }
+
+    fn with_event(
+        parent: &'a mut EventBuilder,
+        signature: String,
+        event: Result<(B256, Option<Arc<Event>>), String>,
+    ) -> Self {
+        let parameter_current_index = match event {
+            Ok((topic_0, abi)) => {
+                parent.append_node(EventCondition::EventData {
+                    signature: signature.clone(),
+                    abi,
+                    topic_0,
+                    parameters: vec![],
+                });
+                Some(parent.nodes.len() - 1)
+            }
+            Err(reason) => {
+                parent.append_error(FilterError::InvalidSignature {
+                    signature: signature.clone(),
+                    reason,
+                });
+                None
+            }
+        };
+
         Self {
             parent,
             signature,
-            parameter_current_index: None,
+            parameter_current_index,
         }
     }
 
@@ -161,12 +251,25 @@ impl<'a> SignatureEventBuilder<'a, EventBuilder> {
             parent: self,
         }
     }
+
+    /// Array condition on a decoded event parameter by name.
+    pub fn array_params(
+        &mut self,
+        name: &str,
+    ) -> FieldWrapper<'_, ArrayFieldType<ContractField>, Self> {
+        FieldWrapper {
+            field: ArrayFieldType(ContractField::Parameter(name.to_string())),
+            parent: self,
+        }
+    }
 }
 #[cfg(test)]
 mod tests {
     use super::*;
     use crate::filter::{
-        conditions::{ArrayCondition, FilterCondition, NumericCondition, StringCondition},
+        conditions::{
+            ArrayCondition, BytesCondition, FilterCondition, NumericCondition, StringCondition,
+        },
         ArrayOps, NumericOps, StringOps,
     };
 
@@ -180,7 +283,7 @@ mod tests {
         BASE_VALUE * 6, // between start
     ];
 
-    const ADDRESS: &str = "0x123";
+    const ADDRESS: &str = "0x742d35Cc6634C0532925a3b844Bc454e4438f44e";
     const CONTENT: &str = "0x456";
     const PREFIX: &str = "0x";
     const SUFFIX: &str = "789";
@@ -255,25 +358,25 @@ mod tests {
             FilterNode {
                 children: None,
                 value: Some(FilterCondition::Event(EventCondition::Contract(
-                    StringCondition::EqualTo(ADDRESS.to_string()),
+                    BytesCondition::EqualTo(ADDRESS.parse().unwrap()),
                 ))),
             },
             FilterNode {
                 children: None,
                 value: Some(FilterCondition::Event(EventCondition::Contract(
-                    StringCondition::Contains(CONTENT.to_string()),
+                    BytesCondition::Hex(StringCondition::Contains(CONTENT.to_string())),
                 ))),
             },
             FilterNode {
                 children: None,
                 value: Some(FilterCondition::Event(EventCondition::Contract(
-                    StringCondition::StartsWith(PREFIX.to_string()),
+                    BytesCondition::Hex(StringCondition::StartsWith(PREFIX.to_string())),
                 ))),
             },
             FilterNode {
                 children: None,
                 value: Some(FilterCondition::Event(EventCondition::Contract(
-                    StringCondition::EndsWith(SUFFIX.to_string()),
+                    BytesCondition::Hex(StringCondition::EndsWith(SUFFIX.to_string())),
                 ))),
             },
         ];
@@ -3,33 +3,36 @@ use super::{
     optimism::OptimismFilterBuilder, pool::PoolBuilder, transaction::TxBuilder,
 };
 use crate::filter::{
-    conditions::{FilterNode, LogicalOp},
+    conditions::{FilterNode, LogicalOp, NodeBuilder},
     LogicalOps,
 };
-use std::marker::PhantomData;
 
 /// ===== LOGICAL OPERATION BUILDER =====
 #[allow(dead_code)]
 pub struct LogicalFilterBuilder<'a, B: FilterBuilderOps> {
-    pub(crate) nodes: &'a mut Vec<FilterNode>,
-    pub(crate) _marker: PhantomData<B>,
+    pub(crate) parent: &'a mut B,
 }
 
 #[allow(dead_code)]
-impl<B: FilterBuilderOps> LogicalFilterBuilder<'_, B> {
+impl<B: FilterBuilderOps + NodeBuilder + AsMut<Vec<FilterNode>>> LogicalFilterBuilder<'_, B> {
     fn build_logical_operation<F>(self, op: LogicalOp, f: F) -> Self
     where
         F: FnOnce(&mut B),
     {
-        let mut builder = B::new();
+        let mut builder = self.parent.new_group();
         f(&mut builder);
         let builder_nodes = builder.take_nodes();
 
+        // Errors raised in the group reject the whole filter
+        for error in builder.take_errors() {
+            self.parent.append_error(error);
+        }
+
         // Only create OR group if we have multiple filters
         match builder_nodes.len() {
             0 => self,
             1 => {
-                self.nodes.extend(builder_nodes);
+                self.parent.as_mut().extend(builder_nodes);
                 self
             }
             _ => {
@@ -37,7 +40,7 @@ impl<B: FilterBuilderOps> LogicalFilterBuilder<'_, B> {
                     children: Some((op, builder_nodes)),
                     value: None,
                 };
-                self.nodes.push(node);
+                self.parent.as_mut().push(node);
                 self
             }
         }
@@ -46,72 +49,48 @@ impl<B: FilterBuilderOps> LogicalFilterBuilder<'_, B> {
 
 impl<T> LogicalOps<T> for T
 where
-    T: AsMut<Vec<FilterNode>> + FilterBuilderOps,
+    T: AsMut<Vec<FilterNode>> + FilterBuilderOps + NodeBuilder,
 {
-    fn and<F>(&mut self, f: F) -> LogicalFilterBuilder<T>
+    fn and<F>(&mut self, f: F) -> LogicalFilterBuilder<'_, T>
     where
         F: FnOnce(&mut T),
     {
-        LogicalFilterBuilder {
-            nodes: self.as_mut(),
-            _marker: PhantomData,
-        }
-        .build_logical_operation(LogicalOp::And, f)
+        LogicalFilterBuilder { parent: self }.build_logical_operation(LogicalOp::And, f)
     }
 
-    fn all_of<F>(&mut self, f: F) -> LogicalFilterBuilder<T>
+    fn all_of<F>(&mut self, f: F) -> LogicalFilterBuilder<'_, T>
     where
         F: FnOnce(&mut T),
     {
-        LogicalFilterBuilder {
-            nodes: self.as_mut(),
-            _marker: PhantomData,
-        }
-        .build_logical_operation(LogicalOp::And, f)
+        LogicalFilterBuilder { parent: self }.build_logical_operation(LogicalOp::And, f)
     }
 
-    fn not<F>(&mut self, f: F) -> LogicalFilterBuilder<T>
+    fn not<F>(&mut self, f: F) -> LogicalFilterBuilder<'_, T>
     where
         F: FnOnce(&mut T),
     {
-        LogicalFilterBuilder {
-            nodes: self.as_mut(),
-            _marker: PhantomData,
-        }
-        .build_logical_operation(LogicalOp::Not, f)
+        LogicalFilterBuilder { parent: self }.build_logical_operation(LogicalOp::Not, f)
     }
 
-    fn unless<F>(&mut self, f: F) -> LogicalFilterBuilder<T>
+    fn unless<F>(&mut self, f: F) -> LogicalFilterBuilder<'_, T>
     where
         F: FnOnce(&mut T),
     {
-        LogicalFilterBuilder {
-            nodes: self.as_mut(),
-            _marker: PhantomData,
-        }
-        .build_logical_operation(LogicalOp::Not, f)
+        LogicalFilterBuilder { parent: self }.build_logical_operation(LogicalOp::Not, f)
     }
 
-    fn or<F>(&mut self, f: F) -> LogicalFilterBuilder<T>
+    fn or<F>(&mut self, f: F) -> LogicalFilterBuilder<'_, T>
     where
         F: FnOnce(&mut T),
     {
-        LogicalFilterBuilder {
-            nodes: self.as_mut(),
-            _marker: PhantomData,
-        }
-        .build_logical_operation(LogicalOp::Or, f)
+        LogicalFilterBuilder { parent: self }.build_logical_operation(LogicalOp::Or, f)
     }
 
-    fn any_of<F>(&mut self, f: F) -> LogicalFilterBuilder<T>
+    fn any_of<F>(&mut self, f: F) -> LogicalFilterBuilder<'_, T>
     where
         F: FnOnce(&mut T),
     {
-        LogicalFilterBuilder {
-            nodes: self.as_mut(),
-            _marker: PhantomData,
-        }
-        .build_logical_operation(LogicalOp::Or, f)
+        LogicalFilterBuilder { parent: self }.build_logical_operation(LogicalOp::Or, f)
     }
 }
 
@@ -1,3 +1,4 @@
+pub(crate) mod abi;
 pub(crate) mod block_header;
 pub(crate) mod builder;
 pub(crate) mod builder_ops;
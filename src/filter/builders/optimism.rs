@@ -1,5 +1,6 @@
 use crate::filter::{
     conditions::{FilterCondition, FilterNode, NodeBuilder},
+    error::FilterError,
     field::{DynField, DynValueFieldType, FieldWrapper},
 };
 
@@ -8,16 +9,24 @@ use super::builder_ops::FilterBuilderOps;
 // ===== OPTIMISIM FILTER BUILDER ============
 pub struct OptimismFilterBuilder {
     pub(crate) nodes: Vec<FilterNode>,
+    pub(crate) errors: Vec<FilterError>,
 }
 
 impl FilterBuilderOps for OptimismFilterBuilder {
     fn new() -> Self {
-        Self { nodes: Vec::new() }
+        Self {
+            nodes: Vec::new(),
+            errors: Vec::new(),
+        }
     }
 
     fn take_nodes(&mut self) -> Vec<FilterNode> {
         std::mem::take(&mut self.nodes)
     }
+
+    fn take_errors(&mut self) -> Vec<FilterError> {
+        std::mem::take(&mut self.errors)
+    }
 }
 
 impl NodeBuilder for OptimismFilterBuilder {
@@ -30,6 +39,10 @@ impl NodeBuilder for OptimismFilterBuilder {
         };
         self.nodes.push(node);
     }
+
+    fn append_error(&mut self, error: FilterError) {
+        self.errors.push(error);
+    }
 }
 
 #[allow(dead_code)]
@@ -1,7 +1,11 @@
 // Pool builder
 use crate::filter::{
     conditions::{FilterCondition, FilterNode, NodeBuilder, PoolCondition},
-    field::{FieldWrapper, PoolField, StringFieldType, U128FieldType, U256FieldType, U64FieldType},
+    error::FilterError,
+    field::{
+        AddressFieldType, DurationFieldType, FieldWrapper, HashFieldType, PoolField, U128FieldType,
+        U256FieldType, U64FieldType,
+    },
 };
 
 use super::builder_ops::FilterBuilderOps;
@@ -9,6 +13,7 @@ use super::builder_ops::FilterBuilderOps;
 // ===== Pool Builder =====
 pub struct PoolBuilder {
     pub(crate) nodes: Vec<FilterNode>,
+    pub(crate) errors: Vec<FilterError>,
 }
 
 #[allow(dead_code)]
@@ -20,7 +25,10 @@ impl Default for PoolBuilder {
 
 impl PoolBuilder {
     pub fn new() -> Self {
-        Self { nodes: Vec::new() }
+        Self {
+            nodes: Vec::new(),
+            errors: Vec::new(),
+        }
     }
 
     pub fn nonce(&mut self) -> FieldWrapper<'_, U64FieldType<PoolField>, Self> {
@@ -51,33 +59,49 @@ impl PoolBuilder {
         }
     }
 
-    pub fn from(&mut self) -> FieldWrapper<'_, StringFieldType<PoolField>, Self> {
+    pub fn from(&mut self) -> FieldWrapper<'_, AddressFieldType<PoolField>, Self> {
         FieldWrapper {
-            field: StringFieldType(PoolField::From),
+            field: AddressFieldType(PoolField::From),
             parent: self,
         }
     }
 
-    pub fn to(&mut self) -> FieldWrapper<'_, StringFieldType<PoolField>, Self> {
+    pub fn to(&mut self) -> FieldWrapper<'_, AddressFieldType<PoolField>, Self> {
         FieldWrapper {
-            field: StringFieldType(PoolField::To),
+            field: AddressFieldType(PoolField::To),
             parent: self,
         }
     }
 
-    pub fn hash(&mut self) -> FieldWrapper<'_, StringFieldType<PoolField>, Self> {
+    pub fn hash(&mut self) -> FieldWrapper<'_, HashFieldType<PoolField>, Self> {
         FieldWrapper {
-            field: StringFieldType(PoolField::Hash),
+            field: HashFieldType(PoolField::Hash),
             parent: self,
         }
     }
 
+    /// Unix timestamp, in seconds, of when the transaction was first seen
     pub fn timestamp(&mut self) -> FieldWrapper<'_, U64FieldType<PoolField>, Self> {
         FieldWrapper {
             field: U64FieldType(PoolField::Timestamp),
             parent: self,
         }
     }
+
+    /// Time since the transaction was first seen, conditions on it are re-evaluated while the
+    /// transaction is pending e.g. `age().gt(Duration::from_secs(600))` for stuck transactions.
+    pub fn age(&mut self) -> FieldWrapper<'_, DurationFieldType<PoolField>, Self> {
+        FieldWrapper {
+            field: DurationFieldType(PoolField::Age),
+            parent: self,
+        }
+    }
+
+    /// Matches transactions replacing a pending transaction of the same sender and nonce with
+    /// higher fees, e.g. speed-ups, cancellations or bots outbidding each other.
+    pub fn is_replacement(&mut self) {
+        self.append_node(PoolCondition::IsReplacement);
+    }
 }
 
 impl NodeBuilder for PoolBuilder {
@@ -90,30 +114,43 @@ impl NodeBuilder for PoolBuilder {
             value: Some(FilterCondition::Pool(condition)),
         })
     }
+
+    fn append_error(&mut self, error: FilterError) {
+        self.errors.push(error);
+    }
 }
 
 impl FilterBuilderOps for PoolBuilder {
     fn new() -> Self {
-        Self { nodes: Vec::new() }
+        Self {
+            nodes: Vec::new(),
+            errors: Vec::new(),
+        }
     }
 
     fn take_nodes(&mut self) -> Vec<FilterNode> {
         std::mem::take(&mut self.nodes)
     }
+
+    fn take_errors(&mut self) -> Vec<FilterError> {
+        std::mem::take(&mut self.errors)
+    }
 }
 
 #[cfg(test)]
 mod tests {
+    use std::time::Duration;
+
     use alloy_primitives::ruint::aliases::U256;
 
     use super::*;
     use crate::filter::{
-        conditions::{NumericCondition, PoolCondition, StringCondition},
+        conditions::{BytesCondition, NumericCondition, PoolCondition, StringCondition},
         NumericOps, StringOps,
     };
 
     const NONCE: u64 = 1;
-    const ADDRESS: &str = "0xABCD1234";
+    const ADDRESS: &str = "0x742d35Cc6634C0532925a3b844Bc454e4438f44e";
     const HASH: &str = "0x9876fedc";
     const PREFIX: &str = "0x";
 
@@ -163,19 +200,19 @@ mod tests {
             FilterNode {
                 children: None,
                 value: Some(FilterCondition::Pool(PoolCondition::From(
-                    StringCondition::EqualTo(ADDRESS.to_string()),
+                    BytesCondition::EqualTo(ADDRESS.parse().unwrap()),
                 ))),
             },
             FilterNode {
                 children: None,
                 value: Some(FilterCondition::Pool(PoolCondition::To(
-                    StringCondition::Contains(HASH.to_string()),
+                    BytesCondition::Hex(StringCondition::Contains(HASH.to_string())),
                 ))),
             },
             FilterNode {
                 children: None,
                 value: Some(FilterCondition::Pool(PoolCondition::Hash(
-                    StringCondition::StartsWith(PREFIX.to_string()),
+                    BytesCondition::Hex(StringCondition::StartsWith(PREFIX.to_string())),
                 ))),
             },
         ];
@@ -202,7 +239,7 @@ mod tests {
             FilterNode {
                 children: None,
                 value: Some(FilterCondition::Pool(PoolCondition::From(
-                    StringCondition::Contains(ADDRESS.to_string()),
+                    BytesCondition::Hex(StringCondition::Contains(ADDRESS.to_lowercase())),
                 ))),
             },
             FilterNode {
@@ -214,7 +251,7 @@ mod tests {
             FilterNode {
                 children: None,
                 value: Some(FilterCondition::Pool(PoolCondition::Hash(
-                    StringCondition::StartsWith(PREFIX.to_string()),
+                    BytesCondition::Hex(StringCondition::StartsWith(PREFIX.to_string())),
                 ))),
             },
         ];
@@ -222,6 +259,38 @@ mod tests {
         assert_eq!(builder.nodes, expected_conditions);
     }
 
+    #[test]
+    fn test_mempool_field_operations() {
+        let mut builder = PoolBuilder::new();
+
+        builder.timestamp().gt(1_700_000_000);
+        builder.age().gte(Duration::from_secs(600));
+        builder.is_replacement();
+
+        let expected_conditions = vec![
+            FilterNode {
+                children: None,
+                value: Some(FilterCondition::Pool(PoolCondition::Timestamp(
+                    NumericCondition::GreaterThan(1_700_000_000),
+                ))),
+            },
+            FilterNode {
+                children: None,
+                value: Some(FilterCondition::Pool(PoolCondition::Age(
+                    NumericCondition::GreaterThanOrEqualTo(Duration::from_secs(600)),
+                ))),
+            },
+            FilterNode {
+                children: None,
+                value: Some(FilterCondition::Pool(PoolCondition::IsReplacement)),
+            },
+        ];
+
+        assert_eq!(builder.nodes, expected_conditions);
+        assert!(!builder.nodes[0].is_time_dependent());
+        assert!(builder.nodes[1].is_time_dependent());
+    }
+
     #[test]
     fn builder_new() {
         let builder = PoolBuilder::new();
@@ -1,22 +1,28 @@
-use std::str::FromStr;
+use std::{str::FromStr, sync::Arc};
 
 use crate::filter::{
     conditions::{
         ContractCondition, DynFieldCondition, FilterCondition, FilterNode, NodeBuilder,
         TransactionCondition,
     },
+    error::FilterError,
     field::{
-        ArrayFieldType, DynField, DynValueFieldType, FieldWrapper, StringFieldType, TxField,
-        U128FieldType, U256FieldType, U64FieldType, U8FieldType,
+        AddressFieldType, ArrayFieldType, ContractField, DynField, DynValueFieldType, FieldWrapper,
+        HashFieldType, TxField, U128FieldType, U256FieldType, U64FieldType, U8FieldType,
     },
 };
+use crate::utils::decoder::{function_selector, CallDefinition};
+use alloy_json_abi::{Function, JsonAbi};
 use alloy_primitives::Selector;
 
-use super::builder_ops::FilterBuilderOps;
+use super::{abi::abi_function, builder_ops::FilterBuilderOps};
 
 // ===== Transaction Builder =====
 pub struct TxBuilder {
     pub(crate) nodes: Vec<FilterNode>,
+    pub(crate) errors: Vec<FilterError>,
+    /// Contract ABI used to look up methods by name
+    pub(crate) abi: Option<Arc<JsonAbi>>,
 }
 
 impl NodeBuilder for TxBuilder {
@@ -28,6 +34,10 @@ impl NodeBuilder for TxBuilder {
             value: Some(FilterCondition::Transaction(condition)),
         })
     }
+
+    fn append_error(&mut self, error: FilterError) {
+        self.errors.push(error);
+    }
 }
 
 impl Default for TxBuilder {
@@ -39,7 +49,15 @@ impl Default for TxBuilder {
 #[allow(dead_code)]
 impl TxBuilder {
     pub fn new() -> Self {
-        Self { nodes: Vec::new() }
+        Self::with_abi(None)
+    }
+
+    pub(crate) fn with_abi(abi: Option<Arc<JsonAbi>>) -> Self {
+        Self {
+            nodes: Vec::new(),
+            errors: Vec::new(),
+            abi,
+        }
     }
 
     pub fn value(&mut self) -> FieldWrapper<'_, U256FieldType<TxField>, Self> {
@@ -105,30 +123,30 @@ impl TxBuilder {
         }
     }
 
-    pub fn from(&mut self) -> FieldWrapper<'_, StringFieldType<TxField>, Self> {
+    pub fn from(&mut self) -> FieldWrapper<'_, AddressFieldType<TxField>, Self> {
         FieldWrapper {
-            field: StringFieldType(TxField::From),
+            field: AddressFieldType(TxField::From),
             parent: self,
         }
     }
 
-    pub fn to(&mut self) -> FieldWrapper<'_, StringFieldType<TxField>, Self> {
+    pub fn to(&mut self) -> FieldWrapper<'_, AddressFieldType<TxField>, Self> {
         FieldWrapper {
-            field: StringFieldType(TxField::To),
+            field: AddressFieldType(TxField::To),
             parent: self,
         }
     }
 
-    pub fn hash(&mut self) -> FieldWrapper<'_, StringFieldType<TxField>, Self> {
+    pub fn hash(&mut self) -> FieldWrapper<'_, HashFieldType<TxField>, Self> {
         FieldWrapper {
-            field: StringFieldType(TxField::Hash),
+            field: HashFieldType(TxField::Hash),
             parent: self,
         }
     }
 
-    pub fn block_hash(&mut self) -> FieldWrapper<'_, StringFieldType<TxField>, Self> {
+    pub fn block_hash(&mut self) -> FieldWrapper<'_, HashFieldType<TxField>, Self> {
         FieldWrapper {
-            field: StringFieldType(TxField::BlockHash),
+            field: HashFieldType(TxField::BlockHash),
             parent: self,
         }
     }
@@ -147,10 +165,16 @@ impl TxBuilder {
         }
     }
 
-    pub fn call_data(&mut self, signature: &str) -> CallDataBuilder<Self> {
+    pub fn call_data(&mut self, signature: &str) -> CallDataBuilder<'_, Self> {
         CallDataBuilder::new(self, signature.to_string())
     }
 
+    /// Matches calls of a method of the registered contract ABI, by name or by signature
+    /// (e.g. `swap(uint256,address)`) for overloaded methods.
+    pub fn abi_call(&mut self, name: &str) -> CallDataBuilder<'_, Self> {
+        CallDataBuilder::from_abi(self, name)
+    }
+
     pub fn field(&mut self, path: &str) -> FieldWrapper<'_, DynValueFieldType<DynField>, Self> {
         FieldWrapper {
             field: DynValueFieldType(DynField(path.to_string())),
@@ -159,10 +183,14 @@ impl TxBuilder {
     }
 }
 
+/// [`CallDataBuilder`] matches transactions calling the method identified by a signature
+/// (e.g. `transfer(address to,uint256 amount)`) or a raw 4-byte selector (e.g. `0xa9059cbb`),
+/// optionally with conditions on the decoded call parameters which require a signature.
 #[allow(dead_code)]
 pub struct CallDataBuilder<'a, B> {
     parent: &'a mut B,
     signature: String,
+    /// Index of the call data node in the parent, `None` if the signature is invalid
     parameter_current_index: Option<usize>,
 }
 
@@ -170,114 +198,202 @@ impl NodeBuilder for CallDataBuilder<'_, TxBuilder> {
     type Condition = ContractCondition;
 
     fn append_node(&mut self, condition: ContractCondition) {
+        let Some(idx) = self.parameter_current_index else {
+            return;
+        };
+
+        if is_raw_selector(&self.signature) {
+            self.parent.append_error(FilterError::InvalidSignature {
+                signature: self.signature.clone(),
+                reason: "parameter conditions require a signature to decode call data".to_string(),
+            });
+            return;
+        }
+
+        let Some(FilterCondition::Transaction(TransactionCondition::CallData {
+            parameters,
+            paths,
+            ..
+        })) = self
+            .parent
+            .nodes
+            .get_mut(idx)
+            .and_then(|node| node.value.as_mut())
+        else {
+            return;
+        };
+
         match condition {
             ContractCondition::Parameter(param, parameter_condition) => {
-                if let Some(idx) = self.parameter_current_index {
-                    if let Some(node) = self.parent.nodes.get_mut(idx) {
-                        if let Some(FilterCondition::Transaction(
-                            TransactionCondition::CallData { parameters, .. },
-                        )) = node.value.as_mut()
-                        {
-                            parameters.push(DynFieldCondition {
-                                path: param,
-                                condition: parameter_condition,
-                            });
-                        }
-                    }
-                } else {
-                    let method_selector = Selector::from_str(&self.signature).unwrap_or_default();
-                    self.parent.append_node(TransactionCondition::CallData {
-                        paths: vec![],
-                        parameters: vec![DynFieldCondition {
-                            path: param,
-                            condition: parameter_condition,
-                        }],
-                        method_selector,
-                    });
-                    self.parameter_current_index = Some(self.parent.nodes.len() - 1);
-                }
+                parameters.push(DynFieldCondition {
+                    path: param,
+                    condition: parameter_condition,
+                });
             }
             ContractCondition::Path(path, path_condition) => {
-                if let Some(idx) = self.parameter_current_index {
-                    if let Some(node) = self.parent.nodes.get_mut(idx) {
-                        if let Some(FilterCondition::Transaction(
-                            TransactionCondition::CallData { paths, .. },
-                        )) = node.value.as_mut()
-                        {
-                            paths.push(DynFieldCondition {
-                                path,
-                                condition: path_condition,
-                            });
-                        }
-                    }
-                } else {
-                    let method_selector = Selector::from_str(&self.signature).unwrap_or_default();
-                    self.parent.append_node(TransactionCondition::CallData {
-                        paths: vec![],
-                        parameters: vec![DynFieldCondition {
-                            path,
-                            condition: path_condition,
-                        }],
-                        method_selector,
-                    });
-                    self.parameter_current_index = Some(self.parent.nodes.len() - 1);
-                }
+                paths.push(DynFieldCondition {
+                    path,
+                    condition: path_condition,
+                });
             }
         };
     }
+
+    fn append_error(&mut self, error: FilterError) {
+        self.parent.append_error(error);
+    }
+}
+
+/// Checks if a call data signature is a raw `0x` prefixed selector.
+fn is_raw_selector(signature: &str) -> bool {
+    signature.starts_with("0x")
 }
 
 impl<'a> CallDataBuilder<'a, TxBuilder> {
     pub fn new(parent: &'a mut TxBuilder, signature: String) -> Self {
+        let method_selector = if is_raw_selector(&signature) {
+            Selector::from_str(&signature).map_err(|e| e.to_string())
+        } else {
+            function_selector(&signature).map_err(|e| e.to_string())
+        };
+
+        Self::with_method(
+            parent,
+            signature,
+            method_selector.map(|selector| (selector, None)),
+        )
+    }
+
+    /// Creates a [`CallDataBuilder`] for a method of the ABI registered on the parent.
+    pub fn from_abi(parent: &'a mut TxBuilder, name: &str) -> Self {
+        let function = match abi_function(parent.abi.as_deref(), name) {
+            Ok(function) => function,
+            Err(error) => {
+                parent.append_error(error);
+                return Self {
+                    parent,
+                    signature: name.to_string(),
+                    parameter_current_index: None,
+                };
+            }
+        };
+
+        // Make sure the call data can be decoded before accepting the filter
+        let method = CallDefinition::from_abi(&function)
+            .map(|_| (function.selector(), Some(Arc::new(function.clone()))))
+            .map_err(|e| e.to_string());
+
+        Self::with_method(parent, function.full_signature(), method)
+    }
+
+    fn with_method(
+        parent: &'a mut TxBuilder,
+        signature: String,
+        method: Result<(Selector, Option<Arc<Function>>), String>,
+    ) -> Self {
+        let parameter_current_index = match method {
+            Ok((method_selector, abi)) => {
+                parent.append_node(TransactionCondition::CallData {
+                    signature: signature.clone(),
+                    abi,
+                    paths: vec![],
+                    method_selector,
+                    parameters: vec![],
+                });
+                Some(parent.nodes.len() - 1)
+            }
+            Err(reason) => {
+                parent.append_error(FilterError::InvalidSignature {
+                    signature: signature.clone(),
+                    reason,
+                });
+                None
+            }
+        };
+
         Self {
             parent,
             signature,
-            parameter_current_index: None,
+            parameter_current_index,
         }
     }
 
-    // pub fn params(
-    //     &mut self,
-    //     name: &str,
-    // ) -> FieldWrapper<'_, DynValueFieldType<ContractField>, Self> {
-    //     FieldWrapper {
-    //         field: DynValueFieldType(ContractField::Parameter(name.to_string())),
-    //         parent: self,
-    //     }
-    // }
-
-    // pub fn path(&mut self, name: &str) -> FieldWrapper<'_, DynValueFieldType<ContractField>, Self> {
-    //     FieldWrapper {
-    //         field: DynValueFieldType(ContractField::Path(name.to_string())),
-    //         parent: self,
-    //     }
-    // }
+    /// Condition on a decoded call parameter by name, e.g `amount`.
+    pub fn params(
+        &mut self,
+        name: &str,
+    ) -> FieldWrapper<'_, DynValueFieldType<ContractField>, Self> {
+        FieldWrapper {
+            field: DynValueFieldType(ContractField::Parameter(name.to_string())),
+            parent: self,
+        }
+    }
+
+    /// Array condition on a decoded call parameter by name, e.g `path` of a swap.
+    pub fn array_params(
+        &mut self,
+        name: &str,
+    ) -> FieldWrapper<'_, ArrayFieldType<ContractField>, Self> {
+        FieldWrapper {
+            field: ArrayFieldType(ContractField::Parameter(name.to_string())),
+            parent: self,
+        }
+    }
+
+    /// Array condition on a nested value of the decoded call parameters, see [`Self::path`].
+    pub fn array_path(
+        &mut self,
+        path: &str,
+    ) -> FieldWrapper<'_, ArrayFieldType<ContractField>, Self> {
+        FieldWrapper {
+            field: ArrayFieldType(ContractField::Path(path.to_string())),
+            parent: self,
+        }
+    }
+
+    /// Condition on a nested value of the decoded call parameters, e.g `path.0` for the first
+    /// element of an array parameter or `params.1` for the second field of a tuple.
+    pub fn path(&mut self, path: &str) -> FieldWrapper<'_, DynValueFieldType<ContractField>, Self> {
+        FieldWrapper {
+            field: DynValueFieldType(ContractField::Path(path.to_string())),
+            parent: self,
+        }
+    }
 }
 
 impl FilterBuilderOps for TxBuilder {
     fn new() -> Self {
-        Self { nodes: Vec::new() }
+        Self::with_abi(None)
+    }
+
+    fn new_group(&self) -> Self {
+        Self::with_abi(self.abi.clone())
     }
 
     fn take_nodes(&mut self) -> Vec<FilterNode> {
         std::mem::take(&mut self.nodes)
     }
+
+    fn take_errors(&mut self) -> Vec<FilterError> {
+        std::mem::take(&mut self.errors)
+    }
 }
 
 #[cfg(test)]
 mod tests {
-    use alloy_primitives::U256;
+    use alloy_primitives::{Address, U256};
 
     use super::*;
     use crate::filter::{
         conditions::{
-            ArrayCondition, FilterCondition, NumericCondition, StringCondition,
-            TransactionCondition,
+            ArrayCondition, BytesCondition, FilterCondition, NumericCondition, StringCondition,
+            TransactionCondition, ValueCondition,
         },
         ArrayOps, NumericOps, StringOps,
     };
 
-    const ADDRESS: &str = "0x123";
+    const ADDRESS: &str = "0x742d35Cc6634C0532925a3b844Bc454e4438f44e";
+    const SIGNATURE: &str = "transfer(address to,uint256 amount)";
     const PREFIX: &str = "0x";
     const CONTENT: &str = "abc";
 
@@ -348,30 +464,68 @@ mod tests {
             FilterNode {
                 children: None,
                 value: Some(FilterCondition::Transaction(TransactionCondition::From(
-                    StringCondition::EqualTo(ADDRESS.to_string()),
+                    BytesCondition::EqualTo(ADDRESS.parse().unwrap()),
                 ))),
             },
             FilterNode {
                 children: None,
                 value: Some(FilterCondition::Transaction(TransactionCondition::To(
-                    StringCondition::StartsWith(PREFIX.to_string()),
+                    BytesCondition::Hex(StringCondition::StartsWith(PREFIX.to_string())),
                 ))),
             },
             FilterNode {
                 children: None,
                 value: Some(FilterCondition::Transaction(
-                    TransactionCondition::BlockHash(StringCondition::Contains(CONTENT.to_string())),
+                    TransactionCondition::BlockHash(BytesCondition::Hex(
+                        StringCondition::Contains(CONTENT.to_string()),
+                    )),
                 )),
             },
             FilterNode {
                 children: None,
                 value: Some(FilterCondition::Transaction(TransactionCondition::Hash(
-                    StringCondition::EndsWith(CONTENT.to_string()),
+                    BytesCondition::Hex(StringCondition::EndsWith(CONTENT.to_string())),
+                ))),
+            },
+        ];
+
+        assert_eq!(builder.nodes, expected_nodes);
+    }
+
+    #[test]
+    fn test_tx_set_operations() {
+        let mut builder = TxBuilder::new();
+
+        builder
+            .to()
+            .in_set([ADDRESS, "0x0000000000000000000000000000000000000001"]);
+        builder.nonce().not_in_set([1, 2, 3]);
+        builder.to().in_set(["0xdead"]);
+
+        let expected_nodes = vec![
+            FilterNode {
+                children: None,
+                value: Some(FilterCondition::Transaction(TransactionCondition::To(
+                    BytesCondition::In(
+                        [ADDRESS.parse().unwrap(), Address::with_last_byte(1)]
+                            .into_iter()
+                            .collect(),
+                    ),
+                ))),
+            },
+            FilterNode {
+                children: None,
+                value: Some(FilterCondition::Transaction(TransactionCondition::Nonce(
+                    NumericCondition::NotIn([3, 2, 1].into_iter().collect()),
                 ))),
             },
         ];
 
         assert_eq!(builder.nodes, expected_nodes);
+        assert!(matches!(
+            builder.take_errors().as_slice(),
+            [FilterError::InvalidAddress { value, .. }] if value == "0xdead"
+        ));
     }
 
     #[test]
@@ -438,6 +592,89 @@ mod tests {
         assert_eq!(builder.nodes, expected_nodes);
     }
 
+    #[test]
+    fn test_tx_call_data_operations() {
+        let mut builder = TxBuilder::new();
+
+        {
+            let mut call = builder.call_data(SIGNATURE);
+            call.params("amount").gt(U256::from(100));
+            call.path("to").exact(ADDRESS);
+        }
+
+        assert_eq!(builder.nodes.len(), 1);
+        match &builder.nodes[0].value {
+            Some(FilterCondition::Transaction(TransactionCondition::CallData {
+                signature,
+                parameters,
+                paths,
+                ..
+            })) => {
+                assert_eq!(signature, SIGNATURE);
+                assert_eq!(
+                    parameters,
+                    &vec![DynFieldCondition {
+                        path: "amount".to_string(),
+                        condition: ValueCondition::U256(NumericCondition::GreaterThan(U256::from(
+                            100
+                        ))),
+                    }]
+                );
+                assert_eq!(
+                    paths,
+                    &vec![DynFieldCondition {
+                        path: "to".to_string(),
+                        // Address literals are compared as lowercase hex
+                        condition: ValueCondition::String(StringCondition::EqualTo(
+                            ADDRESS.to_lowercase()
+                        )),
+                    }]
+                );
+            }
+            _ => panic!("Expected call data condition"),
+        }
+    }
+
+    #[test]
+    fn test_tx_call_data_selector() {
+        let selector = |signature: &str| {
+            let mut builder = TxBuilder::new();
+            builder.call_data(signature);
+            assert!(builder.errors.is_empty());
+            match &builder.nodes[0].value {
+                Some(FilterCondition::Transaction(TransactionCondition::CallData {
+                    method_selector,
+                    ..
+                })) => *method_selector,
+                _ => panic!("Expected call data condition"),
+            }
+        };
+
+        let transfer = Selector::from_str("0xa9059cbb").unwrap();
+        assert_eq!(selector(SIGNATURE), transfer);
+        assert_eq!(selector("transfer(address,uint256)"), transfer);
+        assert_eq!(selector("0xa9059cbb"), transfer);
+    }
+
+    #[test]
+    fn test_tx_call_data_errors() {
+        let mut builder = TxBuilder::new();
+        builder.call_data("transfer(address to,uint256 amount");
+        assert!(builder.nodes.is_empty());
+        assert!(matches!(
+            builder.errors.as_slice(),
+            [FilterError::InvalidSignature { .. }]
+        ));
+
+        // Parameters can't be decoded without a signature
+        let mut builder = TxBuilder::new();
+        builder
+            .call_data("0xa9059cbb")
+            .params("amount")
+            .gt(U256::from(100));
+        assert_eq!(builder.errors.len(), 1);
+    }
+
     #[test]
     fn builder_new() {
         let builder = TxBuilder::new();
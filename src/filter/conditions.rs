@@ -1,14 +1,19 @@
-use alloy_primitives::{Selector, U256};
+use alloy_json_abi::{Event, Function};
+use alloy_primitives::{Address, Selector, B256, I256, U256};
+use regex::{Regex, RegexBuilder};
 use std::{
     cmp::PartialOrd,
-    hash::Hash,
+    collections::{hash_map::DefaultHasher, HashSet},
+    hash::{Hash, Hasher},
     sync::{
         atomic::{AtomicU64, Ordering},
         Arc,
     },
+    time::Duration,
 };
 
 use crate::config::Chain;
+use crate::filter::error::FilterError;
 
 #[derive(Debug, Clone, Hash, PartialEq, Eq)]
 #[allow(dead_code)]
@@ -20,7 +25,7 @@ pub enum LogicalOp {
 }
 
 #[allow(dead_code)]
-pub trait NumericType: Clone + PartialEq + PartialOrd {
+pub trait NumericType: Clone + PartialEq + PartialOrd + Eq + Hash {
     fn from_string(value: String) -> Self;
 }
 
@@ -48,6 +53,19 @@ impl NumericType for U256 {
     }
 }
 
+impl NumericType for I256 {
+    fn from_string(value: String) -> Self {
+        value.parse().unwrap_or_default()
+    }
+}
+
+// Durations are parsed from a number of seconds
+impl NumericType for Duration {
+    fn from_string(value: String) -> Self {
+        Duration::from_secs(value.parse().unwrap_or_default())
+    }
+}
+
 // Generic numeric condition that works with any numeric type
 #[derive(Debug, PartialEq, Eq, Hash, Clone)]
 pub enum NumericCondition<T: NumericType> {
@@ -59,6 +77,8 @@ pub enum NumericCondition<T: NumericType> {
     NotEqualTo(T),
     Between(T, T),
     Outside(T, T),
+    In(ValueSet<T>),
+    NotIn(ValueSet<T>),
 }
 
 #[derive(Debug, Clone, Hash, PartialEq, Eq)]
@@ -75,7 +95,129 @@ pub enum StringCondition {
     Contains(String),
     StartsWith(String),
     EndsWith(String),
-    Matches(String),
+    Matches(Pattern),
+    In(ValueSet<String>),
+    NotIn(ValueSet<String>),
+}
+
+impl StringCondition {
+    /// Lowercases the exact match operands that are addresses, decoded and json values render
+    /// addresses as lowercase hex whatever the casing of the literal they are compared to.
+    pub(crate) fn with_lowercase_addresses(self) -> Self {
+        match self {
+            Self::EqualTo(value) => Self::EqualTo(lowercase_address(value)),
+            Self::In(set) => Self::In(set.0.iter().cloned().map(lowercase_address).collect()),
+            Self::NotIn(set) => Self::NotIn(set.0.iter().cloned().map(lowercase_address).collect()),
+            condition => condition,
+        }
+    }
+}
+
+impl ArrayCondition<String> {
+    /// Lowercases the operands that are addresses, see [`StringCondition::with_lowercase_addresses`].
+    pub(crate) fn with_lowercase_addresses(self) -> Self {
+        match self {
+            Self::Contains(value) => Self::Contains(lowercase_address(value)),
+            Self::NotIn(values) => Self::NotIn(values.into_iter().map(lowercase_address).collect()),
+            condition => condition,
+        }
+    }
+}
+
+/// Lowercase `0x` prefixed hex of an address literal, other values are returned as is.
+fn lowercase_address(value: String) -> String {
+    match value.parse::<Address>() {
+        Ok(address) if value.starts_with("0x") => format!("{address:#x}"),
+        _ => value,
+    }
+}
+
+/// Condition on fixed size bytes such as addresses and hashes. Exact matches compare the bytes,
+/// other string operations are case-insensitive and run on the lowercase `0x` prefixed hex.
+#[derive(Debug, Clone, Hash, PartialEq, Eq)]
+pub enum BytesCondition<T: Hash + Eq> {
+    EqualTo(T),
+    In(ValueSet<T>),
+    NotIn(ValueSet<T>),
+    Hex(StringCondition),
+}
+
+pub type AddressCondition = BytesCondition<Address>;
+pub type HashCondition = BytesCondition<B256>;
+
+/// Regular expression compiled once when the filter is built, two patterns are equal when
+/// their source is.
+#[derive(Debug, Clone)]
+pub struct Pattern(Regex);
+
+impl Pattern {
+    pub(crate) fn new(pattern: &str) -> Result<Self, regex::Error> {
+        Regex::new(pattern).map(Self)
+    }
+
+    /// Compiles a pattern matching regardless of case, e.g. for hex strings.
+    pub(crate) fn case_insensitive(pattern: &str) -> Result<Self, regex::Error> {
+        RegexBuilder::new(pattern)
+            .case_insensitive(true)
+            .build()
+            .map(Self)
+    }
+
+    pub(crate) fn is_match(&self, value: &str) -> bool {
+        self.0.is_match(value)
+    }
+}
+
+impl PartialEq for Pattern {
+    fn eq(&self, other: &Self) -> bool {
+        self.0.as_str() == other.0.as_str()
+    }
+}
+
+impl Eq for Pattern {}
+
+impl Hash for Pattern {
+    fn hash<H: Hasher>(&self, state: &mut H) {
+        self.0.as_str().hash(state);
+    }
+}
+
+/// Set of values for membership conditions, lookups are O(1) however large the set is. It is
+/// shared between clones of a filter and hashes the same regardless of insertion order.
+#[derive(Debug, Clone)]
+pub struct ValueSet<T>(Arc<HashSet<T>>);
+
+impl<T: Hash + Eq> ValueSet<T> {
+    pub(crate) fn contains(&self, value: &T) -> bool {
+        self.0.contains(value)
+    }
+}
+
+impl<T: Hash + Eq> FromIterator<T> for ValueSet<T> {
+    fn from_iter<I: IntoIterator<Item = T>>(iter: I) -> Self {
+        Self(Arc::new(iter.into_iter().collect()))
+    }
+}
+
+impl<T: Hash + Eq> PartialEq for ValueSet<T> {
+    fn eq(&self, other: &Self) -> bool {
+        self.0 == other.0
+    }
+}
+
+impl<T: Hash + Eq> Eq for ValueSet<T> {}
+
+impl<T: Hash> Hash for ValueSet<T> {
+    fn hash<H: Hasher>(&self, state: &mut H) {
+        // Sum of the element hashes, so the iteration order of the set doesn't matter
+        let sum = self.0.iter().fold(0u64, |sum, value| {
+            let mut hasher = DefaultHasher::new();
+            value.hash(&mut hasher);
+            sum.wrapping_add(hasher.finish())
+        });
+        self.0.len().hash(state);
+        sum.hash(state);
+    }
 }
 
 #[derive(Debug, Clone, PartialEq, Hash, Eq)]
@@ -99,7 +241,11 @@ pub enum ValueCondition {
     U64(NumericCondition<u64>),
     U128(NumericCondition<u128>),
     U256(NumericCondition<U256>),
+    /// Condition on signed integers, unsigned values are compared when they fit
+    I256(NumericCondition<I256>),
     String(StringCondition),
+    /// Condition on the elements of an array, compared in their string form
+    Array(ArrayCondition<String>),
 }
 
 #[derive(Debug, Clone, PartialEq, Hash, Eq)]
@@ -115,13 +261,16 @@ pub enum TransactionCondition {
     GasPrice(NumericCondition<u128>),
     MaxFeePerGas(NumericCondition<u128>),
     MaxPriorityFee(NumericCondition<u128>),
-    From(StringCondition),
-    To(StringCondition),
-    Hash(StringCondition),
-    BlockHash(StringCondition),
+    From(AddressCondition),
+    To(AddressCondition),
+    Hash(HashCondition),
+    BlockHash(HashCondition),
     AccessList(ArrayCondition<String>),
 
     CallData {
+        signature: String,
+        /// Definition of the method when imported from a contract JSON ABI
+        abi: Option<Arc<Function>>,
         paths: Vec<DynFieldCondition>,
         method_selector: Selector,
         parameters: Vec<DynFieldCondition>,
@@ -134,7 +283,7 @@ pub enum TransactionCondition {
 #[allow(dead_code)]
 pub enum EventCondition {
     // String conditions
-    Contract(StringCondition),
+    Contract(AddressCondition),
     BlockHash(StringCondition),
     TxHash(StringCondition),
 
@@ -145,6 +294,9 @@ pub enum EventCondition {
 
     EventData {
         signature: String,
+        /// Definition of the event when imported from a contract JSON ABI
+        abi: Option<Arc<Event>>,
+        topic_0: B256,
         parameters: Vec<(String, ValueCondition)>,
     },
 
@@ -156,7 +308,7 @@ pub enum EventCondition {
 
 #[derive(Debug, Clone, PartialEq, Eq)]
 #[allow(dead_code)]
-pub(crate) enum ContractCondition {
+pub enum ContractCondition {
     Parameter(String, ValueCondition),
     Path(String, ValueCondition),
 }
@@ -164,13 +316,19 @@ pub(crate) enum ContractCondition {
 #[derive(Debug, Clone, Hash, PartialEq, Eq)]
 #[allow(dead_code)]
 pub enum PoolCondition {
-    Hash(StringCondition),
-    To(StringCondition),
-    From(StringCondition),
+    Hash(HashCondition),
+    To(AddressCondition),
+    From(AddressCondition),
     Value(NumericCondition<U256>),
     Nonce(NumericCondition<u64>),
     GasPrice(NumericCondition<u128>),
     GasLimit(NumericCondition<u64>),
+    /// Unix timestamp, in seconds, of when the transaction was first seen
+    Timestamp(NumericCondition<u64>),
+    /// Time since the transaction was first seen, re-evaluated while it is pending
+    Age(NumericCondition<Duration>),
+    /// The transaction replaced a pending transaction of the same sender and nonce
+    IsReplacement,
 }
 
 #[derive(Debug, Clone, Hash, PartialEq, Eq)]
@@ -181,21 +339,32 @@ pub enum BlockHeaderCondition {
     Timestamp(NumericCondition<u64>),
     GasUsed(NumericCondition<u64>),
     GasLimit(NumericCondition<u64>),
-
+    BlobGasUsed(NumericCondition<u64>),
+    ExcessBlobGas(NumericCondition<u64>),
+    Size(NumericCondition<U256>),
+
+    Hash(HashCondition),
+    Miner(AddressCondition),
+    WithdrawalsRoot(HashCondition),
+    ParentBeaconBlockRoot(HashCondition),
+    MixHash(HashCondition),
     ParentHash(StringCondition),
     StateRoot(StringCondition),
     ReceiptsRoot(StringCondition),
     TransactionsRoot(StringCondition),
+    ExtraData(StringCondition),
 
     DynField(DynFieldCondition),
 }
 
-pub(crate) trait NodeBuilder {
+pub trait NodeBuilder {
     type Condition;
     fn append_node(&mut self, condition: Self::Condition);
+    /// Records an error raised while building, the filter is rejected once built.
+    fn append_error(&mut self, error: FilterError);
 }
 
-#[derive(Clone, PartialEq, Eq, Hash)]
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
 #[allow(dead_code)]
 pub(crate) enum EventType {
     Transaction = 0,
@@ -302,12 +471,12 @@ impl FilterNode {
 #[cfg(test)]
 mod tests {
     use super::*;
-    use crate::filter::conditions::{StringCondition, TransactionCondition};
+    use crate::filter::conditions::TransactionCondition;
 
     #[test]
     fn test_optimize_single_condition() {
         let condition = FilterCondition::Transaction(TransactionCondition::From(
-            StringCondition::EqualTo("0x123".to_string()),
+            BytesCondition::EqualTo(Address::repeat_byte(0x12)),
         ));
         let node = FilterNode {
             children: None,
@@ -321,7 +490,7 @@ mod tests {
     fn test_optimize_single_node_children() {
         // ensure that children with single node should be flattened
         let condition = FilterCondition::Transaction(TransactionCondition::From(
-            StringCondition::EqualTo("0x123".to_string()),
+            BytesCondition::EqualTo(Address::repeat_byte(0x12)),
         ));
         let inner_node = FilterNode {
             children: None,
@@ -341,10 +510,10 @@ mod tests {
     fn test_optimize_multi_node_children() {
         // children with multiple nodes should stay as children
         let condition1 = FilterCondition::Transaction(TransactionCondition::From(
-            StringCondition::EqualTo("0x123".to_string()),
+            BytesCondition::EqualTo(Address::repeat_byte(0x12)),
         ));
         let condition2 = FilterCondition::Transaction(TransactionCondition::To(
-            StringCondition::EqualTo("0x456".to_string()),
+            BytesCondition::EqualTo(Address::repeat_byte(0x45)),
         ));
 
         let node = FilterNode {
@@ -369,6 +538,24 @@ mod tests {
         assert!(optimized.value.is_none());
     }
 
+    #[test]
+    fn test_value_set_hash() {
+        let hash = |set: &ValueSet<u64>| {
+            let mut hasher = DefaultHasher::new();
+            set.hash(&mut hasher);
+            hasher.finish()
+        };
+
+        let set: ValueSet<u64> = (0..1000).collect();
+        let reversed: ValueSet<u64> = (0..1000).rev().collect();
+        let other: ValueSet<u64> = (1..1001).collect();
+
+        assert_eq!(set, reversed);
+        assert_eq!(hash(&set), hash(&reversed));
+        assert_ne!(set, other);
+        assert_ne!(hash(&set), hash(&other));
+    }
+
     #[test]
     fn test_optimize_empty_children() {
         // children with only empty nodes should become empty
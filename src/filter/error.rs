@@ -0,0 +1,30 @@
+use std::convert::Infallible;
+use thiserror::Error;
+
+/// Errors raised while building a [`super::conditions::Filter`].
+#[derive(Error, Debug, Clone, PartialEq, Eq)]
+pub enum FilterError {
+    #[error("`{field}` does not support {operation} conditions")]
+    UnsupportedOperation { field: String, operation: String },
+    #[error("Invalid signature `{signature}`: {reason}")]
+    InvalidSignature { signature: String, reason: String },
+    #[error("Invalid address `{value}`: {reason}")]
+    InvalidAddress { value: String, reason: String },
+    #[error("Invalid hash `{value}`: {reason}")]
+    InvalidHash { value: String, reason: String },
+    #[error("Invalid regex `{pattern}`: {reason}")]
+    InvalidRegex { pattern: String, reason: String },
+    #[error("No ABI registered to look up `{name}`, see `FilterBuilder::abi`")]
+    MissingAbi { name: String },
+    #[error("`{name}` is not defined in the registered ABI")]
+    AbiItemNotFound { name: String },
+    #[error("`{name}` is overloaded in the registered ABI, use its signature instead e.g. `{signature}`")]
+    AmbiguousAbiItem { name: String, signature: String },
+}
+
+// Conversions of fields that support every operation can't fail
+impl From<Infallible> for FilterError {
+    fn from(e: Infallible) -> Self {
+        match e {}
+    }
+}
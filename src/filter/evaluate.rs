@@ -1,12 +1,15 @@
-use crate::utils::json::resolve_path;
+use crate::utils::json::{dyn_sol_to_json, resolve_path};
 
 use super::conditions::{
-    ArrayCondition, DynFieldCondition, EventCondition, FilterCondition, NumericCondition,
-    NumericType, StringCondition, TransactionCondition, ValueCondition,
+    ArrayCondition, BytesCondition, DynFieldCondition, EventCondition, FilterCondition, FilterNode,
+    NumericCondition, NumericType, PoolCondition, StringCondition, TransactionCondition,
+    ValueCondition,
 };
+
 use alloy_dyn_abi::DynSolValue;
-use alloy_primitives::U256;
+use alloy_primitives::{I256, U256};
 use serde_json::Value;
+use std::{fmt::LowerHex, hash::Hash};
 
 pub(crate) trait Evaluable<T> {
     fn evaluate(&self, value: &T) -> bool;
@@ -14,13 +17,17 @@ pub(crate) trait Evaluable<T> {
 
 impl FilterCondition {
     pub(crate) fn needs_decoded_data(&self) -> bool {
+        // Method and event matches without parameter conditions only need the selector
         match self {
-            FilterCondition::Transaction(transaction_condition) => {
-                matches!(transaction_condition, TransactionCondition::CallData { .. })
-            }
-            FilterCondition::Event(event_condition) => {
-                matches!(event_condition, EventCondition::EventData { .. })
-            }
+            FilterCondition::Transaction(transaction_condition) => matches!(
+                transaction_condition,
+                TransactionCondition::CallData { parameters, paths, .. }
+                    if !parameters.is_empty() || !paths.is_empty()
+            ),
+            FilterCondition::Event(event_condition) => matches!(
+                event_condition,
+                EventCondition::EventData { parameters, .. } if !parameters.is_empty()
+            ),
             FilterCondition::Pool(_) => false,
             FilterCondition::BlockHeader(_) => false,
             FilterCondition::DynField(_) => false,
@@ -28,6 +35,20 @@ impl FilterCondition {
     }
 }
 
+impl FilterNode {
+    /// Whether the result of this filter can change over time for the same data, e.g. the age
+    /// of a pending transaction, such filters are re-evaluated periodically.
+    pub(crate) fn is_time_dependent(&self) -> bool {
+        match (&self.value, &self.children) {
+            (Some(condition), _) => {
+                matches!(condition, FilterCondition::Pool(PoolCondition::Age(_)))
+            }
+            (None, Some((_, nodes))) => nodes.iter().any(FilterNode::is_time_dependent),
+            (None, None) => false,
+        }
+    }
+}
+
 impl<T> Evaluable<T> for NumericCondition<T>
 where
     T: NumericType,
@@ -42,6 +63,8 @@ where
             Self::NotEqualTo(threshold) => value != threshold,
             Self::Between(min, max) => value >= min && value <= max,
             Self::Outside(min, max) => value < min || value > max,
+            Self::In(set) => set.contains(value),
+            Self::NotIn(set) => !set.contains(value),
         }
     }
 }
@@ -53,11 +76,23 @@ impl Evaluable<String> for StringCondition {
             Self::Contains(substring) => value.contains(substring),
             Self::StartsWith(prefix) => value.starts_with(prefix),
             Self::EndsWith(suffix) => value.ends_with(suffix),
-            Self::Matches(pattern) => {
-                // TODO: Use regex pattern matching here
-                //
-                value == pattern
-            }
+            Self::Matches(pattern) => pattern.is_match(value),
+            Self::In(set) => set.contains(value),
+            Self::NotIn(set) => !set.contains(value),
+        }
+    }
+}
+
+impl<T> Evaluable<T> for BytesCondition<T>
+where
+    T: Hash + Eq + LowerHex,
+{
+    fn evaluate(&self, value: &T) -> bool {
+        match self {
+            Self::EqualTo(expected) => value == expected,
+            Self::In(set) => set.contains(value),
+            Self::NotIn(set) => !set.contains(value),
+            Self::Hex(condition) => condition.evaluate(&format!("{value:#x}")),
         }
     }
 }
@@ -81,15 +116,16 @@ where
 impl Evaluable<DynSolValue> for ValueCondition {
     fn evaluate(&self, value: &DynSolValue) -> bool {
         match self {
-            Self::U256(condition) => {
-                if let Some((value_uint, size)) = value.as_uint() {
-                    // Check that we have a uint256
-                    if size == 256 {
-                        return condition.evaluate(&value_uint);
-                    }
-                }
-                false
-            }
+            // Every uint size fits in a U256
+            Self::U256(condition) => value
+                .as_uint()
+                .is_some_and(|(value_uint, _)| condition.evaluate(&value_uint)),
+            Self::I256(condition) => match value {
+                DynSolValue::Int(value_int, _) => condition.evaluate(value_int),
+                DynSolValue::Uint(value_uint, _) => I256::try_from(*value_uint)
+                    .is_ok_and(|value_int| condition.evaluate(&value_int)),
+                _ => false,
+            },
             Self::U128(condition) => {
                 if let Some((value_uint, size)) = value.as_uint() {
                     // Check that we have a uint128 or smaller
@@ -105,12 +141,21 @@ impl Evaluable<DynSolValue> for ValueCondition {
                 }
                 false
             }
-            Self::String(condition) => {
-                if let Some(val_str) = value.as_str() {
-                    return condition.evaluate(&val_str.to_string());
+            // Compared in their json string form, e.g. lowercase hex for addresses
+            Self::String(condition) => match dyn_sol_to_json(value, &[]) {
+                Value::String(s) => condition.evaluate(&s),
+                _ => false,
+            },
+            Self::Array(condition) => match value {
+                DynSolValue::Array(values) | DynSolValue::FixedArray(values) => {
+                    let values = values
+                        .iter()
+                        .map(|value| json_to_string(&dyn_sol_to_json(value, &[])))
+                        .collect();
+                    condition.evaluate(&values)
                 }
-                false
-            }
+                _ => false,
+            },
             Self::U64(condition) => {
                 if let Some((value_uint, size)) = value.as_uint() {
                     // Check that we have a uint64 or smaller
@@ -163,6 +208,15 @@ impl Evaluable<Value> for DynFieldCondition {
                     }
                     false
                 }
+                // Signed integers are decimal strings, unsigned ones `0x` prefixed hex
+                (Value::String(s), ValueCondition::I256(num_condition)) => {
+                    let num = if s.starts_with("0x") {
+                        I256::from_hex_str(s)
+                    } else {
+                        I256::from_dec_str(s)
+                    };
+                    num.is_ok_and(|num| num_condition.evaluate(&num))
+                }
                 (Value::String(hex_str), ValueCondition::U128(num_condition)) => {
                     if let Some(stripped) = hex_str.strip_prefix("0x") {
                         if let Ok(num) = u128::from_str_radix(stripped, 16) {
@@ -179,6 +233,9 @@ impl Evaluable<Value> for DynFieldCondition {
                     }
                     false
                 }
+                (Value::Array(items), ValueCondition::Array(array_condition)) => {
+                    array_condition.evaluate(&items.iter().map(json_to_string).collect())
+                }
                 _ => false,
             }
         } else {
@@ -187,9 +244,18 @@ impl Evaluable<Value> for DynFieldCondition {
     }
 }
 
+/// String form of an array element, strings are used as is (e.g. addresses, `0x` hex numbers).
+fn json_to_string(value: &Value) -> String {
+    match value {
+        Value::String(s) => s.clone(),
+        value => value.to_string(),
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::filter::conditions::Pattern;
     use serde_json::json;
 
     #[test]
@@ -220,6 +286,26 @@ mod tests {
         assert!(condition.evaluate(&tx));
     }
 
+    #[test]
+    fn test_i256_evaluation() {
+        // Decoded call parameters, signed integers are decimal strings
+        let params = json!({ "amountSpecified": "-5000", "fee": "0xbb8" });
+
+        let condition = DynFieldCondition {
+            path: "amountSpecified".to_string(),
+            condition: ValueCondition::I256(NumericCondition::LessThan(I256::ZERO)),
+        };
+        assert!(condition.evaluate(&params));
+
+        let condition = DynFieldCondition {
+            path: "fee".to_string(),
+            condition: ValueCondition::I256(NumericCondition::EqualTo(
+                I256::try_from(3000).unwrap(),
+            )),
+        };
+        assert!(condition.evaluate(&params));
+    }
+
     #[test]
     fn test_u64_hex_evaluation() {
         let tx = json!({
@@ -248,4 +334,47 @@ mod tests {
         };
         assert!(condition.evaluate(&tx));
     }
+
+    #[test]
+    fn test_string_matches_evaluation() {
+        let condition = StringCondition::Matches(Pattern::new("^0x(dead|beef)").unwrap());
+        assert!(condition.evaluate(&"0xdead000000000000000000000000000000000000".to_string()));
+        assert!(condition.evaluate(&"0xbeef000000000000000000000000000000000000".to_string()));
+        assert!(!condition.evaluate(&"0x00dead0000000000000000000000000000000000".to_string()));
+
+        let memo = DynFieldCondition {
+            path: "memo".to_string(),
+            condition: ValueCondition::String(StringCondition::Matches(
+                Pattern::new(r"^invoice-\d+$").unwrap(),
+            )),
+        };
+        assert!(memo.evaluate(&json!({ "memo": "invoice-42" })));
+        assert!(!memo.evaluate(&json!({ "memo": "invoice-" })));
+    }
+
+    #[test]
+    fn test_array_evaluation() {
+        let call = json!({ "path": ["0xaa", "0xbb"], "amounts": [] });
+
+        let condition = DynFieldCondition {
+            path: "path".to_string(),
+            condition: ValueCondition::Array(ArrayCondition::Contains("0xbb".to_string())),
+        };
+        assert!(condition.evaluate(&call));
+
+        let condition = DynFieldCondition {
+            path: "amounts".to_string(),
+            condition: ValueCondition::Array(ArrayCondition::NotEmpty),
+        };
+        assert!(!condition.evaluate(&call));
+
+        let value = DynSolValue::FixedArray(vec![
+            DynSolValue::Uint(U256::from(1), 256),
+            DynSolValue::Uint(U256::from(2), 256),
+        ]);
+        let condition = ValueCondition::Array(ArrayCondition::NotIn(vec!["0x3".to_string()]));
+        assert!(condition.evaluate(&value));
+        let condition = ValueCondition::Array(ArrayCondition::Contains("0x2".to_string()));
+        assert!(condition.evaluate(&value));
+    }
 }
@@ -1,19 +1,25 @@
 use super::{
     conditions::{
-        ArrayCondition, BlockHeaderCondition, ContractCondition, DynFieldCondition, EventCondition,
-        FilterCondition, NodeBuilder, NumericCondition, PoolCondition, StringCondition,
-        TransactionCondition, ValueCondition,
+        AddressCondition, ArrayCondition, BlockHeaderCondition, BytesCondition, ContractCondition,
+        DynFieldCondition, EventCondition, FilterCondition, HashCondition, NodeBuilder,
+        NumericCondition, Pattern, PoolCondition, StringCondition, TransactionCondition,
+        ValueCondition, ValueSet,
     },
+    error::FilterError,
     operations::{ArrayOps, NumericOps, StringOps},
 };
-use alloy_primitives::U256;
+use alloy_primitives::{Address, B256, I256, U256};
+use std::{any::type_name, fmt::Debug, hash::Hash, str::FromStr, time::Duration};
 
 pub struct U8FieldType<T>(pub T);
 pub struct U64FieldType<T>(pub T);
 pub struct U128FieldType<T>(pub T);
 pub struct U256FieldType<T>(pub T);
+pub struct DurationFieldType<T>(pub T);
 pub struct StringFieldType<T>(pub T);
 pub struct ArrayFieldType<T>(pub T);
+pub struct AddressFieldType<T>(pub T);
+pub struct HashFieldType<T>(pub T);
 
 pub struct DynValueFieldType<T>(pub T);
 
@@ -21,8 +27,12 @@ pub struct U8FieldCondition<T>(pub T, pub NumericCondition<u8>);
 pub struct U64FieldCondition<T>(pub T, pub NumericCondition<u64>);
 pub struct U128FieldCondition<T>(pub T, pub NumericCondition<u128>);
 pub struct U256FieldCondition<T>(pub T, pub NumericCondition<U256>);
+pub struct I256FieldCondition<T>(pub T, pub NumericCondition<I256>);
+pub struct DurationFieldCondition<T>(pub T, pub NumericCondition<Duration>);
 pub struct StringFieldCondition<T>(pub T, pub StringCondition);
 pub struct ArrayFieldCondition<T, V>(pub T, pub ArrayCondition<V>);
+pub struct AddressFieldCondition<T>(pub T, pub AddressCondition);
+pub struct HashFieldCondition<T>(pub T, pub HashCondition);
 
 // === Transaction Fields ======
 // Contract-specific fields
@@ -82,9 +92,15 @@ pub enum BlockField {
     GasLimit,   // Block gas limit
     BaseFee,    // Base fee per gas (EIP-1559)
     Miner,
-    StateRoot,        // State root hash
-    ReceiptsRoot,     // Receipts root hash
-    TransactionsRoot, // Transactions root hash
+    StateRoot,             // State root hash
+    ReceiptsRoot,          // Receipts root hash
+    TransactionsRoot,      // Transactions root hash
+    BlobGasUsed,           // Blob gas used in this block (EIP-4844)
+    ExcessBlobGas,         // Excess blob gas (EIP-4844)
+    WithdrawalsRoot,       // Withdrawals root hash (EIP-4895)
+    ParentBeaconBlockRoot, // Parent beacon block root (EIP-4788)
+    ExtraData,             // Arbitrary data set by the block producer
+    MixHash,               // Mix hash, `prevrandao` since the merge
 }
 
 // ==== Pool-specific fields (mempool) ====
@@ -98,6 +114,7 @@ pub enum PoolField {
     GasPrice,  // Gas price
     GasLimit,  // Gas limit
     Timestamp, // When tx added to pool
+    Age,       // How long tx has been in the pool
 }
 
 pub struct FieldWrapper<'a, T, P> {
@@ -105,74 +122,88 @@ pub struct FieldWrapper<'a, T, P> {
     pub(crate) parent: &'a mut P,
 }
 
-impl From<U64FieldCondition<TxField>> for TransactionCondition {
-    fn from(fc: U64FieldCondition<TxField>) -> TransactionCondition {
+impl TryFrom<U64FieldCondition<TxField>> for TransactionCondition {
+    type Error = FilterError;
+
+    fn try_from(fc: U64FieldCondition<TxField>) -> Result<TransactionCondition, FilterError> {
         let U64FieldCondition(field, value) = fc;
         match field {
-            TxField::Nonce => TransactionCondition::Nonce(value),
-            TxField::Gas => TransactionCondition::Gas(value),
-            TxField::ChainId => TransactionCondition::ChainId(value),
-            TxField::BlockNumber => TransactionCondition::BlockNumber(value),
-            TxField::TransactionIndex => TransactionCondition::TransactionIndex(value),
-            _ => panic!("Field does not support u64 numeric conditions"),
+            TxField::Nonce => Ok(TransactionCondition::Nonce(value)),
+            TxField::Gas => Ok(TransactionCondition::Gas(value)),
+            TxField::ChainId => Ok(TransactionCondition::ChainId(value)),
+            TxField::BlockNumber => Ok(TransactionCondition::BlockNumber(value)),
+            TxField::TransactionIndex => Ok(TransactionCondition::TransactionIndex(value)),
+            field => Err(unsupported(field, "u64 numeric")),
         }
     }
 }
 
-impl From<U256FieldCondition<TxField>> for TransactionCondition {
-    fn from(fc: U256FieldCondition<TxField>) -> TransactionCondition {
+impl TryFrom<U256FieldCondition<TxField>> for TransactionCondition {
+    type Error = FilterError;
+
+    fn try_from(fc: U256FieldCondition<TxField>) -> Result<TransactionCondition, FilterError> {
         let U256FieldCondition(field, value) = fc;
         match field {
-            TxField::Value => TransactionCondition::Value(value),
-            _ => panic!("Field does not support U256 numeric conditions"),
+            TxField::Value => Ok(TransactionCondition::Value(value)),
+            field => Err(unsupported(field, "U256 numeric")),
         }
     }
 }
 
-impl From<U8FieldCondition<TxField>> for TransactionCondition {
-    fn from(fc: U8FieldCondition<TxField>) -> TransactionCondition {
+impl TryFrom<U8FieldCondition<TxField>> for TransactionCondition {
+    type Error = FilterError;
+
+    fn try_from(fc: U8FieldCondition<TxField>) -> Result<TransactionCondition, FilterError> {
         let U8FieldCondition(field, value) = fc;
         match field {
-            TxField::Type => TransactionCondition::Type(value),
-            _ => panic!("Field does not support U8 numeric conditions"),
+            TxField::Type => Ok(TransactionCondition::Type(value)),
+            field => Err(unsupported(field, "u8 numeric")),
         }
     }
 }
 
-impl From<U128FieldCondition<TxField>> for TransactionCondition {
-    fn from(fc: U128FieldCondition<TxField>) -> TransactionCondition {
+impl TryFrom<U128FieldCondition<TxField>> for TransactionCondition {
+    type Error = FilterError;
+
+    fn try_from(fc: U128FieldCondition<TxField>) -> Result<TransactionCondition, FilterError> {
         let U128FieldCondition(field, value) = fc;
         match field {
-            TxField::GasPrice => TransactionCondition::GasPrice(value),
-            TxField::MaxFeePerGas => TransactionCondition::MaxFeePerGas(value),
-            TxField::MaxPriorityFee => TransactionCondition::MaxPriorityFee(value),
-            _ => panic!("Field does not support U128 numeric conditions"),
+            TxField::GasPrice => Ok(TransactionCondition::GasPrice(value)),
+            TxField::MaxFeePerGas => Ok(TransactionCondition::MaxFeePerGas(value)),
+            TxField::MaxPriorityFee => Ok(TransactionCondition::MaxPriorityFee(value)),
+            field => Err(unsupported(field, "u128 numeric")),
         }
     }
 }
 
-impl From<U64FieldCondition<EventField>> for EventCondition {
-    fn from(fc: U64FieldCondition<EventField>) -> EventCondition {
+impl TryFrom<U64FieldCondition<EventField>> for EventCondition {
+    type Error = FilterError;
+
+    fn try_from(fc: U64FieldCondition<EventField>) -> Result<EventCondition, FilterError> {
         let U64FieldCondition(field, value) = fc;
         match field {
-            EventField::LogIndex => EventCondition::LogIndex(value),
-            EventField::BlockNumber => EventCondition::BlockNumber(value),
-            EventField::TxIndex => EventCondition::TxIndex(value),
-            _ => panic!("Field does not support numeric conditions"),
+            EventField::LogIndex => Ok(EventCondition::LogIndex(value)),
+            EventField::BlockNumber => Ok(EventCondition::BlockNumber(value)),
+            EventField::TxIndex => Ok(EventCondition::TxIndex(value)),
+            field => Err(unsupported(field, "u64 numeric")),
         }
     }
 }
 
-impl From<U64FieldCondition<BlockField>> for BlockHeaderCondition {
-    fn from(fc: U64FieldCondition<BlockField>) -> BlockHeaderCondition {
+impl TryFrom<U64FieldCondition<BlockField>> for BlockHeaderCondition {
+    type Error = FilterError;
+
+    fn try_from(fc: U64FieldCondition<BlockField>) -> Result<BlockHeaderCondition, FilterError> {
         let U64FieldCondition(field, value) = fc;
         match field {
-            BlockField::Number => BlockHeaderCondition::Number(value),
-            BlockField::Timestamp => BlockHeaderCondition::Timestamp(value),
-            BlockField::GasUsed => BlockHeaderCondition::GasUsed(value),
-            BlockField::GasLimit => BlockHeaderCondition::GasLimit(value),
-            BlockField::BaseFee => BlockHeaderCondition::BaseFee(value),
-            _ => panic!("Field does not support u64 numeric conditions"),
+            BlockField::Number => Ok(BlockHeaderCondition::Number(value)),
+            BlockField::Timestamp => Ok(BlockHeaderCondition::Timestamp(value)),
+            BlockField::GasUsed => Ok(BlockHeaderCondition::GasUsed(value)),
+            BlockField::GasLimit => Ok(BlockHeaderCondition::GasLimit(value)),
+            BlockField::BaseFee => Ok(BlockHeaderCondition::BaseFee(value)),
+            BlockField::BlobGasUsed => Ok(BlockHeaderCondition::BlobGasUsed(value)),
+            BlockField::ExcessBlobGas => Ok(BlockHeaderCondition::ExcessBlobGas(value)),
+            field => Err(unsupported(field, "u64 numeric")),
         }
     }
 }
@@ -205,9 +236,24 @@ impl From<U256FieldCondition<ContractField>> for ContractCondition {
     }
 }
 
+impl From<I256FieldCondition<ContractField>> for ContractCondition {
+    fn from(fc: I256FieldCondition<ContractField>) -> ContractCondition {
+        let I256FieldCondition(field, value) = fc;
+        match field {
+            ContractField::Parameter(path) => {
+                ContractCondition::Parameter(path.to_string(), ValueCondition::I256(value))
+            }
+            ContractField::Path(path) => {
+                ContractCondition::Path(path.to_string(), ValueCondition::I256(value))
+            }
+        }
+    }
+}
+
 impl From<StringFieldCondition<ContractField>> for ContractCondition {
     fn from(fc: StringFieldCondition<ContractField>) -> ContractCondition {
         let StringFieldCondition(field, value) = fc;
+        let value = value.with_lowercase_addresses();
         match field {
             ContractField::Parameter(path) => {
                 ContractCondition::Parameter(path.to_string(), ValueCondition::String(value))
@@ -219,33 +265,52 @@ impl From<StringFieldCondition<ContractField>> for ContractCondition {
     }
 }
 
-impl From<U64FieldCondition<PoolField>> for PoolCondition {
-    fn from(fc: U64FieldCondition<PoolField>) -> PoolCondition {
+impl TryFrom<U64FieldCondition<PoolField>> for PoolCondition {
+    type Error = FilterError;
+
+    fn try_from(fc: U64FieldCondition<PoolField>) -> Result<PoolCondition, FilterError> {
         let U64FieldCondition(field, value) = fc;
         match field {
-            PoolField::Nonce => PoolCondition::Nonce(value),
-            PoolField::GasLimit => PoolCondition::GasLimit(value),
-            _ => panic!("Field does not support u64 numeric conditions"),
+            PoolField::Nonce => Ok(PoolCondition::Nonce(value)),
+            PoolField::GasLimit => Ok(PoolCondition::GasLimit(value)),
+            PoolField::Timestamp => Ok(PoolCondition::Timestamp(value)),
+            field => Err(unsupported(field, "u64 numeric")),
         }
     }
 }
 
-impl From<U128FieldCondition<PoolField>> for PoolCondition {
-    fn from(fc: U128FieldCondition<PoolField>) -> PoolCondition {
+impl TryFrom<U128FieldCondition<PoolField>> for PoolCondition {
+    type Error = FilterError;
+
+    fn try_from(fc: U128FieldCondition<PoolField>) -> Result<PoolCondition, FilterError> {
         let U128FieldCondition(field, value) = fc;
         match field {
-            PoolField::GasPrice => PoolCondition::GasPrice(value),
-            _ => panic!("Field does not support U128 numeric conditions"),
+            PoolField::GasPrice => Ok(PoolCondition::GasPrice(value)),
+            field => Err(unsupported(field, "u128 numeric")),
         }
     }
 }
 
-impl From<U256FieldCondition<PoolField>> for PoolCondition {
-    fn from(fc: U256FieldCondition<PoolField>) -> PoolCondition {
+impl TryFrom<U256FieldCondition<PoolField>> for PoolCondition {
+    type Error = FilterError;
+
+    fn try_from(fc: U256FieldCondition<PoolField>) -> Result<PoolCondition, FilterError> {
         let U256FieldCondition(field, value) = fc;
         match field {
-            PoolField::Value => PoolCondition::Value(value),
-            _ => panic!("Field does not support U256 numeric conditions"),
+            PoolField::Value => Ok(PoolCondition::Value(value)),
+            field => Err(unsupported(field, "U256 numeric")),
+        }
+    }
+}
+
+impl TryFrom<DurationFieldCondition<PoolField>> for PoolCondition {
+    type Error = FilterError;
+
+    fn try_from(fc: DurationFieldCondition<PoolField>) -> Result<PoolCondition, FilterError> {
+        let DurationFieldCondition(field, value) = fc;
+        match field {
+            PoolField::Age => Ok(PoolCondition::Age(value)),
+            field => Err(unsupported(field, "duration")),
         }
     }
 }
@@ -315,7 +380,7 @@ impl From<StringFieldCondition<DynField>> for TransactionCondition {
         let StringFieldCondition(field, value) = fc;
         TransactionCondition::DynField(DynFieldCondition {
             path: field.0,
-            condition: ValueCondition::String(value),
+            condition: ValueCondition::String(value.with_lowercase_addresses()),
         })
     }
 }
@@ -355,66 +420,113 @@ impl From<StringFieldCondition<DynField>> for FilterCondition {
         let StringFieldCondition(field, value) = fc;
         FilterCondition::DynField(DynFieldCondition {
             path: field.0,
-            condition: ValueCondition::String(value),
+            condition: ValueCondition::String(value.with_lowercase_addresses()),
         })
     }
 }
 
+/// Describes a field that has no condition for the given kind of operation.
+fn unsupported<F: Debug>(field: F, operation: &str) -> FilterError {
+    let field_type = type_name::<F>().rsplit("::").next().unwrap_or_default();
+    FilterError::UnsupportedOperation {
+        field: format!("{field_type}::{field:?}"),
+        operation: operation.to_string(),
+    }
+}
+
+/// Appends the condition of a field to its builder, or records why the field doesn't support it.
+fn append_condition<F, P, C>(parent: &mut P, field_condition: F)
+where
+    F: TryInto<C, Error: Into<FilterError>>,
+    P: NodeBuilder<Condition = C>,
+{
+    match field_condition.try_into() {
+        Ok(condition) => parent.append_node(condition),
+        Err(e) => parent.append_error(e.into()),
+    }
+}
+
 macro_rules! impl_numeric_ops {
     ($type:ty, $field_type:ident, $condition_type:ident) => {
         impl<T, P, C> NumericOps<$type> for FieldWrapper<'_, $field_type<T>, P>
         where
-            $condition_type<T>: Into<C>,
+            $condition_type<T>: TryInto<C, Error: Into<FilterError>>,
             P: NodeBuilder<Condition = C>,
         {
             fn gt(self, value: $type) {
-                let condition =
-                    $condition_type(self.field.0, NumericCondition::GreaterThan(value)).into();
-                self.parent.append_node(condition);
+                append_condition(
+                    self.parent,
+                    $condition_type(self.field.0, NumericCondition::GreaterThan(value)),
+                );
             }
 
             fn lt(self, value: $type) {
-                let condition =
-                    $condition_type(self.field.0, NumericCondition::LessThan(value)).into();
-                self.parent.append_node(condition);
+                append_condition(
+                    self.parent,
+                    $condition_type(self.field.0, NumericCondition::LessThan(value)),
+                );
             }
 
             fn eq(self, value: $type) {
-                let condition =
-                    $condition_type(self.field.0, NumericCondition::EqualTo(value)).into();
-                self.parent.append_node(condition);
+                append_condition(
+                    self.parent,
+                    $condition_type(self.field.0, NumericCondition::EqualTo(value)),
+                );
             }
 
             fn lte(self, value: $type) {
-                let condition =
-                    $condition_type(self.field.0, NumericCondition::LessThanOrEqualTo(value))
-                        .into();
-                self.parent.append_node(condition);
+                append_condition(
+                    self.parent,
+                    $condition_type(self.field.0, NumericCondition::LessThanOrEqualTo(value)),
+                );
             }
 
             fn gte(self, value: $type) {
-                let condition =
-                    $condition_type(self.field.0, NumericCondition::GreaterThanOrEqualTo(value))
-                        .into();
-                self.parent.append_node(condition);
+                append_condition(
+                    self.parent,
+                    $condition_type(self.field.0, NumericCondition::GreaterThanOrEqualTo(value)),
+                );
             }
 
             fn neq(self, value: $type) {
-                let condition =
-                    $condition_type(self.field.0, NumericCondition::NotEqualTo(value)).into();
-                self.parent.append_node(condition);
+                append_condition(
+                    self.parent,
+                    $condition_type(self.field.0, NumericCondition::NotEqualTo(value)),
+                );
             }
 
             fn between(self, min: $type, max: $type) {
-                let condition =
-                    $condition_type(self.field.0, NumericCondition::Between(min, max)).into();
-                self.parent.append_node(condition);
+                append_condition(
+                    self.parent,
+                    $condition_type(self.field.0, NumericCondition::Between(min, max)),
+                );
             }
 
             fn outside(self, min: $type, max: $type) {
-                let condition =
-                    $condition_type(self.field.0, NumericCondition::Outside(min, max)).into();
-                self.parent.append_node(condition);
+                append_condition(
+                    self.parent,
+                    $condition_type(self.field.0, NumericCondition::Outside(min, max)),
+                );
+            }
+
+            fn in_set<I: IntoIterator<Item = $type>>(self, values: I) {
+                append_condition(
+                    self.parent,
+                    $condition_type(
+                        self.field.0,
+                        NumericCondition::In(values.into_iter().collect()),
+                    ),
+                );
+            }
+
+            fn not_in_set<I: IntoIterator<Item = $type>>(self, values: I) {
+                append_condition(
+                    self.parent,
+                    $condition_type(
+                        self.field.0,
+                        NumericCondition::NotIn(values.into_iter().collect()),
+                    ),
+                );
             }
         }
     };
@@ -425,58 +537,50 @@ impl_numeric_ops!(u8, U8FieldType, U8FieldCondition);
 impl_numeric_ops!(u64, U64FieldType, U64FieldCondition);
 impl_numeric_ops!(u128, U128FieldType, U128FieldCondition);
 impl_numeric_ops!(U256, U256FieldType, U256FieldCondition);
+impl_numeric_ops!(Duration, DurationFieldType, DurationFieldCondition);
 
 impl_numeric_ops!(u64, DynValueFieldType, U64FieldCondition);
 impl_numeric_ops!(u128, DynValueFieldType, U128FieldCondition);
 impl_numeric_ops!(U256, DynValueFieldType, U256FieldCondition);
+impl_numeric_ops!(I256, DynValueFieldType, I256FieldCondition);
 
-impl From<StringFieldCondition<TxField>> for TransactionCondition {
-    fn from(fc: StringFieldCondition<TxField>) -> TransactionCondition {
-        let StringFieldCondition(field, value) = fc;
-        match field {
-            TxField::From => TransactionCondition::From(value),
-            TxField::To => TransactionCondition::To(value),
-            TxField::Hash => TransactionCondition::Hash(value),
-            TxField::BlockHash => TransactionCondition::BlockHash(value),
-            // Non-string fields should panic
-            _ => panic!("Field does not support string conditions"),
-        }
-    }
-}
+impl TryFrom<StringFieldCondition<EventField>> for EventCondition {
+    type Error = FilterError;
 
-impl From<StringFieldCondition<EventField>> for EventCondition {
-    fn from(fc: StringFieldCondition<EventField>) -> EventCondition {
+    fn try_from(fc: StringFieldCondition<EventField>) -> Result<EventCondition, FilterError> {
         let StringFieldCondition(field, value) = fc;
         match field {
-            EventField::Contract => EventCondition::Contract(value),
-            EventField::BlockHash => EventCondition::BlockHash(value),
-            EventField::TxHash => EventCondition::TxHash(value),
-            _ => panic!("Field does not support string conditions"),
+            EventField::BlockHash => Ok(EventCondition::BlockHash(value)),
+            EventField::TxHash => Ok(EventCondition::TxHash(value)),
+            field => Err(unsupported(field, "string")),
         }
     }
 }
 
-impl From<StringFieldCondition<PoolField>> for PoolCondition {
-    fn from(fc: StringFieldCondition<PoolField>) -> PoolCondition {
+impl TryFrom<StringFieldCondition<BlockField>> for BlockHeaderCondition {
+    type Error = FilterError;
+
+    fn try_from(fc: StringFieldCondition<BlockField>) -> Result<BlockHeaderCondition, FilterError> {
         let StringFieldCondition(field, value) = fc;
         match field {
-            PoolField::Hash => PoolCondition::Hash(value),
-            PoolField::From => PoolCondition::From(value),
-            PoolField::To => PoolCondition::To(value),
-            _ => panic!("Field does not support string conditions"),
+            BlockField::ParentHash => Ok(BlockHeaderCondition::ParentHash(value)),
+            BlockField::StateRoot => Ok(BlockHeaderCondition::StateRoot(value)),
+            BlockField::ReceiptsRoot => Ok(BlockHeaderCondition::ReceiptsRoot(value)),
+            BlockField::TransactionsRoot => Ok(BlockHeaderCondition::TransactionsRoot(value)),
+            BlockField::ExtraData => Ok(BlockHeaderCondition::ExtraData(value)),
+            field => Err(unsupported(field, "string")),
         }
     }
 }
 
-impl From<StringFieldCondition<BlockField>> for BlockHeaderCondition {
-    fn from(fc: StringFieldCondition<BlockField>) -> BlockHeaderCondition {
-        let StringFieldCondition(field, value) = fc;
+impl TryFrom<U256FieldCondition<BlockField>> for BlockHeaderCondition {
+    type Error = FilterError;
+
+    fn try_from(fc: U256FieldCondition<BlockField>) -> Result<BlockHeaderCondition, FilterError> {
+        let U256FieldCondition(field, value) = fc;
         match field {
-            BlockField::ParentHash => BlockHeaderCondition::ParentHash(value),
-            BlockField::StateRoot => BlockHeaderCondition::StateRoot(value),
-            BlockField::ReceiptsRoot => BlockHeaderCondition::ReceiptsRoot(value),
-            BlockField::TransactionsRoot => BlockHeaderCondition::TransactionsRoot(value),
-            _ => panic!("Field does not support string conditions"),
+            BlockField::Size => Ok(BlockHeaderCondition::Size(value)),
+            field => Err(unsupported(field, "U256 numeric")),
         }
     }
 }
@@ -486,48 +590,86 @@ macro_rules! impl_string_ops {
     ($field_type:ident, $condition_type:ident) => {
         impl<T, P, C> StringOps for FieldWrapper<'_, $field_type<T>, P>
         where
-            $condition_type<T>: Into<C>,
+            $condition_type<T>: TryInto<C, Error: Into<FilterError>>,
             P: NodeBuilder<Condition = C>,
         {
             fn starts_with(self, prefix: &str) {
-                let condition = $condition_type(
-                    self.field.0,
-                    StringCondition::StartsWith(prefix.to_string()),
-                )
-                .into();
-                self.parent.append_node(condition);
+                append_condition(
+                    self.parent,
+                    $condition_type(
+                        self.field.0,
+                        StringCondition::StartsWith(prefix.to_string()),
+                    ),
+                );
             }
 
             fn ends_with(self, suffix: &str) {
-                let condition =
-                    $condition_type(self.field.0, StringCondition::EndsWith(suffix.to_string()))
-                        .into();
-                self.parent.append_node(condition);
+                append_condition(
+                    self.parent,
+                    $condition_type(self.field.0, StringCondition::EndsWith(suffix.to_string())),
+                );
             }
 
             fn contains(self, substring: &str) {
-                let condition = $condition_type(
-                    self.field.0,
-                    StringCondition::Contains(substring.to_string()),
-                )
-                .into();
-                self.parent.append_node(condition);
+                append_condition(
+                    self.parent,
+                    $condition_type(
+                        self.field.0,
+                        StringCondition::Contains(substring.to_string()),
+                    ),
+                );
             }
 
             fn matches(self, regex_pattern: &str) {
-                let condition = $condition_type(
-                    self.field.0,
-                    StringCondition::Matches(regex_pattern.to_string()),
-                )
-                .into();
-                self.parent.append_node(condition);
+                match Pattern::new(regex_pattern) {
+                    Ok(pattern) => {
+                        append_condition(
+                            self.parent,
+                            $condition_type(self.field.0, StringCondition::Matches(pattern)),
+                        );
+                    }
+                    Err(e) => self.parent.append_error(FilterError::InvalidRegex {
+                        pattern: regex_pattern.to_string(),
+                        reason: e.to_string(),
+                    }),
+                }
             }
 
             fn exact(self, value: &str) {
-                let condition =
-                    $condition_type(self.field.0, StringCondition::EqualTo(value.to_string()))
-                        .into();
-                self.parent.append_node(condition);
+                append_condition(
+                    self.parent,
+                    $condition_type(self.field.0, StringCondition::EqualTo(value.to_string())),
+                );
+            }
+
+            fn in_set<I, S>(self, values: I)
+            where
+                I: IntoIterator<Item = S>,
+                S: AsRef<str>,
+            {
+                let set = values
+                    .into_iter()
+                    .map(|value| value.as_ref().to_string())
+                    .collect();
+                append_condition(
+                    self.parent,
+                    $condition_type(self.field.0, StringCondition::In(set)),
+                );
+            }
+
+            fn not_in_set<I, S>(self, values: I)
+            where
+                I: IntoIterator<Item = S>,
+                S: AsRef<str>,
+            {
+                let set = values
+                    .into_iter()
+                    .map(|value| value.as_ref().to_string())
+                    .collect();
+                append_condition(
+                    self.parent,
+                    $condition_type(self.field.0, StringCondition::NotIn(set)),
+                );
             }
         }
     };
@@ -536,22 +678,278 @@ macro_rules! impl_string_ops {
 impl_string_ops!(DynValueFieldType, StringFieldCondition);
 impl_string_ops!(StringFieldType, StringFieldCondition);
 
-impl From<ArrayFieldCondition<TxField, String>> for TransactionCondition {
-    fn from(fc: ArrayFieldCondition<TxField, String>) -> TransactionCondition {
+impl TryFrom<AddressFieldCondition<TxField>> for TransactionCondition {
+    type Error = FilterError;
+
+    fn try_from(fc: AddressFieldCondition<TxField>) -> Result<TransactionCondition, FilterError> {
+        let AddressFieldCondition(field, value) = fc;
+        match field {
+            TxField::From => Ok(TransactionCondition::From(value)),
+            TxField::To => Ok(TransactionCondition::To(value)),
+            field => Err(unsupported(field, "address")),
+        }
+    }
+}
+
+impl TryFrom<HashFieldCondition<TxField>> for TransactionCondition {
+    type Error = FilterError;
+
+    fn try_from(fc: HashFieldCondition<TxField>) -> Result<TransactionCondition, FilterError> {
+        let HashFieldCondition(field, value) = fc;
+        match field {
+            TxField::Hash => Ok(TransactionCondition::Hash(value)),
+            TxField::BlockHash => Ok(TransactionCondition::BlockHash(value)),
+            field => Err(unsupported(field, "hash")),
+        }
+    }
+}
+
+impl TryFrom<AddressFieldCondition<EventField>> for EventCondition {
+    type Error = FilterError;
+
+    fn try_from(fc: AddressFieldCondition<EventField>) -> Result<EventCondition, FilterError> {
+        let AddressFieldCondition(field, value) = fc;
+        match field {
+            EventField::Contract => Ok(EventCondition::Contract(value)),
+            field => Err(unsupported(field, "address")),
+        }
+    }
+}
+
+impl TryFrom<AddressFieldCondition<BlockField>> for BlockHeaderCondition {
+    type Error = FilterError;
+
+    fn try_from(
+        fc: AddressFieldCondition<BlockField>,
+    ) -> Result<BlockHeaderCondition, FilterError> {
+        let AddressFieldCondition(field, value) = fc;
+        match field {
+            BlockField::Miner => Ok(BlockHeaderCondition::Miner(value)),
+            field => Err(unsupported(field, "address")),
+        }
+    }
+}
+
+impl TryFrom<HashFieldCondition<BlockField>> for BlockHeaderCondition {
+    type Error = FilterError;
+
+    fn try_from(fc: HashFieldCondition<BlockField>) -> Result<BlockHeaderCondition, FilterError> {
+        let HashFieldCondition(field, value) = fc;
+        match field {
+            BlockField::Hash => Ok(BlockHeaderCondition::Hash(value)),
+            BlockField::WithdrawalsRoot => Ok(BlockHeaderCondition::WithdrawalsRoot(value)),
+            BlockField::ParentBeaconBlockRoot => {
+                Ok(BlockHeaderCondition::ParentBeaconBlockRoot(value))
+            }
+            BlockField::MixHash => Ok(BlockHeaderCondition::MixHash(value)),
+            field => Err(unsupported(field, "hash")),
+        }
+    }
+}
+
+impl TryFrom<AddressFieldCondition<PoolField>> for PoolCondition {
+    type Error = FilterError;
+
+    fn try_from(fc: AddressFieldCondition<PoolField>) -> Result<PoolCondition, FilterError> {
+        let AddressFieldCondition(field, value) = fc;
+        match field {
+            PoolField::From => Ok(PoolCondition::From(value)),
+            PoolField::To => Ok(PoolCondition::To(value)),
+            field => Err(unsupported(field, "address")),
+        }
+    }
+}
+
+impl TryFrom<HashFieldCondition<PoolField>> for PoolCondition {
+    type Error = FilterError;
+
+    fn try_from(fc: HashFieldCondition<PoolField>) -> Result<PoolCondition, FilterError> {
+        let HashFieldCondition(field, value) = fc;
+        match field {
+            PoolField::Hash => Ok(PoolCondition::Hash(value)),
+            field => Err(unsupported(field, "hash")),
+        }
+    }
+}
+
+// === StringOps on addresses & hashes =====
+// `exact` values are parsed when the filter is built and compared as bytes, the other
+// operations run on the lowercase hex string so they are case-insensitive.
+macro_rules! impl_bytes_ops {
+    ($field_type:ident, $condition_type:ident, $bytes:ty, $error:ident) => {
+        impl<T, P, C> StringOps for FieldWrapper<'_, $field_type<T>, P>
+        where
+            $condition_type<T>: TryInto<C, Error: Into<FilterError>>,
+            P: NodeBuilder<Condition = C>,
+        {
+            fn starts_with(self, prefix: &str) {
+                append_condition(
+                    self.parent,
+                    $condition_type(
+                        self.field.0,
+                        BytesCondition::Hex(StringCondition::StartsWith(prefix.to_lowercase())),
+                    ),
+                );
+            }
+
+            fn ends_with(self, suffix: &str) {
+                append_condition(
+                    self.parent,
+                    $condition_type(
+                        self.field.0,
+                        BytesCondition::Hex(StringCondition::EndsWith(suffix.to_lowercase())),
+                    ),
+                );
+            }
+
+            fn contains(self, substring: &str) {
+                append_condition(
+                    self.parent,
+                    $condition_type(
+                        self.field.0,
+                        BytesCondition::Hex(StringCondition::Contains(substring.to_lowercase())),
+                    ),
+                );
+            }
+
+            fn matches(self, regex_pattern: &str) {
+                match Pattern::case_insensitive(regex_pattern) {
+                    Ok(pattern) => {
+                        append_condition(
+                            self.parent,
+                            $condition_type(
+                                self.field.0,
+                                BytesCondition::Hex(StringCondition::Matches(pattern)),
+                            ),
+                        );
+                    }
+                    Err(e) => self.parent.append_error(FilterError::InvalidRegex {
+                        pattern: regex_pattern.to_string(),
+                        reason: e.to_string(),
+                    }),
+                }
+            }
+
+            fn exact(self, value: &str) {
+                match value.parse::<$bytes>() {
+                    Ok(bytes) => {
+                        append_condition(
+                            self.parent,
+                            $condition_type(self.field.0, BytesCondition::EqualTo(bytes)),
+                        );
+                    }
+                    Err(e) => self.parent.append_error(FilterError::$error {
+                        value: value.to_string(),
+                        reason: e.to_string(),
+                    }),
+                }
+            }
+
+            fn in_set<I, S>(self, values: I)
+            where
+                I: IntoIterator<Item = S>,
+                S: AsRef<str>,
+            {
+                match parse_set::<$bytes, _, _>(values) {
+                    Ok(set) => {
+                        append_condition(
+                            self.parent,
+                            $condition_type(self.field.0, BytesCondition::In(set)),
+                        );
+                    }
+                    Err((value, reason)) => self
+                        .parent
+                        .append_error(FilterError::$error { value, reason }),
+                }
+            }
+
+            fn not_in_set<I, S>(self, values: I)
+            where
+                I: IntoIterator<Item = S>,
+                S: AsRef<str>,
+            {
+                match parse_set::<$bytes, _, _>(values) {
+                    Ok(set) => {
+                        append_condition(
+                            self.parent,
+                            $condition_type(self.field.0, BytesCondition::NotIn(set)),
+                        );
+                    }
+                    Err((value, reason)) => self
+                        .parent
+                        .append_error(FilterError::$error { value, reason }),
+                }
+            }
+        }
+    };
+}
+
+/// Parses every value of a set, returning the first invalid value and the reason it failed.
+fn parse_set<B, I, S>(values: I) -> Result<ValueSet<B>, (String, String)>
+where
+    B: FromStr + Hash + Eq,
+    B::Err: ToString,
+    I: IntoIterator<Item = S>,
+    S: AsRef<str>,
+{
+    values
+        .into_iter()
+        .map(|value| {
+            let value = value.as_ref();
+            value
+                .parse::<B>()
+                .map_err(|e| (value.to_string(), e.to_string()))
+        })
+        .collect()
+}
+
+impl_bytes_ops!(
+    AddressFieldType,
+    AddressFieldCondition,
+    Address,
+    InvalidAddress
+);
+impl_bytes_ops!(HashFieldType, HashFieldCondition, B256, InvalidHash);
+
+impl TryFrom<ArrayFieldCondition<TxField, String>> for TransactionCondition {
+    type Error = FilterError;
+
+    fn try_from(
+        fc: ArrayFieldCondition<TxField, String>,
+    ) -> Result<TransactionCondition, FilterError> {
+        let ArrayFieldCondition(field, value) = fc;
+        match field {
+            TxField::AccessList => Ok(TransactionCondition::AccessList(value)),
+            field => Err(unsupported(field, "array")),
+        }
+    }
+}
+
+impl TryFrom<ArrayFieldCondition<EventField, String>> for EventCondition {
+    type Error = FilterError;
+
+    fn try_from(
+        fc: ArrayFieldCondition<EventField, String>,
+    ) -> Result<EventCondition, FilterError> {
         let ArrayFieldCondition(field, value) = fc;
         match field {
-            TxField::AccessList => TransactionCondition::AccessList(value),
-            _ => panic!("Field does not support string array conditions"),
+            EventField::Topics => Ok(EventCondition::Topics(value)),
+            field => Err(unsupported(field, "array")),
         }
     }
 }
 
-impl From<ArrayFieldCondition<EventField, String>> for EventCondition {
-    fn from(fc: ArrayFieldCondition<EventField, String>) -> EventCondition {
+impl From<ArrayFieldCondition<ContractField, String>> for ContractCondition {
+    fn from(fc: ArrayFieldCondition<ContractField, String>) -> ContractCondition {
         let ArrayFieldCondition(field, value) = fc;
+        let value = value.with_lowercase_addresses();
         match field {
-            EventField::Topics => EventCondition::Topics(value),
-            _ => panic!("Field does not support array conditions"),
+            ContractField::Parameter(path) => {
+                ContractCondition::Parameter(path.to_string(), ValueCondition::Array(value))
+            }
+            ContractField::Path(path) => {
+                ContractCondition::Path(path.to_string(), ValueCondition::Array(value))
+            }
         }
     }
 }
@@ -560,29 +958,35 @@ macro_rules! impl_array_ops {
     ($value_type:ty) => {
         impl<F, B, C> ArrayOps<$value_type> for FieldWrapper<'_, ArrayFieldType<F>, B>
         where
-            ArrayFieldCondition<F, $value_type>: Into<C>,
+            ArrayFieldCondition<F, $value_type>: TryInto<C, Error: Into<FilterError>>,
             B: NodeBuilder<Condition = C>,
         {
             fn contains(self, value: $value_type) {
-                let condition =
-                    ArrayFieldCondition(self.field.0, ArrayCondition::Contains(value)).into();
-                self.parent.append_node(condition);
+                append_condition(
+                    self.parent,
+                    ArrayFieldCondition(self.field.0, ArrayCondition::Contains(value)),
+                );
             }
 
             fn not_in(self, values: Vec<$value_type>) {
-                let condition =
-                    ArrayFieldCondition(self.field.0, ArrayCondition::NotIn(values)).into();
-                self.parent.append_node(condition);
+                append_condition(
+                    self.parent,
+                    ArrayFieldCondition(self.field.0, ArrayCondition::NotIn(values)),
+                );
             }
 
             fn empty(self) {
-                let condition = ArrayFieldCondition(self.field.0, ArrayCondition::Empty).into();
-                self.parent.append_node(condition);
+                append_condition(
+                    self.parent,
+                    ArrayFieldCondition(self.field.0, ArrayCondition::Empty),
+                );
             }
 
             fn not_empty(self) {
-                let condition = ArrayFieldCondition(self.field.0, ArrayCondition::NotEmpty).into();
-                self.parent.append_node(condition);
+                append_condition(
+                    self.parent,
+                    ArrayFieldCondition(self.field.0, ArrayCondition::NotEmpty),
+                );
             }
         }
     };
@@ -1,9 +1,10 @@
 mod builders;
 pub(crate) mod conditions;
+pub(crate) mod error;
 pub(crate) mod evaluate;
 mod field;
 mod operations;
-mod priority;
 
 pub use builders::builder::FilterBuilder;
+pub use error::FilterError;
 pub use operations::{ArrayOps, LogicalOps, NumericOps, StringOps};
@@ -29,6 +29,12 @@ pub trait NumericOps<T: NumericType> {
 
     /// Creates an "outside" condition checking if value is outside the specified range (exclusive).
     fn outside(self, min: T, max: T);
+
+    /// Creates a condition that matches values equal to any of the specified values.
+    fn in_set<I: IntoIterator<Item = T>>(self, values: I);
+
+    /// Creates a condition that matches values equal to none of the specified values.
+    fn not_in_set<I: IntoIterator<Item = T>>(self, values: I);
 }
 
 /// Operations available for string fields that allow various string matching operations.
@@ -49,6 +55,18 @@ pub trait StringOps {
 
     /// Creates a condition that matches strings exactly equal to the specified value.
     fn exact(self, value: &str);
+
+    /// Creates a condition that matches strings exactly equal to any of the specified values.
+    fn in_set<I, S>(self, values: I)
+    where
+        I: IntoIterator<Item = S>,
+        S: AsRef<str>;
+
+    /// Creates a condition that matches strings exactly equal to none of the specified values.
+    fn not_in_set<I, S>(self, values: I)
+    where
+        I: IntoIterator<Item = S>,
+        S: AsRef<str>;
 }
 
 /// Operations available for array fields that allow various array matching operations.
@@ -72,7 +90,7 @@ pub trait LogicalOps<B: FilterBuilderOps> {
     /// Combines conditions with AND logic, requiring all conditions to be true.
     ///
     /// Returns a [`LogicalFilterBuilder`] for further configuration.
-    fn and<F>(&mut self, f: F) -> LogicalFilterBuilder<B>
+    fn and<F>(&mut self, f: F) -> LogicalFilterBuilder<'_, B>
     where
         F: FnOnce(&mut B);
 
@@ -81,14 +99,14 @@ pub trait LogicalOps<B: FilterBuilderOps> {
     /// that must all be satisfied.
     ///
     /// Returns a [`LogicalFilterBuilder`] for further configuration.
-    fn all_of<F>(&mut self, f: F) -> LogicalFilterBuilder<B>
+    fn all_of<F>(&mut self, f: F) -> LogicalFilterBuilder<'_, B>
     where
         F: FnOnce(&mut B);
 
     /// Applies a NOT operation to the given conditions.
     ///
     /// Returns a [`LogicalFilterBuilder`] for further configuration.
-    fn not<F>(&mut self, f: F) -> LogicalFilterBuilder<B>
+    fn not<F>(&mut self, f: F) -> LogicalFilterBuilder<'_, B>
     where
         F: FnOnce(&mut B);
 
@@ -96,14 +114,14 @@ pub trait LogicalOps<B: FilterBuilderOps> {
     /// Provides a more readable way to express "except when" conditions.
     ///
     /// Returns a [`LogicalFilterBuilder`] for further configuration.
-    fn unless<F>(&mut self, f: F) -> LogicalFilterBuilder<B>
+    fn unless<F>(&mut self, f: F) -> LogicalFilterBuilder<'_, B>
     where
         F: FnOnce(&mut B);
 
     /// Combines conditions with OR logic, requiring at least one condition to be true.
     ///
     /// Returns a [`LogicalFilterBuilder`] for further configuration.
-    fn or<F>(&mut self, f: F) -> LogicalFilterBuilder<B>
+    fn or<F>(&mut self, f: F) -> LogicalFilterBuilder<'_, B>
     where
         F: FnOnce(&mut B);
 
@@ -112,7 +130,7 @@ pub trait LogicalOps<B: FilterBuilderOps> {
     /// of multiple conditions should match.
     ///
     /// Returns a [`LogicalFilterBuilder`] for further configuration.
-    fn any_of<F>(&mut self, f: F) -> LogicalFilterBuilder<B>
+    fn any_of<F>(&mut self, f: F) -> LogicalFilterBuilder<'_, B>
     where
         F: FnOnce(&mut B);
 }
@@ -1,3 +1,4 @@
+use alloy_primitives::B256;
 use lru::LruCache;
 use std::{num::NonZeroUsize, sync::Arc};
 use tokio::sync::{broadcast, RwLock};
@@ -22,25 +23,25 @@ pub enum ChainStreamError {
 pub(crate) struct ChainStream {
     chain: Chain,
     sender: broadcast::Sender<ChainData>,
-    block_header_cache: Arc<RwLock<LruCache<String, ()>>>,
-    tx_cache: Arc<RwLock<LruCache<String, ()>>>,
+    /// Recently seen blocks keyed by number and hash
+    block_header_cache: Arc<RwLock<LruCache<(u64, B256), ()>>>,
+    /// Recently seen pending transactions keyed by hash
+    tx_cache: Arc<RwLock<LruCache<B256, ()>>>,
 }
 
 #[allow(dead_code)]
 impl ChainStream {
-    /// Creates a new [`ChainStream`] instance with specified chain.
-    pub fn new(chain: Chain) -> Self {
+    /// Creates a new [`ChainStream`] instance with specified chain, remembering up to
+    /// `cache_size` blocks and transactions to drop duplicates.
+    pub fn new(chain: Chain, cache_size: usize) -> Self {
         let (sender, _) = broadcast::channel(100);
+        let cache_size = NonZeroUsize::new(cache_size).unwrap_or(NonZeroUsize::MIN);
 
         Self {
             chain,
             sender,
-            block_header_cache: Arc::new(RwLock::new(LruCache::new(
-                NonZeroUsize::new(1_0000).unwrap(),
-            ))),
-            tx_cache: Arc::new(RwLock::new(LruCache::new(
-                NonZeroUsize::new(1_0000).unwrap(),
-            ))),
+            block_header_cache: Arc::new(RwLock::new(LruCache::new(cache_size))),
+            tx_cache: Arc::new(RwLock::new(LruCache::new(cache_size))),
         }
     }
     /// Processes incoming chain data, caching and broadcasting new blocks and transactions.
@@ -49,7 +50,7 @@ impl ChainStream {
         match data {
             ChainData::AnyRPCNetwork(eth_data) => match eth_data {
                 AnyRPCNetwork::Block(block) => {
-                    let block_id = format!("{:?}-{:?}", block.header.number, block.header.hash);
+                    let block_id = (block.header.number, block.header.hash);
 
                     let mut cache = self.block_header_cache.write().await;
                     if cache.put(block_id, ()).is_none() {
@@ -61,13 +62,30 @@ impl ChainStream {
                 AnyRPCNetwork::TransactionPool(tx) => {
                     let mut cache = self.tx_cache.write().await;
 
-                    let tx_id = format!("{:?}-{:?}", tx.transaction_index, tx.block_hash);
+                    let tx_id = tx.transaction.info().hash.unwrap_or_default();
                     if cache.put(tx_id, ()).is_none() {
                         self.sender
                             .send(ChainData::AnyRPCNetwork(AnyRPCNetwork::TransactionPool(tx)))
                             .map_err(ChainStreamError::BroadcastError)?;
                     }
                 }
+                AnyRPCNetwork::TransactionPoolRemovals(hashes) => {
+                    // Transactions re-entering the pool are broadcast again
+                    let mut cache = self.tx_cache.write().await;
+                    for hash in &hashes {
+                        cache.pop(hash);
+                    }
+                    self.sender
+                        .send(ChainData::AnyRPCNetwork(
+                            AnyRPCNetwork::TransactionPoolRemovals(hashes),
+                        ))
+                        .map_err(ChainStreamError::BroadcastError)?;
+                }
+                AnyRPCNetwork::Logs(logs) => {
+                    self.sender
+                        .send(ChainData::AnyRPCNetwork(AnyRPCNetwork::Logs(logs)))
+                        .map_err(ChainStreamError::BroadcastError)?;
+                }
             },
         }
         Ok(())
@@ -78,13 +96,88 @@ impl ChainStream {
         self.sender.subscribe()
     }
 
-    /// Checks if a block with the given ID has been processed.
-    pub async fn has_seen_block(&self, block_id: &str) -> bool {
-        self.block_header_cache.read().await.contains(block_id)
+    /// Checks if a block with the given number and hash has been processed.
+    pub async fn has_seen_block(&self, number: u64, hash: B256) -> bool {
+        self.block_header_cache
+            .read()
+            .await
+            .contains(&(number, hash))
+    }
+
+    /// Checks if a transaction with the given hash has been processed.
+    pub async fn has_seen_tx(&self, hash: &B256) -> bool {
+        self.tx_cache.read().await.contains(hash)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use alloy_network::{AnyHeader, AnyRpcBlock, AnyRpcTransaction};
+    use alloy_rpc_types::{Block, Header};
+
+    use super::*;
+    use crate::{
+        network::orchestrator::PendingTransaction, utils::test_utils::generate_random_transaction,
+    };
+
+    fn pending(tx: &AnyRpcTransaction) -> ChainData {
+        ChainData::AnyRPCNetwork(AnyRPCNetwork::TransactionPool(PendingTransaction::new(
+            tx.clone(),
+        )))
+    }
+
+    fn block(number: u64, hash: B256) -> ChainData {
+        let block = Block {
+            header: Header {
+                hash,
+                inner: AnyHeader {
+                    number,
+                    ..Default::default()
+                },
+                ..Default::default()
+            },
+            ..Default::default()
+        };
+        ChainData::AnyRPCNetwork(AnyRPCNetwork::Block(AnyRpcBlock::new(block)))
     }
 
-    /// Checks if a transaction with the given ID has been processed.
-    pub async fn has_seen_tx(&self, tx_id: &str) -> bool {
-        self.tx_cache.read().await.contains(tx_id)
+    #[tokio::test]
+    async fn test_deduplicates_chain_data() {
+        let stream = ChainStream::new(Chain::Ethereum, 2);
+        let mut receiver = stream.subscribe();
+
+        let first = generate_random_transaction(100);
+        let second = generate_random_transaction(200);
+        for data in [
+            pending(&first),
+            pending(&second),
+            pending(&first),
+            block(1, B256::repeat_byte(1)),
+            block(1, B256::repeat_byte(2)),
+            block(1, B256::repeat_byte(1)),
+        ] {
+            stream.process_data(data).await.unwrap();
+        }
+
+        // Every pending transaction and every sibling block goes through once
+        let mut received = Vec::new();
+        while let Ok(ChainData::AnyRPCNetwork(data)) = receiver.try_recv() {
+            received.push(match data {
+                AnyRPCNetwork::TransactionPool(tx) => tx.transaction.info().hash.unwrap(),
+                AnyRPCNetwork::Block(block) => block.header.hash,
+                _ => unreachable!(),
+            });
+        }
+        assert_eq!(
+            received,
+            vec![
+                first.info().hash.unwrap(),
+                second.info().hash.unwrap(),
+                B256::repeat_byte(1),
+                B256::repeat_byte(2),
+            ]
+        );
+        assert!(stream.has_seen_tx(&second.info().hash.unwrap()).await);
+        assert!(stream.has_seen_block(1, B256::repeat_byte(2)).await);
     }
 }
@@ -0,0 +1,382 @@
+use std::{
+    collections::{HashMap, HashSet},
+    num::NonZeroUsize,
+    time::{Duration, Instant},
+};
+
+use alloy_consensus::Transaction;
+use alloy_network::AnyRpcTransaction;
+use alloy_primitives::{Address, B256};
+use lru::LruCache;
+
+use crate::{network::orchestrator::PendingTransaction, TransactionStatus};
+
+/// Default number of pending transactions tracked per chain.
+pub(crate) const DEFAULT_MEMPOOL_CAPACITY: usize = 10_000;
+
+/// Default time after which a transaction that is still pending is considered dropped, nodes
+/// evict transactions from their pool after a similar lifetime.
+pub(crate) const DEFAULT_PENDING_LIFETIME: Duration = Duration::from_secs(3 * 60 * 60);
+
+/// Default time a transaction that left the node's pool has to show up in a block before it is
+/// considered dropped, nodes remove included transactions before their block is fetched.
+pub(crate) const DEFAULT_REMOVAL_GRACE_PERIOD: Duration = Duration::from_secs(30);
+
+/// A transaction tracked while it is pending along with the filters it already matched.
+pub(crate) struct PendingEntry {
+    pub(crate) transaction: PendingTransaction,
+    /// `(group id, filter id)` pairs that matched the transaction, they are not evaluated again
+    matched: HashSet<(u64, u64)>,
+    /// Groups following the lifecycle of the transaction
+    tracked_by: HashSet<u64>,
+    /// The pending transaction it replaced
+    pub(crate) replaced: Option<PendingTransaction>,
+    /// When the node reported the transaction left its pool
+    left_pool: Option<Instant>,
+}
+
+impl PendingEntry {
+    fn new(transaction: PendingTransaction) -> Self {
+        Self {
+            transaction,
+            matched: HashSet::new(),
+            tracked_by: HashSet::new(),
+            replaced: None,
+            left_pool: None,
+        }
+    }
+
+    /// Hash of the transaction.
+    pub(crate) fn hash(&self) -> B256 {
+        self.transaction.transaction.info().hash.unwrap_or_default()
+    }
+
+    /// Whether a filter of a group already matched this transaction.
+    pub(crate) fn has_matched(&self, group_id: u64, filter_id: u64) -> bool {
+        self.matched.contains(&(group_id, filter_id))
+    }
+
+    /// Records that a filter of a group matched this transaction.
+    pub(crate) fn set_matched(&mut self, group_id: u64, filter_id: u64) {
+        self.matched.insert((group_id, filter_id));
+    }
+
+    /// Follows the lifecycle of the transaction for a group, returns whether it was not
+    /// followed yet.
+    pub(crate) fn track(&mut self, group_id: u64) -> bool {
+        self.tracked_by.insert(group_id)
+    }
+
+    /// Groups following the lifecycle of the transaction.
+    pub(crate) fn tracked_by(&self) -> impl Iterator<Item = &u64> {
+        self.tracked_by.iter()
+    }
+}
+
+/// Sender and nonce of a transaction, pending transactions sharing them replace each other.
+type SenderNonce = (Address, u64);
+
+fn sender_nonce(transaction: &AnyRpcTransaction) -> SenderNonce {
+    (transaction.from, transaction.nonce())
+}
+
+/// Whether a transaction pays higher fees than the pending one it conflicts with, nodes reject
+/// replacements that do not.
+fn outbids(transaction: &AnyRpcTransaction, pending: &AnyRpcTransaction) -> bool {
+    transaction.max_fee_per_gas() > pending.max_fee_per_gas()
+        && transaction.max_priority_fee_per_gas() >= pending.max_priority_fee_per_gas()
+}
+
+/// [`Mempool`] keeps the transactions of a chain that are still pending, so that conditions
+/// depending on how long they have been pending can be re-evaluated and their lifecycle
+/// followed until they are included in a block, replaced or dropped. The least recently seen
+/// transactions are dropped once it is full.
+pub(crate) struct Mempool {
+    pending: LruCache<B256, PendingEntry>,
+    /// Hash of the pending transaction of each sender and nonce
+    nonces: HashMap<SenderNonce, B256>,
+    /// How long a transaction can stay pending before it is considered dropped
+    lifetime: Duration,
+    /// How long a transaction that left the node's pool can take to be included
+    removal_grace: Duration,
+}
+
+impl Mempool {
+    /// Creates a new [`Mempool`] tracking at most `capacity` transactions for `lifetime`, or for
+    /// `removal_grace` once they left the node's pool.
+    pub(crate) fn new(capacity: usize, lifetime: Duration, removal_grace: Duration) -> Self {
+        Self {
+            pending: LruCache::new(NonZeroUsize::new(capacity).unwrap_or(NonZeroUsize::MIN)),
+            nonces: HashMap::new(),
+            lifetime,
+            removal_grace,
+        }
+    }
+
+    /// Starts tracking a pending transaction. A transaction that is already tracked keeps its
+    /// entry, and with it when it was first seen. A transaction conflicting with a pending one
+    /// of the same sender and nonce replaces it when it pays higher fees and is ignored otherwise.
+    ///
+    /// Returns the transactions that stopped being tracked because of it, i.e. the pending
+    /// transaction it replaces or the one dropped to make room.
+    pub(crate) fn insert(
+        &mut self,
+        transaction: PendingTransaction,
+    ) -> Vec<(PendingEntry, TransactionStatus)> {
+        let mut entry = PendingEntry::new(transaction);
+        let hash = entry.hash();
+        if let Some(entry) = self.pending.get_mut(&hash) {
+            entry.left_pool = None;
+            return Vec::new();
+        }
+
+        let mut settled = Vec::new();
+        let key = sender_nonce(&entry.transaction.transaction);
+        if let Some(pending_hash) = self.nonces.get(&key).copied() {
+            if let Some(pending) = self.pending.peek(&pending_hash) {
+                if !outbids(
+                    &entry.transaction.transaction,
+                    &pending.transaction.transaction,
+                ) {
+                    return settled;
+                }
+            }
+
+            if let Some(replaced) = self.pending.pop(&pending_hash) {
+                entry.transaction.replaces = Some(pending_hash);
+                entry.replaced = Some(replaced.transaction.clone());
+                settled.push((replaced, TransactionStatus::Replaced { by: hash }));
+            }
+        }
+        self.nonces.insert(key, hash);
+
+        if let Some((evicted_hash, evicted)) = self.pending.push(hash, entry) {
+            if evicted_hash != hash {
+                self.forget_nonce(&evicted);
+                settled.push((evicted, TransactionStatus::Dropped));
+            }
+        }
+
+        settled
+    }
+
+    /// Returns the entry of a tracked transaction.
+    pub(crate) fn get_mut(&mut self, hash: &B256) -> Option<&mut PendingEntry> {
+        self.pending.peek_mut(hash)
+    }
+
+    /// Stops tracking the pending transaction settled by a transaction included in a block,
+    /// either the same transaction or one it was replaced by.
+    pub(crate) fn include(
+        &mut self,
+        transaction: &AnyRpcTransaction,
+        block: u64,
+        index: u64,
+    ) -> Option<(PendingEntry, TransactionStatus)> {
+        let hash = transaction.info().hash.unwrap_or_default();
+        let pending_hash = self.nonces.remove(&sender_nonce(transaction))?;
+        let entry = self.pending.pop(&pending_hash)?;
+
+        let status = if pending_hash == hash {
+            TransactionStatus::Included { block, index }
+        } else {
+            TransactionStatus::Replaced { by: hash }
+        };
+        Some((entry, status))
+    }
+
+    /// Records that a transaction left the node's pool, it is dropped unless it is included in
+    /// a block within the removal grace period.
+    pub(crate) fn mark_left_pool(&mut self, hash: &B256) {
+        if let Some(entry) = self.pending.peek_mut(hash) {
+            entry.left_pool.get_or_insert_with(Instant::now);
+        }
+    }
+
+    /// Stops tracking transactions pending for longer than the lifetime of the mempool, and
+    /// those that left the node's pool without being included.
+    pub(crate) fn drop_expired(&mut self) -> Vec<(PendingEntry, TransactionStatus)> {
+        let expired: Vec<B256> = self
+            .pending
+            .iter()
+            .filter(|(_, entry)| {
+                entry.transaction.age() >= self.lifetime
+                    || entry
+                        .left_pool
+                        .is_some_and(|left_pool| left_pool.elapsed() >= self.removal_grace)
+            })
+            .map(|(hash, _)| *hash)
+            .collect();
+
+        let mut dropped = Vec::with_capacity(expired.len());
+        for hash in expired {
+            if let Some(entry) = self.pending.pop(&hash) {
+                self.forget_nonce(&entry);
+                dropped.push((entry, TransactionStatus::Dropped));
+            }
+        }
+        dropped
+    }
+
+    /// Iterates over the tracked transactions.
+    pub(crate) fn iter_mut(&mut self) -> impl Iterator<Item = &mut PendingEntry> {
+        self.pending.iter_mut().map(|(_, entry)| entry)
+    }
+
+    /// Number of tracked transactions.
+    #[cfg(test)]
+    pub(crate) fn len(&self) -> usize {
+        self.pending.len()
+    }
+
+    fn forget_nonce(&mut self, entry: &PendingEntry) {
+        let key = sender_nonce(&entry.transaction.transaction);
+        if self.nonces.get(&key) == Some(&entry.hash()) {
+            self.nonces.remove(&key);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::utils::test_utils::{generate_random_transaction, generate_transaction};
+
+    const FEE: u128 = 20_000_000_000;
+
+    fn pending(transaction: &AnyRpcTransaction) -> PendingTransaction {
+        PendingTransaction::new(transaction.clone())
+    }
+
+    fn hash(transaction: &AnyRpcTransaction) -> B256 {
+        transaction.info().hash.unwrap_or_default()
+    }
+
+    #[test]
+    fn test_mempool_tracking() {
+        let mut mempool = Mempool::new(2, DEFAULT_PENDING_LIFETIME, DEFAULT_REMOVAL_GRACE_PERIOD);
+
+        let first = generate_random_transaction(100);
+        assert!(mempool.insert(pending(&first)).is_empty());
+        let first_seen = mempool
+            .get_mut(&hash(&first))
+            .unwrap()
+            .transaction
+            .first_seen;
+        mempool.get_mut(&hash(&first)).unwrap().set_matched(1, 2);
+
+        // Seeing the transaction again keeps its entry
+        assert!(mempool.insert(pending(&first)).is_empty());
+        let entry = mempool.get_mut(&hash(&first)).unwrap();
+        assert_eq!(entry.transaction.first_seen, first_seen);
+        assert!(entry.has_matched(1, 2));
+        assert!(!entry.has_matched(2, 2));
+
+        // The least recently seen transaction is dropped once full
+        let second = generate_random_transaction(200);
+        assert!(mempool.insert(pending(&second)).is_empty());
+        let settled = mempool.insert(pending(&generate_random_transaction(300)));
+        assert_eq!(settled.len(), 1);
+        assert_eq!(settled[0].0.hash(), hash(&first));
+        assert_eq!(settled[0].1, TransactionStatus::Dropped);
+        assert_eq!(mempool.len(), 2);
+
+        let (entry, status) = mempool.include(&second, 7, 3).unwrap();
+        assert_eq!(entry.hash(), hash(&second));
+        assert_eq!(status, TransactionStatus::Included { block: 7, index: 3 });
+        assert_eq!(mempool.len(), 1);
+        assert!(mempool.include(&second, 7, 3).is_none());
+    }
+
+    #[test]
+    fn test_mempool_replacements() {
+        let mut mempool = Mempool::new(
+            DEFAULT_MEMPOOL_CAPACITY,
+            DEFAULT_PENDING_LIFETIME,
+            DEFAULT_REMOVAL_GRACE_PERIOD,
+        );
+        let sender = Address::repeat_byte(0xaa);
+
+        let original = generate_transaction(sender, 1, 100, FEE);
+        let other_nonce = generate_transaction(sender, 2, 100, FEE);
+        mempool.insert(pending(&original));
+        mempool.insert(pending(&other_nonce));
+
+        // A pending transaction with the same sender and nonce paying more replaces it
+        let speed_up = generate_transaction(sender, 1, 100, 2 * FEE);
+        let settled = mempool.insert(pending(&speed_up));
+        assert_eq!(settled.len(), 1);
+        assert_eq!(settled[0].0.hash(), hash(&original));
+        assert_eq!(
+            settled[0].1,
+            TransactionStatus::Replaced {
+                by: hash(&speed_up)
+            }
+        );
+        let entry = mempool.get_mut(&hash(&speed_up)).unwrap();
+        assert_eq!(entry.transaction.replaces, Some(hash(&original)));
+        assert_eq!(
+            entry
+                .replaced
+                .as_ref()
+                .map(|replaced| &replaced.transaction),
+            Some(&original)
+        );
+
+        // Underpriced replacements are ignored
+        let underpriced = generate_transaction(sender, 1, 100, FEE);
+        assert!(mempool.insert(pending(&underpriced)).is_empty());
+        assert!(mempool.get_mut(&hash(&underpriced)).is_none());
+        assert_eq!(mempool.len(), 2);
+
+        // Whatever is included in a block replaces it, even if never seen pending
+        let cancel = generate_transaction(sender, 1, 0, FEE);
+        let (entry, status) = mempool.include(&cancel, 1, 0).unwrap();
+        assert_eq!(entry.hash(), hash(&speed_up));
+        assert_eq!(status, TransactionStatus::Replaced { by: hash(&cancel) });
+        assert_eq!(mempool.len(), 1);
+    }
+
+    #[test]
+    fn test_mempool_drops_expired_transactions() {
+        let mut mempool = Mempool::new(
+            DEFAULT_MEMPOOL_CAPACITY,
+            Duration::ZERO,
+            DEFAULT_REMOVAL_GRACE_PERIOD,
+        );
+        let transaction = generate_random_transaction(100);
+        mempool.insert(pending(&transaction));
+
+        let dropped = mempool.drop_expired();
+        assert_eq!(dropped.len(), 1);
+        assert_eq!(dropped[0].0.hash(), hash(&transaction));
+        assert_eq!(dropped[0].1, TransactionStatus::Dropped);
+        assert_eq!(mempool.len(), 0);
+        assert!(mempool.include(&transaction, 1, 0).is_none());
+    }
+
+    #[test]
+    fn test_mempool_drops_transactions_that_left_the_pool() {
+        let mut mempool = Mempool::new(
+            DEFAULT_MEMPOOL_CAPACITY,
+            DEFAULT_PENDING_LIFETIME,
+            Duration::ZERO,
+        );
+        let included = generate_random_transaction(100);
+        let dropped = generate_random_transaction(200);
+        let returned = generate_random_transaction(300);
+        for tx in [&included, &dropped, &returned] {
+            mempool.insert(pending(tx));
+            mempool.mark_left_pool(&hash(tx));
+        }
+        // Seen pending again, e.g. after a reorg
+        mempool.insert(pending(&returned));
+
+        assert!(mempool.include(&included, 1, 0).is_some());
+        let settled = mempool.drop_expired();
+        assert_eq!(settled.len(), 1);
+        assert_eq!(settled[0].0.hash(), hash(&dropped));
+        assert_eq!(settled[0].1, TransactionStatus::Dropped);
+        assert!(mempool.get_mut(&hash(&returned)).is_some());
+    }
+}
@@ -1,16 +1,19 @@
 use std::{collections::HashMap, sync::Arc, time::Duration};
 
 mod chain_stream;
+pub(crate) mod mempool;
+pub(crate) mod reorg;
 
 use chain_stream::ChainStream;
 use tokio::{sync::broadcast, task::JoinHandle};
 use tokio_stream::wrappers::BroadcastStream;
 
 use crate::{
-    config::{Chain, ChainConfig},
+    config::{Chain, ChainConfig, MempoolSource},
     network::{
         orchestrator::{ChainData, ChainOrchestrator},
         rpc::RpcOrchestrator,
+        ws::WsOrchestrator,
     },
 };
 
@@ -33,6 +36,7 @@ impl std::fmt::Display for IngestError {
 impl std::error::Error for IngestError {}
 
 const DEFAULT_POLL_INTERVAL: Duration = Duration::from_secs(3);
+const DEFAULT_RECONNECT_DELAY: Duration = Duration::from_secs(1);
 
 #[allow(dead_code)]
 struct ChainState {
@@ -83,71 +87,15 @@ impl Ingest {
 
             match config.chain() {
                 Chain::Ethereum => {
-                    let chain_stream = Arc::new(ChainStream::new(chain.clone()));
-
-                    // Start RPC orchestrator if configured...
-                    if !config.rpc_url().is_empty() {
-                        tracing::info!("Starting Ethereum chain stream...");
-
-                        let orchestrator = RpcOrchestrator::new(
-                            format!("{:?}", config.chain()),
-                            config.rpc_url().to_string(),
-                            DEFAULT_POLL_INTERVAL,
-                        )
-                        .unwrap();
-
-                        let mut orchestrator = Box::new(orchestrator);
-                        let mut receiver = orchestrator.start().await.unwrap();
-
-                        let stream_clone = chain_stream.clone();
-                        let handle = tokio::spawn(async move {
-                            while let Some(data) = receiver.recv().await {
-                                let _ = stream_clone.process_data(data).await;
-                            }
-                        });
-
-                        chain_states.insert(
-                            chain,
-                            ChainState {
-                                chain_stream,
-                                orchestrator,
-                                handle,
-                            },
-                        );
+                    tracing::info!("Starting Ethereum chain stream...");
+                    if let Some(state) = Self::start_chain(&config).await {
+                        chain_states.insert(chain, state);
                     }
                 }
                 Chain::Optimism => {
-                    let chain_stream = Arc::new(ChainStream::new(chain.clone()));
-
-                    // Start RPC orchestrator if configured...
-                    if !config.rpc_url().is_empty() {
-                        tracing::info!("Starting Optimism chain stream...");
-
-                        let orchestrator = RpcOrchestrator::new(
-                            format!("{:?}", config.chain()),
-                            config.rpc_url().to_string(),
-                            DEFAULT_POLL_INTERVAL,
-                        )
-                        .unwrap();
-
-                        let mut orchestrator = Box::new(orchestrator);
-                        let mut receiver = orchestrator.start().await.unwrap();
-
-                        let stream_clone = chain_stream.clone();
-                        let handle = tokio::spawn(async move {
-                            while let Some(data) = receiver.recv().await {
-                                let _ = stream_clone.process_data(data).await;
-                            }
-                        });
-
-                        chain_states.insert(
-                            chain,
-                            ChainState {
-                                chain_stream,
-                                orchestrator,
-                                handle,
-                            },
-                        );
+                    tracing::info!("Starting Optimism chain stream...");
+                    if let Some(state) = Self::start_chain(&config).await {
+                        chain_states.insert(chain, state);
                     }
                 }
                 Chain::Base => println!("<> implement base"),
@@ -157,6 +105,55 @@ impl Ingest {
         Self { chain_states }
     }
 
+    /// Starts the orchestrator for a chain configuration. The WebSocket orchestrator takes over
+    /// from RPC polling when a `ws_url` is configured: the RPC endpoints, and their failover,
+    /// health checks, backoff, rate limits and hedging, are then unused. Chains snapshotting the
+    /// txpool are always polled over RPC.
+    async fn start_chain(config: &ChainConfig) -> Option<ChainState> {
+        let name = format!("{:?}", config.chain());
+        let use_ws =
+            !config.ws_url().is_empty() && config.mempool_source() != MempoolSource::TxPool;
+
+        if use_ws && !config.rpc_endpoints().is_empty() {
+            tracing::info!(chain = name, "Using WebSocket, RPC endpoints are unused");
+        }
+
+        let mut orchestrator: Box<dyn ChainOrchestrator> = if use_ws {
+            Box::new(
+                WsOrchestrator::new(name, config.ws_url().to_string(), DEFAULT_RECONNECT_DELAY)
+                    .unwrap(),
+            )
+        } else if !config.rpc_endpoints().is_empty() {
+            let mut orchestrator =
+                RpcOrchestrator::new(name, config.rpc_endpoints(), DEFAULT_POLL_INTERVAL)
+                    .unwrap()
+                    .with_backoff(config.rpc_backoff())
+                    .with_mempool_source(config.mempool_source());
+            if let Some(delay) = config.hedge_delay() {
+                orchestrator = orchestrator.with_hedge_delay(delay);
+            }
+            Box::new(orchestrator)
+        } else {
+            return None;
+        };
+
+        let chain_stream = Arc::new(ChainStream::new(config.chain(), config.dedup_cache_size()));
+        let mut receiver = orchestrator.start().await.unwrap();
+
+        let stream_clone = chain_stream.clone();
+        let handle = tokio::spawn(async move {
+            while let Some(data) = receiver.recv().await {
+                let _ = stream_clone.process_data(data).await;
+            }
+        });
+
+        Some(ChainState {
+            chain_stream,
+            orchestrator,
+            handle,
+        })
+    }
+
     /// Subscribe to a specific chain's processed and deduplicated data stream
     fn subscribe(&self, chain: Chain) -> Result<broadcast::Receiver<ChainData>, IngestError> {
         self.chain_states
@@ -0,0 +1,198 @@
+use std::collections::VecDeque;
+
+use alloy_consensus::BlockHeader;
+use alloy_network::AnyHeader;
+use alloy_primitives::B256;
+use alloy_rpc_types::Header;
+
+/// Number of recent blocks followed per chain: kept to detect reorganizations, and fetched back
+/// at most by orchestrators to deliver the ancestors of a fork. Deeper reorganizations are missed.
+pub(crate) const MAX_REORG_DEPTH: usize = 64;
+
+/// Minimal reference to a block required to follow the canonical chain.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub(crate) struct BlockRef {
+    pub(crate) number: u64,
+    pub(crate) hash: B256,
+    pub(crate) parent_hash: B256,
+}
+
+impl From<&Header<AnyHeader>> for BlockRef {
+    fn from(header: &Header<AnyHeader>) -> Self {
+        Self {
+            number: header.number(),
+            hash: header.hash,
+            parent_hash: header.parent_hash(),
+        }
+    }
+}
+
+/// [`ReorgDetector`] keeps a short buffer of the most recent canonical blocks of a chain and
+/// detects reorganizations by checking `parent_hash` continuity of every new block.
+///
+/// Orchestrators deliver the missing ancestors of a fork before its new blocks, oldest first
+/// (see [`crate::network::orchestrator::with_undelivered_ancestors`]), so the first block of a
+/// fork drops everything above the common ancestor.
+///
+/// Each block carries a payload `T` (e.g. the events emitted for it), which is handed back
+/// when the block is dropped from the canonical chain.
+pub(crate) struct ReorgDetector<T> {
+    depth: usize,
+    blocks: VecDeque<(BlockRef, T)>,
+}
+
+impl<T: Default> ReorgDetector<T> {
+    /// Creates a new [`ReorgDetector`] keeping at most `depth` blocks.
+    pub(crate) fn new(depth: usize) -> Self {
+        Self {
+            depth: depth.max(1),
+            blocks: VecDeque::with_capacity(depth),
+        }
+    }
+
+    /// Appends a new block to the canonical chain and returns the blocks (with their payload)
+    /// it replaced, most recent first. A block that is already part of the chain is ignored.
+    ///
+    /// Blocks are dropped when the new block is at or below their height, or when the new block
+    /// does not build on top of them (parent hash mismatch).
+    pub(crate) fn push(&mut self, block: BlockRef) -> Vec<(BlockRef, T)> {
+        if self.contains(&block.hash) {
+            return Vec::new();
+        }
+
+        let mut dropped = Vec::new();
+        while let Some((tip, _)) = self.blocks.back() {
+            let replaced = tip.number >= block.number
+                || (tip.number + 1 == block.number && tip.hash != block.parent_hash);
+            if !replaced {
+                break;
+            }
+            if let Some(entry) = self.blocks.pop_back() {
+                dropped.push(entry);
+            }
+        }
+
+        self.blocks.push_back((block, T::default()));
+        while self.blocks.len() > self.depth {
+            self.blocks.pop_front();
+        }
+
+        dropped
+    }
+
+    /// Returns a mutable reference to the payload of a canonical block.
+    pub(crate) fn payload_mut(&mut self, hash: &B256) -> Option<&mut T> {
+        self.blocks
+            .iter_mut()
+            .rev()
+            .find(|(block, _)| block.hash == *hash)
+            .map(|(_, payload)| payload)
+    }
+
+    /// Iterates over the canonical blocks and their payload, oldest first.
+    pub(crate) fn iter_mut(&mut self) -> impl Iterator<Item = (&BlockRef, &mut T)> {
+        self.blocks
+            .iter_mut()
+            .map(|(block, payload)| (&*block, payload))
+    }
+
+    /// Checks if a block is part of the canonical chain buffer.
+    pub(crate) fn contains(&self, hash: &B256) -> bool {
+        self.blocks.iter().any(|(block, _)| block.hash == *hash)
+    }
+
+    /// Returns the most recent canonical block.
+    pub(crate) fn tip(&self) -> Option<&BlockRef> {
+        self.blocks.back().map(|(block, _)| block)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn block(number: u64, hash: u8, parent_hash: u8) -> BlockRef {
+        BlockRef {
+            number,
+            hash: B256::repeat_byte(hash),
+            parent_hash: B256::repeat_byte(parent_hash),
+        }
+    }
+
+    #[test]
+    fn test_linear_chain_has_no_reorg() {
+        let mut detector = ReorgDetector::<()>::new(MAX_REORG_DEPTH);
+        assert!(detector.push(block(1, 1, 0)).is_empty());
+        assert!(detector.push(block(2, 2, 1)).is_empty());
+        assert!(detector.push(block(3, 3, 2)).is_empty());
+        assert_eq!(detector.tip(), Some(&block(3, 3, 2)));
+    }
+
+    #[test]
+    fn test_duplicate_block_is_ignored() {
+        let mut detector = ReorgDetector::<()>::new(MAX_REORG_DEPTH);
+        detector.push(block(1, 1, 0));
+        detector.push(block(2, 2, 1));
+        assert!(detector.push(block(1, 1, 0)).is_empty());
+        assert_eq!(detector.tip(), Some(&block(2, 2, 1)));
+    }
+
+    #[test]
+    fn test_sibling_block_replaces_tip() {
+        let mut detector = ReorgDetector::<u8>::new(MAX_REORG_DEPTH);
+        detector.push(block(1, 1, 0));
+        detector.push(block(2, 2, 1));
+        *detector.payload_mut(&B256::repeat_byte(2)).unwrap() = 42;
+
+        let dropped = detector.push(block(2, 3, 1));
+        assert_eq!(dropped, vec![(block(2, 2, 1), 42)]);
+        assert_eq!(detector.tip(), Some(&block(2, 3, 1)));
+    }
+
+    #[test]
+    fn test_fork_drops_every_block_above_common_ancestor() {
+        let mut detector = ReorgDetector::<()>::new(MAX_REORG_DEPTH);
+        for number in 1..=4 {
+            detector.push(block(number, number as u8, number as u8 - 1));
+        }
+
+        // Fork from block 1: 2' <- 3' <- 4' <- 5', delivered oldest first
+        let fork = [
+            block(2, 0x22, 1),
+            block(3, 0x33, 0x22),
+            block(4, 0x44, 0x33),
+            block(5, 0x55, 0x44),
+        ];
+        let dropped: Vec<BlockRef> = fork
+            .into_iter()
+            .flat_map(|block| detector.push(block))
+            .map(|(block, _)| block)
+            .collect();
+        assert_eq!(
+            dropped,
+            vec![block(4, 4, 3), block(3, 3, 2), block(2, 2, 1)]
+        );
+        assert!(detector.contains(&B256::repeat_byte(1)));
+        assert_eq!(detector.tip(), Some(&fork[3]));
+    }
+
+    #[test]
+    fn test_deep_reorg_drops_every_replaced_block() {
+        let mut detector = ReorgDetector::<()>::new(MAX_REORG_DEPTH);
+        detector.push(block(1, 1, 0));
+        detector.push(block(2, 2, 1));
+        detector.push(block(3, 3, 2));
+
+        let dropped = detector.push(block(2, 4, 1));
+        assert_eq!(dropped, vec![(block(3, 3, 2), ()), (block(2, 2, 1), ())]);
+    }
+
+    #[test]
+    fn test_buffer_is_bounded() {
+        let mut detector = ReorgDetector::<()>::new(2);
+        detector.push(block(1, 1, 0));
+        detector.push(block(2, 2, 1));
+        detector.push(block(3, 3, 2));
+        assert!(!detector.contains(&B256::repeat_byte(1)));
+    }
+}
@@ -9,34 +9,68 @@ mod utils;
 
 // prelude module for convenient imports
 pub mod prelude {
-    pub use crate::config::{Chain, ChainConfig, ChainConfigBuilder};
+    pub use crate::config::{
+        Backoff, Chain, ChainConfig, ChainConfigBuilder, MempoolSource, RpcEndpoint,
+    };
     pub use crate::engine::FilterEngine;
     pub use crate::filter::conditions::{Filter, FilterNode};
-    pub use crate::filter::{ArrayOps, FilterBuilder, LogicalOps, NumericOps, StringOps};
-    pub use crate::Sieve;
+    pub use crate::filter::{
+        ArrayOps, FilterBuilder, FilterError, LogicalOps, NumericOps, StringOps,
+    };
+    pub use crate::{PendingTransaction, Sieve, Subscription, TransactionStatus};
+    pub use alloy_json_abi::JsonAbi;
 }
 
 use crate::config::ChainConfig;
 use alloy_network::{AnyHeader, AnyRpcBlock, AnyRpcTransaction, BlockResponse};
-use alloy_rpc_types::{BlockTransactions, Header};
+use alloy_primitives::B256;
+use alloy_rpc_types::{BlockTransactions, Header, Log};
 use config::Chain;
 use dashmap::DashMap;
 use engine::FilterEngine;
 use filter::conditions::{EventType, Filter};
-use futures::StreamExt;
+use futures::{Stream, StreamExt};
+use ingest::mempool::{
+    Mempool, PendingEntry, DEFAULT_MEMPOOL_CAPACITY, DEFAULT_PENDING_LIFETIME,
+    DEFAULT_REMOVAL_GRACE_PERIOD,
+};
+use ingest::reorg::{BlockRef, ReorgDetector, MAX_REORG_DEPTH};
 use ingest::{Ingest, IngestError, IngestGateway};
+pub use network::orchestrator::PendingTransaction;
 use network::orchestrator::{AnyRPCNetwork, ChainData};
 use std::hash::{Hash, Hasher};
+use std::pin::Pin;
 use std::sync::atomic::{AtomicU64, Ordering};
 use std::sync::Arc;
+use std::task::{Context, Poll};
 use std::time::{Duration, Instant};
 use std::{collections::HashMap, hash::DefaultHasher};
 use thiserror::Error;
 use tokio::sync::{broadcast, RwLock};
-use tokio_stream::wrappers::BroadcastStream;
+use tokio::task::JoinHandle;
+use tokio_stream::wrappers::{errors::BroadcastStreamRecvError, BroadcastStream};
 
 const BROADCAST_CHANNEL_SIZE: usize = 1_000;
 
+/// How often pending transactions are re-evaluated against time dependent pool filters
+const MEMPOOL_REEVALUATION_INTERVAL: Duration = Duration::from_secs(1);
+
+/// Sequence distinguishing groups of one-shot and expiring subscriptions to the same filter
+static SUBSCRIPTION_SEQ: AtomicU64 = AtomicU64::new(0);
+
+/// An event matched in a canonical block for a filter group
+struct BlockEvent {
+    /// Group the event matched for
+    group_id: u64,
+    /// The matched event
+    event: Event,
+    /// Whether the event was sent, confirmation subscriptions hold events back until confirmed
+    sent: bool,
+}
+
+/// Events matched for a block
+type BlockEvents = Vec<BlockEvent>;
+
 #[derive(Error, Debug)]
 pub enum SieveError {
     #[error("Failed to connect to chain: {0}")]
@@ -50,6 +84,9 @@ pub enum SieveError {
 
     #[error("Invalid window duration: {0}")]
     InvalidWindowDuration(String),
+
+    #[error("Invalid confirmation depth: {0}")]
+    InvalidConfirmationDepth(String),
 }
 
 /// A single event that matched a filter.
@@ -57,13 +94,56 @@ pub enum SieveError {
 // we will have to manually create events for each data types by mapping RPC or any other
 // network data types to a unified event type.
 #[derive(Clone, PartialEq, Eq, Debug)]
+#[allow(clippy::large_enum_variant)]
 pub enum Event {
     /// A transaction included in a block
     Transaction(AnyRpcTransaction),
-    /// A transaction from the mempool
-    Pool(AnyRpcTransaction),
+    /// A transaction from the mempool along with when it was first seen
+    Pool(PendingTransaction),
+    /// A transaction from the mempool replacing a pending transaction of the same sender and
+    /// nonce with higher fees
+    PoolReplacement {
+        /// The replaced transaction
+        old: PendingTransaction,
+        /// The replacing transaction
+        new: PendingTransaction,
+    },
     /// A block header
     Header(Header<AnyHeader>),
+    /// A log emitted by a transaction included in a block
+    Log(Log),
+    /// A previously emitted event whose block was dropped from the canonical chain by a reorg
+    Removed(Box<Event>),
+    /// The subscription timed out or outlived its TTL, no further events are emitted
+    Expired,
+    /// A pending transaction followed with `subscribe_lifecycle` changed status
+    Status {
+        /// Hash of the pending transaction
+        hash: B256,
+        /// Its new status
+        status: TransactionStatus,
+    },
+}
+
+/// Lifecycle status of a pending transaction
+#[derive(Clone, PartialEq, Eq, Debug)]
+pub enum TransactionStatus {
+    /// The transaction was seen in the mempool
+    Pending,
+    /// The transaction was included in a block
+    Included {
+        /// Number of the block
+        block: u64,
+        /// Index of the transaction in the block
+        index: u64,
+    },
+    /// A transaction with the same sender and nonce took its place
+    Replaced {
+        /// Hash of the replacing transaction
+        by: B256,
+    },
+    /// The transaction left the mempool without being included
+    Dropped,
 }
 
 /// A window-based event that contains either matched events or a timeout
@@ -82,6 +162,23 @@ pub enum SubscriptionType {
     Default,
     /// Window-based filtering with `watch_within` - collects events until all conditions met
     WatchWindow,
+    /// Confirmed filtering with `subscribe_with_confirmations` - emits events once their block
+    /// has the given number of blocks built on top of it
+    Confirmations(u64),
+    /// One-shot filtering with `subscribe_once` - emits the first event then closes
+    Once,
+    /// Expiring filtering with `subscribe_for` - emits events as they occur until its TTL passes
+    Ttl,
+    /// Lifecycle filtering with `subscribe_lifecycle` - emits status changes of matching pending
+    /// transactions
+    Lifecycle,
+}
+
+impl SubscriptionType {
+    /// Whether groups of this type are closed once their window in the [`WindowManager`] is
+    fn is_windowed(&self) -> bool {
+        matches!(self, Self::WatchWindow | Self::Once | Self::Ttl)
+    }
 }
 
 /// [`GroupSender`] type based on subscription type
@@ -111,7 +208,11 @@ impl FilterGroup {
     /// Creates a new [`FilterGroup`] with the specified parameters
     fn new(group_id: u64, filters: Vec<Filter>, sub_type: SubscriptionType) -> Self {
         let sender = match sub_type {
-            SubscriptionType::Default => {
+            SubscriptionType::Default
+            | SubscriptionType::Confirmations(_)
+            | SubscriptionType::Once
+            | SubscriptionType::Ttl
+            | SubscriptionType::Lifecycle => {
                 GroupSender::Default(broadcast::channel(BROADCAST_CHANNEL_SIZE).0)
             }
             SubscriptionType::WatchWindow => {
@@ -169,29 +270,112 @@ impl FilterGroup {
 
         events
     }
-    /// Evaluates a mempool transaction against this group's filters
-    fn evaluate_transaction(
+    /// Evaluates the logs of a block against this group's filters
+    fn evaluate_logs(
         &self,
-        tx: &AnyRpcTransaction,
+        logs: &[Log],
         engine: &FilterEngine,
         chain: &Chain,
     ) -> Vec<(u64, Event)> {
         let mut events = Vec::new();
 
         for filter in &self.filters {
-            if filter.chain() != chain {
+            if filter.chain() != chain || filter.event_type() != Some(EventType::LogEvent) {
                 continue;
             }
 
-            if filter.event_type() == Some(EventType::Transaction)
-                && engine.evaluate_with_context(filter.filter_node().as_ref(), Arc::new(tx.clone()))
-            {
-                events.push((filter.id(), Event::Pool(tx.clone())));
+            for log in logs {
+                if engine
+                    .evaluate_with_context(filter.filter_node().as_ref(), Arc::new(log.clone()))
+                {
+                    events.push((filter.id(), Event::Log(log.clone())));
+                }
+            }
+        }
+
+        events
+    }
+
+    /// Evaluates a mempool transaction against this group's pool filters. Filters match a
+    /// transaction at most once.
+    fn evaluate_transaction(
+        &self,
+        entry: &mut PendingEntry,
+        engine: &FilterEngine,
+        chain: &Chain,
+    ) -> Vec<(u64, Event)> {
+        let transaction = Arc::new(entry.transaction.clone());
+        let matched = self
+            .pool_filters(entry, chain, false)
+            .filter(|filter| {
+                engine.evaluate_with_context(filter.filter_node().as_ref(), transaction.clone())
+            })
+            .map(Filter::id)
+            .collect::<Vec<_>>();
+
+        self.record_pool_matches(entry, matched)
+    }
+
+    /// Pool filters of the group a mempool transaction did not match yet, when `lingering` only
+    /// the time dependent ones.
+    fn pool_filters<'a, 'b>(
+        &'a self,
+        entry: &'b PendingEntry,
+        chain: &'b Chain,
+        lingering: bool,
+    ) -> impl Iterator<Item = &'a Filter> + 'b
+    where
+        'a: 'b,
+    {
+        self.filters.iter().filter(move |filter| {
+            filter.chain() == chain
+                && filter.event_type() == Some(EventType::Pool)
+                && !entry.has_matched(self.group_id, filter.id())
+                && (!lingering || filter.filter_node().is_time_dependent())
+        })
+    }
+
+    /// Records the filters a mempool transaction matched, returning their events. Filters that
+    /// already matched the transaction are skipped.
+    fn record_pool_matches(
+        &self,
+        entry: &mut PendingEntry,
+        filter_ids: impl IntoIterator<Item = u64>,
+    ) -> Vec<(u64, Event)> {
+        let mut events = Vec::new();
+        for filter_id in filter_ids {
+            if entry.has_matched(self.group_id, filter_id) {
+                continue;
             }
+
+            entry.set_matched(self.group_id, filter_id);
+            let event = match &entry.replaced {
+                Some(old) => Event::PoolReplacement {
+                    old: old.clone(),
+                    new: entry.transaction.clone(),
+                },
+                None => Event::Pool(entry.transaction.clone()),
+            };
+            events.push((filter_id, event));
         }
 
         events
     }
+
+    /// Whether any filter of the group depends on how long a transaction has been pending
+    fn is_time_dependent(&self) -> bool {
+        self.filters
+            .iter()
+            .any(|filter| filter.filter_node().is_time_dependent())
+    }
+    /// Number of live receivers subscribed to this group
+    fn receiver_count(&self) -> usize {
+        match &self.sender {
+            GroupSender::Default(sender) => sender.receiver_count(),
+            GroupSender::Watch(sender) => sender.receiver_count(),
+        }
+    }
+
     /// Sends a single event to subscribers if this is a default subscription
     fn send_event(&self, event: Event) {
         if let GroupSender::Default(sender) = &self.sender {
@@ -205,6 +389,18 @@ impl FilterGroup {
             let _ = sender.send(event);
         }
     }
+
+    /// Sends the terminal event of an expired window to subscribers
+    fn send_expired(&self) {
+        match &self.sender {
+            GroupSender::Default(sender) => {
+                let _ = sender.send(Event::Expired);
+            }
+            GroupSender::Watch(sender) => {
+                let _ = sender.send(EventWindow::Timeout);
+            }
+        }
+    }
 }
 
 /// Window manages the state of time-based event matching.
@@ -295,6 +491,17 @@ impl WindowManager {
         self.windows.insert(group_id, window);
     }
 
+    /// Checks whether a window is still open for a filter group
+    pub(crate) fn has_window(&self, group_id: u64) -> bool {
+        self.windows.contains_key(&group_id)
+    }
+
+    /// Closes the window of a filter group without emitting anything, returns whether it was
+    /// still open
+    pub(crate) fn remove_window(&self, group_id: u64) -> bool {
+        self.windows.remove(&group_id).is_some()
+    }
+
     /// Processes a batch of events for a window
     pub(crate) fn process_events(
         &self,
@@ -344,6 +551,56 @@ impl WindowManager {
     }
 }
 
+/// [`Subscription`] is a handle to a filter group subscription and the stream of its events.
+///
+/// A group is removed from the sieve once every subscription to it is unsubscribed or dropped,
+/// watch groups are also removed once their window matched or timed out, which ends the stream.
+pub struct Subscription<T> {
+    /// Filter group this subscription listens to
+    group_id: u64,
+    /// Events sent to the group
+    stream: BroadcastStream<T>,
+    /// Sieve the group is registered in
+    sieve: Sieve,
+}
+
+impl<T: Clone + Send + 'static> Subscription<T> {
+    fn new(group_id: u64, receiver: broadcast::Receiver<T>, sieve: Sieve) -> Self {
+        Self {
+            group_id,
+            stream: BroadcastStream::new(receiver),
+            sieve,
+        }
+    }
+
+    /// Stops receiving events, the filter group is removed when no other subscription uses it.
+    pub async fn unsubscribe(self) {
+        let Self {
+            group_id,
+            stream,
+            sieve,
+        } = self;
+        drop(stream);
+
+        let mut filters = sieve.filters.write().await;
+        if filters
+            .get(&group_id)
+            .is_some_and(|group| group.receiver_count() == 0)
+        {
+            filters.remove(&group_id);
+            sieve.window_manager.remove_window(group_id);
+        }
+    }
+}
+
+impl<T: Clone + Send + 'static> Stream for Subscription<T> {
+    type Item = Result<T, BroadcastStreamRecvError>;
+
+    fn poll_next(mut self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Option<Self::Item>> {
+        Pin::new(&mut self.stream).poll_next(cx)
+    }
+}
+
 /// Sieve is the main entry point for the filtering engine.
 /// It coordinates filter evaluation, event processing, and window management.
 #[derive(Clone)]
@@ -356,6 +613,14 @@ pub struct Sieve {
     ingest: Arc<dyn IngestGateway>,
     /// Window management system
     window_manager: Arc<WindowManager>,
+    /// Recent canonical blocks per chain along with the events matched in them
+    canonical_chains: Arc<DashMap<Chain, ReorgDetector<BlockEvents>>>,
+    /// Logs per chain received before their block, keyed by block hash
+    pending_logs: Arc<DashMap<Chain, HashMap<B256, Vec<Log>>>>,
+    /// Transactions per chain that are still pending
+    mempools: Arc<DashMap<Chain, Mempool>>,
+    /// Background tasks processing chain data and re-evaluating mempools
+    processors: Arc<std::sync::Mutex<Vec<JoinHandle<()>>>>,
 }
 
 impl Sieve {
@@ -378,6 +643,10 @@ impl Sieve {
             ingest,
             filters,
             window_manager,
+            canonical_chains: Arc::new(DashMap::new()),
+            pending_logs: Arc::new(DashMap::new()),
+            mempools: Arc::new(DashMap::new()),
+            processors: Arc::new(std::sync::Mutex::new(Vec::new())),
         };
 
         sieve
@@ -388,14 +657,27 @@ impl Sieve {
         Ok(sieve)
     }
 
+    /// Stops processing chain data: aborts the background tasks processing chain data and
+    /// re-evaluating mempools, and ends every subscription.
+    pub async fn shutdown(&self) {
+        let processors =
+            std::mem::take(&mut *self.processors.lock().unwrap_or_else(|e| e.into_inner()));
+        for processor in processors {
+            processor.abort();
+        }
+
+        // Dropping the groups drops their senders, which ends the subscription streams
+        self.filters.write().await.clear();
+    }
+
     /// Subscribes to events matching a single filter
     ///
     /// # Arguments
     /// * `filter` - Filter to match events against
     ///
     /// # Returns
-    /// Stream of matching events
-    pub async fn subscribe(&self, filter: Filter) -> Result<BroadcastStream<Event>, SieveError> {
+    /// Subscription streaming matching events
+    pub async fn subscribe(&self, filter: Filter) -> Result<Subscription<Event>, SieveError> {
         let mut filters = self.filters.write().await;
         let group = FilterGroup::new(filter.id(), vec![filter], SubscriptionType::Default);
 
@@ -410,7 +692,177 @@ impl Sieve {
             }
         };
 
-        Ok(BroadcastStream::new(receiver))
+        Ok(Subscription::new(group.group_id, receiver, self.clone()))
+    }
+
+    /// Subscribes to events matching a single filter, only emitting them once their block is
+    /// `confirmations` blocks deep. Events whose block is reorged out before that are dropped.
+    ///
+    /// # Arguments
+    /// * `filter` - Filter to match events against
+    /// * `confirmations` - Number of blocks that must be built on top of the event's block
+    ///
+    /// # Returns
+    /// Subscription streaming confirmed matching events
+    pub async fn subscribe_with_confirmations(
+        &self,
+        filter: Filter,
+        confirmations: u64,
+    ) -> Result<Subscription<Event>, SieveError> {
+        if filter.event_type() == Some(EventType::Pool) {
+            return Err(SieveError::SubscriptionError(
+                "Pool filters cannot be confirmed".to_string(),
+            ));
+        }
+
+        // Blocks older than the reorg buffer are forgotten, together with their pending events.
+        if confirmations >= MAX_REORG_DEPTH as u64 {
+            return Err(SieveError::InvalidConfirmationDepth(format!(
+                "Confirmation depth must be lower than {MAX_REORG_DEPTH}"
+            )));
+        }
+
+        let mut hasher = DefaultHasher::new();
+        filter.hash(&mut hasher);
+        confirmations.hash(&mut hasher);
+        let group_id = hasher.finish();
+
+        let mut filters = self.filters.write().await;
+        let group = filters.entry(group_id).or_insert_with(|| {
+            FilterGroup::new(
+                group_id,
+                vec![filter],
+                SubscriptionType::Confirmations(confirmations),
+            )
+        });
+
+        let receiver = match &group.sender {
+            GroupSender::Default(sender) => sender.subscribe(),
+            _ => {
+                return Err(SieveError::SubscriptionError(
+                    "Invalid subscription type".to_string(),
+                ))
+            }
+        };
+
+        Ok(Subscription::new(group_id, receiver, self.clone()))
+    }
+
+    /// Follows the lifecycle of pending transactions matching a pool filter. An
+    /// [`Event::Status`] is emitted when a transaction first matches and once it is included,
+    /// replaced or dropped.
+    ///
+    /// # Arguments
+    /// * `filter` - Pool filter to match pending transactions against
+    ///
+    /// # Returns
+    /// Subscription streaming the status changes of matching transactions
+    pub async fn subscribe_lifecycle(
+        &self,
+        filter: Filter,
+    ) -> Result<Subscription<Event>, SieveError> {
+        if filter.event_type() != Some(EventType::Pool) {
+            return Err(SieveError::SubscriptionError(
+                "Only pool filters can follow transaction lifecycles".to_string(),
+            ));
+        }
+
+        let mut hasher = DefaultHasher::new();
+        filter.hash(&mut hasher);
+        "lifecycle".hash(&mut hasher);
+        let group_id = hasher.finish();
+
+        let mut filters = self.filters.write().await;
+        let group = filters.entry(group_id).or_insert_with(|| {
+            FilterGroup::new(group_id, vec![filter], SubscriptionType::Lifecycle)
+        });
+
+        let receiver = match &group.sender {
+            GroupSender::Default(sender) => sender.subscribe(),
+            _ => {
+                return Err(SieveError::SubscriptionError(
+                    "Invalid subscription type".to_string(),
+                ))
+            }
+        };
+
+        Ok(Subscription::new(group_id, receiver, self.clone()))
+    }
+
+    /// Subscribes to the first event matching a single filter, the subscription closes after it
+    /// is emitted or emits [`Event::Expired`] if nothing matched within `timeout`.
+    ///
+    /// # Arguments
+    /// * `filter` - Filter to match events against
+    /// * `timeout` - How long to wait for a match
+    ///
+    /// # Returns
+    /// Subscription streaming the first matching event
+    pub async fn subscribe_once(
+        &self,
+        filter: Filter,
+        timeout: Duration,
+    ) -> Result<Subscription<Event>, SieveError> {
+        self.subscribe_windowed(filter, timeout, SubscriptionType::Once)
+            .await
+    }
+
+    /// Subscribes to events matching a single filter for a limited time, [`Event::Expired`] is
+    /// emitted once `ttl` passes and the subscription closes.
+    ///
+    /// # Arguments
+    /// * `filter` - Filter to match events against
+    /// * `ttl` - How long the subscription stays active
+    ///
+    /// # Returns
+    /// Subscription streaming matching events
+    pub async fn subscribe_for(
+        &self,
+        filter: Filter,
+        ttl: Duration,
+    ) -> Result<Subscription<Event>, SieveError> {
+        self.subscribe_windowed(filter, ttl, SubscriptionType::Ttl)
+            .await
+    }
+
+    /// Registers a group that is closed by the [`WindowManager`] once `duration` passes.
+    /// Every call gets its own group so each listener keeps its own deadline.
+    async fn subscribe_windowed(
+        &self,
+        filter: Filter,
+        duration: Duration,
+        sub_type: SubscriptionType,
+    ) -> Result<Subscription<Event>, SieveError> {
+        if duration.is_zero() {
+            return Err(SieveError::InvalidWindowDuration(
+                "Subscription duration cannot be zero".to_string(),
+            ));
+        }
+
+        let mut hasher = DefaultHasher::new();
+        filter.hash(&mut hasher);
+        SUBSCRIPTION_SEQ
+            .fetch_add(1, Ordering::Relaxed)
+            .hash(&mut hasher);
+        let group_id = hasher.finish();
+
+        let mut filters = self.filters.write().await;
+        let group = FilterGroup::new(group_id, vec![filter], sub_type);
+
+        let receiver = match &group.sender {
+            GroupSender::Default(sender) => sender.subscribe(),
+            _ => {
+                return Err(SieveError::SubscriptionError(
+                    "Invalid subscription type".to_string(),
+                ))
+            }
+        };
+
+        self.window_manager
+            .create_window(group_id, Vec::new(), duration);
+        filters.insert(group_id, group);
+
+        Ok(Subscription::new(group_id, receiver, self.clone()))
     }
 
     /// Creates a time-window based subscription
@@ -420,12 +872,12 @@ impl Sieve {
     /// * `duration` - How long to wait for all conditions
     ///
     /// # Returns
-    /// Stream of window events (matches or timeout)
+    /// Subscription streaming the window event (match or timeout), it ends once the window closes
     pub async fn watch_within(
         &self,
         filters: Vec<Filter>,
         duration: Duration,
-    ) -> Result<BroadcastStream<EventWindow>, SieveError> {
+    ) -> Result<Subscription<EventWindow>, SieveError> {
         if duration.is_zero() {
             return Err(SieveError::InvalidWindowDuration(
                 "Window duration cannot be zero".to_string(),
@@ -455,98 +907,475 @@ impl Sieve {
             .create_window(group_id, filter_ids, duration);
 
         filter_entries.insert(group_id, group);
-        Ok(BroadcastStream::new(receiver))
+        Ok(Subscription::new(group_id, receiver, self.clone()))
     }
 
     /// Processes a block through all filter groups
     async fn process_any_rpc_block(&self, block: &AnyRpcBlock, chain: &Chain) {
         let filters = self.filters.read().await;
 
-        for group in filters.values() {
-            let matches = group.evaluate_block(block, &self.engine, chain);
-            match group.sub_type {
-                SubscriptionType::Default => {
-                    for (_, event) in matches {
-                        group.send_event(event);
-                    }
+        let block_ref = BlockRef::from(&block.header);
+        let mut canonical_chain = self
+            .canonical_chains
+            .entry(chain.clone())
+            .or_insert_with(|| ReorgDetector::new(MAX_REORG_DEPTH));
+
+        if canonical_chain.contains(&block_ref.hash) {
+            return;
+        }
+
+        // Included transactions, and the pending ones they replace, are no longer pending
+        let settled = self
+            .mempools
+            .get_mut(chain)
+            .map(|mut mempool| {
+                block
+                    .transactions()
+                    .txns()
+                    .enumerate()
+                    .filter_map(|(index, tx)| mempool.include(tx, block_ref.number, index as u64))
+                    .collect()
+            })
+            .unwrap_or_default();
+
+        // Retract events emitted for blocks that are no longer part of the canonical chain,
+        // events still waiting for confirmations are silently dropped.
+        for (dropped, matches) in canonical_chain.push(block_ref) {
+            tracing::warn!(?chain, number = dropped.number, hash = %dropped.hash, "Block reorged out");
+            for matched in matches.into_iter().filter(|matched| matched.sent) {
+                if let Some(group) = filters.get(&matched.group_id) {
+                    group.send_event(Event::Removed(Box::new(matched.event)));
+                }
+            }
+        }
+
+        // Logs of this block that were received before the block itself
+        let early_logs = self
+            .pending_logs
+            .get_mut(chain)
+            .and_then(|mut pending_logs| {
+                let logs = pending_logs.remove(&block_ref.hash);
+                pending_logs.retain(|_, logs| {
+                    logs.iter().any(|log| {
+                        log.block_number.is_some_and(|number| {
+                            number.saturating_add(MAX_REORG_DEPTH as u64) > block_ref.number
+                        })
+                    })
+                });
+                logs
+            });
+
+        if let Some(block_events) = canonical_chain.payload_mut(&block_ref.hash) {
+            for (group_id, event) in Self::status_events(settled) {
+                if let Some(group) = filters.get(&group_id) {
+                    block_events.push(BlockEvent {
+                        group_id,
+                        event: event.clone(),
+                        sent: true,
+                    });
+                    group.send_event(event);
                 }
-                SubscriptionType::WatchWindow => {
-                    self.window_manager
-                        .process_events(group.group_id, matches, group);
+            }
+
+            for group in filters.values() {
+                let mut matches = group.evaluate_block(block, &self.engine, chain);
+                if let Some(logs) = &early_logs {
+                    matches.extend(group.evaluate_logs(logs, &self.engine, chain));
                 }
+                self.dispatch_block_matches(group, matches, block_events);
             }
         }
+
+        Self::release_confirmed_events(&filters, &mut canonical_chain, block_ref.number);
     }
 
-    /// Processes a transaction through all filter groups
-    async fn process_any_rpc_transaction(&self, tx: &AnyRpcTransaction, chain: &Chain) {
+    /// Processes the logs of canonical blocks through all filter groups. Logs of a block that
+    /// has not been processed yet are held back until it is.
+    async fn process_any_rpc_logs(&self, logs: Vec<Log>, chain: &Chain) {
         let filters = self.filters.read().await;
 
-        for group in filters.values() {
-            let matches = group.evaluate_transaction(tx, &self.engine, chain);
+        let mut logs_by_block: HashMap<B256, Vec<Log>> = HashMap::new();
+        // Removed logs are retracted through the reorg of their block.
+        for log in logs.into_iter().filter(|log| !log.removed) {
+            if let Some(block_hash) = log.block_hash {
+                logs_by_block.entry(block_hash).or_default().push(log);
+            }
+        }
 
-            match group.sub_type {
-                SubscriptionType::Default => {
-                    for (_, event) in matches {
-                        group.send_event(event);
-                    }
+        let mut canonical_chain = self
+            .canonical_chains
+            .entry(chain.clone())
+            .or_insert_with(|| ReorgDetector::new(MAX_REORG_DEPTH));
+
+        for (block_hash, logs) in logs_by_block {
+            let Some(block_events) = canonical_chain.payload_mut(&block_hash) else {
+                self.pending_logs
+                    .entry(chain.clone())
+                    .or_default()
+                    .entry(block_hash)
+                    .or_default()
+                    .extend(logs);
+                continue;
+            };
+
+            for group in filters.values() {
+                let matches = group.evaluate_logs(&logs, &self.engine, chain);
+                self.dispatch_block_matches(group, matches, block_events);
+            }
+        }
+
+        if let Some(tip) = canonical_chain.tip().map(|tip| tip.number) {
+            Self::release_confirmed_events(&filters, &mut canonical_chain, tip);
+        }
+    }
+
+    /// Sends events matched in a canonical block according to the group subscription type and
+    /// records them against the block so they can be retracted on reorg.
+    fn dispatch_block_matches(
+        &self,
+        group: &FilterGroup,
+        matches: Vec<(u64, Event)>,
+        block_events: &mut BlockEvents,
+    ) {
+        match group.sub_type {
+            SubscriptionType::Default | SubscriptionType::Ttl => {
+                for (_, event) in matches {
+                    block_events.push(BlockEvent {
+                        group_id: group.group_id,
+                        event: event.clone(),
+                        sent: true,
+                    });
+                    group.send_event(event);
                 }
-                SubscriptionType::WatchWindow => {
-                    self.window_manager
-                        .process_events(group.group_id, matches, group);
+            }
+            SubscriptionType::Once => {
+                if let Some((_, event)) = self.take_first_match(group, matches) {
+                    block_events.push(BlockEvent {
+                        group_id: group.group_id,
+                        event: event.clone(),
+                        sent: true,
+                    });
+                    group.send_event(event);
                 }
             }
+            SubscriptionType::Confirmations(_) => {
+                block_events.extend(matches.into_iter().map(|(_, event)| BlockEvent {
+                    group_id: group.group_id,
+                    event,
+                    sent: false,
+                }));
+            }
+            SubscriptionType::WatchWindow => {
+                self.window_manager
+                    .process_events(group.group_id, matches, group);
+            }
+            // Lifecycle groups only match pending transactions
+            SubscriptionType::Lifecycle => {}
         }
     }
-    /// Starts background tasks for processing chain data
-    async fn start_chain_processors(&self) -> Result<(), SieveError> {
-        let mut processor_handles = Vec::new();
-        for chain in self.ingest.active_chains() {
-            let mut stream = self.ingest.subscribe_stream(chain.clone()).await?;
-            let sieve = self.clone();
 
-            let handle = tokio::spawn(async move {
-                while let Some(Ok(chain_data)) = stream.next().await {
-                    match chain_data {
-                        ChainData::AnyRPCNetwork(AnyRPCNetwork::Block(block)) => {
-                            sieve.process_any_rpc_block(&block, &chain).await;
-                        }
-                        ChainData::AnyRPCNetwork(AnyRPCNetwork::TransactionPool(tx)) => {
-                            sieve.process_any_rpc_transaction(&tx, &chain).await;
-                        }
+    /// Takes the first match of a one-shot group and closes its window, so that it is emitted
+    /// once even when several matches race each other.
+    fn take_first_match(
+        &self,
+        group: &FilterGroup,
+        matches: Vec<(u64, Event)>,
+    ) -> Option<(u64, Event)> {
+        let first = matches.into_iter().next()?;
+        self.window_manager
+            .remove_window(group.group_id)
+            .then_some(first)
+    }
+
+    /// Releases events whose block is now deep enough for their confirmation subscription
+    fn release_confirmed_events(
+        filters: &HashMap<u64, FilterGroup>,
+        canonical_chain: &mut ReorgDetector<BlockEvents>,
+        tip: u64,
+    ) {
+        for (canonical_block, block_events) in canonical_chain.iter_mut() {
+            for pending in block_events.iter_mut().filter(|matched| !matched.sent) {
+                let Some(group) = filters.get(&pending.group_id) else {
+                    continue;
+                };
+                if let SubscriptionType::Confirmations(confirmations) = group.sub_type {
+                    if canonical_block.number.saturating_add(confirmations) <= tip {
+                        group.send_event(pending.event.clone());
+                        pending.sent = true;
                     }
                 }
-            });
-
-            processor_handles.push(handle);
+            }
         }
+    }
 
-        Ok(())
+    /// Processes a mempool transaction through all filter groups and keeps track of it until
+    /// it is included in a block
+    async fn process_any_rpc_transaction(&self, tx: PendingTransaction, chain: &Chain) {
+        let filters = self.filters.read().await;
+
+        let hash = tx.transaction.info().hash.unwrap_or_default();
+        let mut mempool = self.mempools.entry(chain.clone()).or_insert_with(|| {
+            Mempool::new(
+                DEFAULT_MEMPOOL_CAPACITY,
+                DEFAULT_PENDING_LIFETIME,
+                DEFAULT_REMOVAL_GRACE_PERIOD,
+            )
+        });
+        Self::send_status_events(&filters, mempool.insert(tx));
+
+        let Some(entry) = mempool.get_mut(&hash) else {
+            return;
+        };
+        for group in filters.values() {
+            let matches = group.evaluate_transaction(entry, &self.engine, chain);
+            self.dispatch_pool_matches(group, matches, entry);
+        }
     }
-    /// Handle window expiration by sending timeout event
-    fn handle_window_expiration(
-        filters: Arc<RwLock<HashMap<u64, FilterGroup>>>,
-    ) -> impl Fn(u64) + Send + Sync + 'static {
-        move |group_id| {
-            if let Ok(filters_rw) = filters.try_read() {
-                if let Some(group) = filters_rw.get(&group_id) {
-                    group.send_window_event(EventWindow::Timeout);
-                }
+
+    /// Records transactions that left the node's pool, they are dropped unless a block includes
+    /// them shortly after
+    fn process_pool_removals(&self, hashes: Vec<B256>, chain: &Chain) {
+        if let Some(mut mempool) = self.mempools.get_mut(chain) {
+            for hash in &hashes {
+                mempool.mark_left_pool(hash);
             }
         }
     }
 
-    /// Gets the current number of subscribers for a filter
-    #[allow(dead_code)]
-    async fn subscriber_count(&self, filter: &Filter) -> usize {
-        self.filters
-            .read()
-            .await
-            .get(&filter.id())
-            .map(|filter_group| match &filter_group.sender {
-                GroupSender::Default(event_sender) => event_sender.receiver_count(),
-                GroupSender::Watch(sender) => sender.receiver_count(),
-            })
+    /// Drops transactions pending for too long and re-evaluates the others against time
+    /// dependent pool filters, e.g. to catch transactions that have been stuck in the mempool.
+    ///
+    /// The mempool is only locked to take a snapshot of the transactions time dependent filters
+    /// did not match yet, and then to record their matches, not while they are evaluated.
+    async fn reevaluate_mempools(&self) {
+        let filters = self.filters.read().await;
+        let lingering_groups = filters
+            .values()
+            .filter(|group| group.is_time_dependent())
+            .collect::<Vec<_>>();
+
+        let chains = self
+            .mempools
+            .iter()
+            .map(|mempool| mempool.key().clone())
+            .collect::<Vec<_>>();
+        for chain in chains {
+            let snapshot = {
+                let Some(mut mempool) = self.mempools.get_mut(&chain) else {
+                    continue;
+                };
+                Self::send_status_events(&filters, mempool.drop_expired());
+
+                mempool
+                    .iter_mut()
+                    .filter_map(|entry| {
+                        let lingering = lingering_groups
+                            .iter()
+                            .flat_map(|group| {
+                                group
+                                    .pool_filters(entry, &chain, true)
+                                    .map(move |filter| (*group, filter))
+                            })
+                            .collect::<Vec<_>>();
+                        (!lingering.is_empty())
+                            .then(|| (entry.hash(), Arc::new(entry.transaction.clone()), lingering))
+                    })
+                    .collect::<Vec<_>>()
+            };
+            if snapshot.is_empty() {
+                continue;
+            }
+
+            let matched = snapshot
+                .into_iter()
+                .map(|(hash, transaction, lingering)| {
+                    let matched = lingering
+                        .into_iter()
+                        .filter(|(_, filter)| {
+                            self.engine.evaluate_with_context(
+                                filter.filter_node().as_ref(),
+                                transaction.clone(),
+                            )
+                        })
+                        .collect::<Vec<_>>();
+                    (hash, matched)
+                })
+                .filter(|(_, matched)| !matched.is_empty())
+                .collect::<Vec<_>>();
+            if matched.is_empty() {
+                continue;
+            }
+
+            // Transactions may have been included or dropped while they were evaluated
+            let Some(mut mempool) = self.mempools.get_mut(&chain) else {
+                continue;
+            };
+            for (hash, matched) in matched {
+                let Some(entry) = mempool.get_mut(&hash) else {
+                    continue;
+                };
+                for (group, filter) in matched {
+                    let matches = group.record_pool_matches(entry, [filter.id()]);
+                    self.dispatch_pool_matches(group, matches, entry);
+                }
+            }
+        }
+    }
+
+    /// Status events of transactions that stopped being pending, for the groups following them
+    fn status_events(settled: Vec<(PendingEntry, TransactionStatus)>) -> Vec<(u64, Event)> {
+        settled
+            .into_iter()
+            .flat_map(|(entry, status)| {
+                let event = Event::Status {
+                    hash: entry.hash(),
+                    status,
+                };
+                entry
+                    .tracked_by()
+                    .map(|group_id| (*group_id, event.clone()))
+                    .collect::<Vec<_>>()
+            })
+            .collect()
+    }
+
+    /// Sends the status events of transactions that stopped being pending
+    fn send_status_events(
+        filters: &HashMap<u64, FilterGroup>,
+        settled: Vec<(PendingEntry, TransactionStatus)>,
+    ) {
+        for (group_id, event) in Self::status_events(settled) {
+            if let Some(group) = filters.get(&group_id) {
+                group.send_event(event);
+            }
+        }
+    }
+
+    /// Sends events matched in the mempool according to the group subscription type
+    fn dispatch_pool_matches(
+        &self,
+        group: &FilterGroup,
+        matches: Vec<(u64, Event)>,
+        entry: &mut PendingEntry,
+    ) {
+        match group.sub_type {
+            SubscriptionType::Default | SubscriptionType::Ttl => {
+                for (_, event) in matches {
+                    group.send_event(event);
+                }
+            }
+            SubscriptionType::Once => {
+                if let Some((_, event)) = self.take_first_match(group, matches) {
+                    group.send_event(event);
+                }
+            }
+            SubscriptionType::WatchWindow => {
+                self.window_manager
+                    .process_events(group.group_id, matches, group);
+            }
+            SubscriptionType::Lifecycle => {
+                if !matches.is_empty() && entry.track(group.group_id) {
+                    group.send_event(Event::Status {
+                        hash: entry.hash(),
+                        status: TransactionStatus::Pending,
+                    });
+                }
+            }
+            // Mempool transactions never get confirmations
+            SubscriptionType::Confirmations(_) => {}
+        }
+    }
+    /// Starts background tasks for processing chain data
+    async fn start_chain_processors(&self) -> Result<(), SieveError> {
+        let mut processor_handles = Vec::new();
+        for chain in self.ingest.active_chains() {
+            let mut stream = self.ingest.subscribe_stream(chain.clone()).await?;
+            let sieve = self.clone();
+
+            let handle = tokio::spawn(async move {
+                while let Some(Ok(chain_data)) = stream.next().await {
+                    match chain_data {
+                        ChainData::AnyRPCNetwork(AnyRPCNetwork::Block(block)) => {
+                            sieve.process_any_rpc_block(&block, &chain).await;
+                        }
+                        ChainData::AnyRPCNetwork(AnyRPCNetwork::TransactionPool(tx)) => {
+                            sieve.process_any_rpc_transaction(tx, &chain).await;
+                        }
+                        ChainData::AnyRPCNetwork(AnyRPCNetwork::TransactionPoolRemovals(
+                            hashes,
+                        )) => {
+                            sieve.process_pool_removals(hashes, &chain);
+                        }
+                        ChainData::AnyRPCNetwork(AnyRPCNetwork::Logs(logs)) => {
+                            sieve.process_any_rpc_logs(logs, &chain).await;
+                        }
+                    }
+                    sieve.remove_closed_groups().await;
+                }
+            });
+
+            processor_handles.push(handle);
+        }
+
+        let sieve = self.clone();
+        processor_handles.push(tokio::spawn(async move {
+            let mut interval = tokio::time::interval(MEMPOOL_REEVALUATION_INTERVAL);
+            loop {
+                interval.tick().await;
+                sieve.reevaluate_mempools().await;
+            }
+        }));
+
+        self.processors
+            .lock()
+            .unwrap_or_else(|e| e.into_inner())
+            .extend(processor_handles);
+        Ok(())
+    }
+    /// Removes groups without subscribers and watch groups whose window has closed
+    async fn remove_closed_groups(&self) {
+        let is_closed = |group: &FilterGroup| {
+            group.receiver_count() == 0
+                || (group.sub_type.is_windowed() && !self.window_manager.has_window(group.group_id))
+        };
+
+        // Only take the write lock when there is something to remove
+        if !self.filters.read().await.values().any(is_closed) {
+            return;
+        }
+
+        self.filters.write().await.retain(|group_id, group| {
+            let closed = is_closed(group);
+            if closed {
+                self.window_manager.remove_window(*group_id);
+            }
+            !closed
+        });
+    }
+
+    /// Handle window expiration by sending the terminal event and removing the group
+    fn handle_window_expiration(
+        filters: Arc<RwLock<HashMap<u64, FilterGroup>>>,
+    ) -> impl Fn(u64) + Send + Sync + 'static {
+        move |group_id| {
+            let filters = filters.clone();
+            tokio::spawn(async move {
+                // Receivers still get the event after the group sender is dropped
+                if let Some(group) = filters.write().await.remove(&group_id) {
+                    group.send_expired();
+                }
+            });
+        }
+    }
+
+    /// Gets the current number of subscribers for a filter
+    #[allow(dead_code)]
+    async fn subscriber_count(&self, filter: &Filter) -> usize {
+        self.filters
+            .read()
+            .await
+            .get(&filter.id())
+            .map(FilterGroup::receiver_count)
             .unwrap_or(0)
     }
 }
@@ -556,14 +1385,17 @@ mod tests {
 
     use super::*;
     use alloy_consensus::Transaction;
-    use alloy_primitives::U256;
+    use alloy_network::AnyHeader;
+    use alloy_primitives::{Address, LogData, U256};
     use alloy_rpc_types::Block;
     use config::Chain;
-    use filter::{FilterBuilder, NumericOps};
+    use filter::{FilterBuilder, NumericOps, StringOps};
     use futures::StreamExt;
     use ingest::IngestError;
     use tokio::time::Duration;
-    use utils::test_utils::generate_random_transaction;
+    use utils::test_utils::{generate_random_transaction, generate_transaction};
+
+    const FEE: u128 = 20_000_000_000;
 
     pub struct MockIngest {
         chain_states: Arc<Mutex<HashMap<Chain, broadcast::Sender<ChainData>>>>,
@@ -633,16 +1465,9 @@ mod tests {
         let engine = Arc::new(FilterEngine::new());
         let filters: Arc<RwLock<HashMap<u64, FilterGroup>>> = Arc::new(RwLock::new(HashMap::new()));
 
-        let clone_filters = filters.clone();
         let window_manager = Arc::new(WindowManager::new(
             Duration::from_secs(1),
-            move |group_id| {
-                if let Ok(filters_rw) = clone_filters.try_read() {
-                    if let Some(group) = filters_rw.get(&group_id) {
-                        group.send_window_event(EventWindow::Timeout);
-                    }
-                }
-            },
+            Sieve::handle_window_expiration(filters.clone()),
         ));
 
         let sieve = Sieve {
@@ -650,6 +1475,10 @@ mod tests {
             ingest: mock_ingest.clone(),
             filters,
             window_manager,
+            canonical_chains: Arc::new(DashMap::new()),
+            pending_logs: Arc::new(DashMap::new()),
+            mempools: Arc::new(DashMap::new()),
+            processors: Arc::new(std::sync::Mutex::new(Vec::new())),
         };
 
         sieve.start_chain_processors().await?;
@@ -657,6 +1486,46 @@ mod tests {
         Ok((sieve, mock_ingest))
     }
 
+    fn mock_block(
+        number: u64,
+        hash: B256,
+        parent_hash: B256,
+        transactions: Vec<AnyRpcTransaction>,
+    ) -> ChainData {
+        let block = Block {
+            header: Header {
+                hash,
+                inner: AnyHeader {
+                    number,
+                    parent_hash,
+                    ..Default::default()
+                },
+                ..Default::default()
+            },
+            transactions: BlockTransactions::Full(transactions),
+            ..Default::default()
+        };
+        ChainData::AnyRPCNetwork(AnyRPCNetwork::Block(AnyRpcBlock::new(block)))
+    }
+
+    fn mock_log(block_number: u64, block_hash: B256, address: Address) -> Log {
+        Log {
+            inner: alloy_primitives::Log {
+                address,
+                data: LogData::default(),
+            },
+            block_hash: Some(block_hash),
+            block_number: Some(block_number),
+            ..Default::default()
+        }
+    }
+
+    fn log_filter(address: Address) -> Filter {
+        FilterBuilder::new().event(|ev| {
+            ev.contract().exact(&address.to_string());
+        })
+    }
+
     #[tokio::test]
     async fn test_single_subscribe() -> Result<(), Box<dyn std::error::Error>> {
         let (sieve, mock_ingest) = setup_test_sieve().await?;
@@ -750,6 +1619,11 @@ mod tests {
             }
         }
 
+        // The window is closed, its group is removed and the stream ends.
+        let next = tokio::time::timeout(Duration::from_secs(5), stream.next()).await?;
+        assert!(next.is_none());
+        assert!(sieve.filters.read().await.is_empty());
+
         Ok(())
     }
 
@@ -776,6 +1650,693 @@ mod tests {
             }
         }
 
+        let next = tokio::time::timeout(Duration::from_secs(5), stream.next()).await?;
+        assert!(next.is_none());
+        assert!(sieve.filters.read().await.is_empty());
+
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn test_subscribe_once() -> Result<(), Box<dyn std::error::Error>> {
+        let (sieve, mock_ingest) = setup_test_sieve().await?;
+
+        let filter = FilterBuilder::new().transaction(|f| {
+            f.value().gt(U256::from(1000));
+        });
+        let mut stream = sieve.subscribe_once(filter, Duration::from_secs(5)).await?;
+
+        mock_ingest.mock_chain_data(
+            Chain::Ethereum,
+            mock_block(
+                1,
+                B256::repeat_byte(1),
+                B256::ZERO,
+                vec![
+                    generate_random_transaction(1500),
+                    generate_random_transaction(2500),
+                ],
+            ),
+        )?;
+
+        let event = tokio::time::timeout(Duration::from_secs(5), stream.next())
+            .await?
+            .expect("stream ended")?;
+        assert!(matches!(event, Event::Transaction(tx) if tx.value() == U256::from(1500)));
+
+        // Only the first match is emitted, then the subscription closes.
+        let next = tokio::time::timeout(Duration::from_secs(5), stream.next()).await?;
+        assert!(next.is_none());
+        assert!(sieve.filters.read().await.is_empty());
+
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn test_subscriptions_expire() -> Result<(), Box<dyn std::error::Error>> {
+        let (sieve, _mock_ingest) = setup_test_sieve().await?;
+
+        let filter = FilterBuilder::new().transaction(|f| {
+            f.value().gt(U256::from(1000));
+        });
+        let mut once = sieve
+            .subscribe_once(filter.clone(), Duration::from_millis(10))
+            .await?;
+        let mut ttl = sieve
+            .subscribe_for(filter.clone(), Duration::from_millis(10))
+            .await?;
+        assert_eq!(sieve.filters.read().await.len(), 2);
+
+        for stream in [&mut once, &mut ttl] {
+            let event = tokio::time::timeout(Duration::from_secs(5), stream.next())
+                .await?
+                .expect("stream ended")?;
+            assert_eq!(event, Event::Expired);
+
+            let next = tokio::time::timeout(Duration::from_secs(5), stream.next()).await?;
+            assert!(next.is_none());
+        }
+        assert!(sieve.filters.read().await.is_empty());
+
+        assert!(matches!(
+            sieve.subscribe_for(filter, Duration::ZERO).await,
+            Err(SieveError::InvalidWindowDuration(_))
+        ));
+
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn test_unsubscribe() -> Result<(), Box<dyn std::error::Error>> {
+        let (sieve, _mock_ingest) = setup_test_sieve().await?;
+
+        let filter = FilterBuilder::new().transaction(|f| {
+            f.value().gt(U256::from(1000));
+        });
+        let first = sieve.subscribe(filter.clone()).await?;
+        let second = sieve.subscribe(filter).await?;
+        assert_eq!(sieve.filters.read().await.len(), 1);
+
+        // The group is shared and kept while a subscription is still alive.
+        first.unsubscribe().await;
+        assert_eq!(sieve.filters.read().await.len(), 1);
+
+        second.unsubscribe().await;
+        assert!(sieve.filters.read().await.is_empty());
+
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn test_dropped_subscriptions_are_removed() -> Result<(), Box<dyn std::error::Error>> {
+        let (sieve, mock_ingest) = setup_test_sieve().await?;
+
+        let dropped = FilterBuilder::new().transaction(|f| {
+            f.value().gt(U256::from(1000));
+        });
+        let kept = FilterBuilder::new().transaction(|f| {
+            f.value().gt(U256::from(2000));
+        });
+        drop(sieve.subscribe(dropped).await?);
+        let mut stream = sieve.subscribe(kept).await?;
+
+        mock_ingest.mock_chain_data(
+            Chain::Ethereum,
+            mock_block(
+                1,
+                B256::repeat_byte(1),
+                B256::ZERO,
+                vec![generate_random_transaction(2500)],
+            ),
+        )?;
+
+        // Groups are cleaned up once the data has been processed.
+        tokio::time::timeout(Duration::from_secs(5), stream.next())
+            .await?
+            .expect("stream ended")?;
+        tokio::time::sleep(Duration::from_millis(100)).await;
+        assert_eq!(sieve.filters.read().await.len(), 1);
+
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn test_reorg_emits_removed_events() -> Result<(), Box<dyn std::error::Error>> {
+        let (sieve, mock_ingest) = setup_test_sieve().await?;
+
+        let filter = FilterBuilder::new().transaction(|f| {
+            f.value().gt(U256::from(1000));
+        });
+        let mut stream = sieve.subscribe(filter).await?;
+
+        let orphaned_tx = generate_random_transaction(1500);
+        let canonical_tx = generate_random_transaction(2500);
+
+        mock_ingest.mock_chain_data(
+            Chain::Ethereum,
+            mock_block(1, B256::repeat_byte(1), B256::ZERO, vec![]),
+        )?;
+        mock_ingest.mock_chain_data(
+            Chain::Ethereum,
+            mock_block(
+                2,
+                B256::repeat_byte(2),
+                B256::repeat_byte(1),
+                vec![orphaned_tx],
+            ),
+        )?;
+        // Sibling of block 2, the first block 2 is reorged out.
+        mock_ingest.mock_chain_data(
+            Chain::Ethereum,
+            mock_block(
+                2,
+                B256::repeat_byte(3),
+                B256::repeat_byte(1),
+                vec![canonical_tx],
+            ),
+        )?;
+
+        let mut events = Vec::new();
+        while events.len() < 3 {
+            let event = tokio::time::timeout(Duration::from_secs(5), stream.next())
+                .await?
+                .expect("stream ended")?;
+            events.push(event);
+        }
+
+        assert!(matches!(&events[0], Event::Transaction(tx) if tx.value() == U256::from(1500)));
+        assert!(matches!(
+            &events[1],
+            Event::Removed(event) if matches!(event.as_ref(), Event::Transaction(tx) if tx.value() == U256::from(1500))
+        ));
+        assert!(matches!(&events[2], Event::Transaction(tx) if tx.value() == U256::from(2500)));
+
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn test_deep_reorg_drops_every_replaced_block() -> Result<(), Box<dyn std::error::Error>>
+    {
+        let (sieve, mock_ingest) = setup_test_sieve().await?;
+
+        let filter = FilterBuilder::new().transaction(|f| {
+            f.value().gt(U256::from(1000));
+        });
+        let mut stream = sieve.subscribe(filter.clone()).await?;
+        let mut confirmed = sieve.subscribe_with_confirmations(filter, 2).await?;
+
+        let orphaned_tx = generate_random_transaction(1500);
+        let canonical_tx = generate_random_transaction(2500);
+
+        let chain = [
+            mock_block(1, B256::repeat_byte(1), B256::ZERO, vec![]),
+            mock_block(
+                2,
+                B256::repeat_byte(2),
+                B256::repeat_byte(1),
+                vec![orphaned_tx],
+            ),
+            mock_block(3, B256::repeat_byte(3), B256::repeat_byte(2), vec![]),
+            // Fork from block 1, its blocks are delivered oldest first
+            mock_block(
+                2,
+                B256::repeat_byte(0x22),
+                B256::repeat_byte(1),
+                vec![canonical_tx],
+            ),
+            mock_block(3, B256::repeat_byte(0x33), B256::repeat_byte(0x22), vec![]),
+            mock_block(4, B256::repeat_byte(0x44), B256::repeat_byte(0x33), vec![]),
+        ];
+        for block in chain {
+            mock_ingest.mock_chain_data(Chain::Ethereum, block)?;
+        }
+
+        let mut events = Vec::new();
+        while events.len() < 3 {
+            let event = tokio::time::timeout(Duration::from_secs(5), stream.next())
+                .await?
+                .expect("stream ended")?;
+            events.push(event);
+        }
+        assert!(matches!(&events[0], Event::Transaction(tx) if tx.value() == U256::from(1500)));
+        assert!(matches!(
+            &events[1],
+            Event::Removed(event) if matches!(event.as_ref(), Event::Transaction(tx) if tx.value() == U256::from(1500))
+        ));
+        assert!(matches!(&events[2], Event::Transaction(tx) if tx.value() == U256::from(2500)));
+
+        // The orphaned match never reaches its confirmation depth
+        let event = tokio::time::timeout(Duration::from_secs(5), confirmed.next())
+            .await?
+            .expect("stream ended")?;
+        assert!(matches!(event, Event::Transaction(tx) if tx.value() == U256::from(2500)));
+        assert!(
+            tokio::time::timeout(Duration::from_millis(100), confirmed.next())
+                .await
+                .is_err()
+        );
+
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn test_subscribe_with_confirmations() -> Result<(), Box<dyn std::error::Error>> {
+        let (sieve, mock_ingest) = setup_test_sieve().await?;
+
+        let filter = FilterBuilder::new().transaction(|f| {
+            f.value().gt(U256::from(1000));
+        });
+        let mut stream = sieve.subscribe_with_confirmations(filter, 2).await?;
+
+        let tx = generate_random_transaction(1500);
+        mock_ingest.mock_chain_data(
+            Chain::Ethereum,
+            mock_block(1, B256::repeat_byte(1), B256::ZERO, vec![tx]),
+        )?;
+        mock_ingest.mock_chain_data(
+            Chain::Ethereum,
+            mock_block(2, B256::repeat_byte(2), B256::repeat_byte(1), vec![]),
+        )?;
+
+        // Only one block was built on top of the match.
+        assert!(
+            tokio::time::timeout(Duration::from_millis(100), stream.next())
+                .await
+                .is_err()
+        );
+
+        mock_ingest.mock_chain_data(
+            Chain::Ethereum,
+            mock_block(3, B256::repeat_byte(3), B256::repeat_byte(2), vec![]),
+        )?;
+
+        let event = tokio::time::timeout(Duration::from_secs(5), stream.next())
+            .await?
+            .expect("stream ended")?;
+        assert!(matches!(event, Event::Transaction(tx) if tx.value() == U256::from(1500)));
+
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn test_unconfirmed_events_are_dropped_on_reorg() -> Result<(), Box<dyn std::error::Error>>
+    {
+        let (sieve, mock_ingest) = setup_test_sieve().await?;
+
+        let filter = FilterBuilder::new().transaction(|f| {
+            f.value().gt(U256::from(1000));
+        });
+        let mut stream = sieve.subscribe_with_confirmations(filter, 1).await?;
+
+        let orphaned_tx = generate_random_transaction(1500);
+        let canonical_tx = generate_random_transaction(2500);
+
+        mock_ingest.mock_chain_data(
+            Chain::Ethereum,
+            mock_block(1, B256::repeat_byte(1), B256::ZERO, vec![orphaned_tx]),
+        )?;
+        // Sibling of block 1 replaces it before it is confirmed.
+        mock_ingest.mock_chain_data(
+            Chain::Ethereum,
+            mock_block(1, B256::repeat_byte(2), B256::ZERO, vec![canonical_tx]),
+        )?;
+        mock_ingest.mock_chain_data(
+            Chain::Ethereum,
+            mock_block(2, B256::repeat_byte(3), B256::repeat_byte(2), vec![]),
+        )?;
+
+        let event = tokio::time::timeout(Duration::from_secs(5), stream.next())
+            .await?
+            .expect("stream ended")?;
+        assert!(matches!(event, Event::Transaction(tx) if tx.value() == U256::from(2500)));
+        assert!(
+            tokio::time::timeout(Duration::from_millis(100), stream.next())
+                .await
+                .is_err()
+        );
+
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn test_subscribe_with_confirmations_rejects_pool_filters(
+    ) -> Result<(), Box<dyn std::error::Error>> {
+        let (sieve, _mock_ingest) = setup_test_sieve().await?;
+
+        let filter = FilterBuilder::new().pool(|f| {
+            f.value().gt(U256::from(1000));
+        });
+        assert!(matches!(
+            sieve.subscribe_with_confirmations(filter, 2).await,
+            Err(SieveError::SubscriptionError(_))
+        ));
+
+        let filter = FilterBuilder::new().transaction(|f| {
+            f.value().gt(U256::from(1000));
+        });
+        assert!(matches!(
+            sieve
+                .subscribe_with_confirmations(filter, MAX_REORG_DEPTH as u64)
+                .await,
+            Err(SieveError::InvalidConfirmationDepth(_))
+        ));
+
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn test_subscribe_logs() -> Result<(), Box<dyn std::error::Error>> {
+        let (sieve, mock_ingest) = setup_test_sieve().await?;
+
+        let contract = Address::repeat_byte(0xaa);
+        let mut stream = sieve.subscribe(log_filter(contract)).await?;
+
+        let block_hash = B256::repeat_byte(1);
+        mock_ingest.mock_chain_data(
+            Chain::Ethereum,
+            mock_block(1, block_hash, B256::ZERO, vec![]),
+        )?;
+        mock_ingest.mock_chain_data(
+            Chain::Ethereum,
+            ChainData::AnyRPCNetwork(AnyRPCNetwork::Logs(vec![
+                mock_log(1, block_hash, Address::repeat_byte(0xbb)),
+                mock_log(1, block_hash, contract),
+            ])),
+        )?;
+
+        let event = tokio::time::timeout(Duration::from_secs(5), stream.next())
+            .await?
+            .expect("stream ended")?;
+        assert!(matches!(event, Event::Log(log) if log.address() == contract));
+        assert!(
+            tokio::time::timeout(Duration::from_millis(100), stream.next())
+                .await
+                .is_err()
+        );
+
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn test_logs_wait_for_their_block() -> Result<(), Box<dyn std::error::Error>> {
+        let (sieve, mock_ingest) = setup_test_sieve().await?;
+
+        let contract = Address::repeat_byte(0xaa);
+        let mut stream = sieve.subscribe(log_filter(contract)).await?;
+
+        // Logs pushed by the node before their block was fetched.
+        let block_hash = B256::repeat_byte(1);
+        mock_ingest.mock_chain_data(
+            Chain::Ethereum,
+            ChainData::AnyRPCNetwork(AnyRPCNetwork::Logs(vec![mock_log(1, block_hash, contract)])),
+        )?;
+        assert!(
+            tokio::time::timeout(Duration::from_millis(100), stream.next())
+                .await
+                .is_err()
+        );
+
+        mock_ingest.mock_chain_data(
+            Chain::Ethereum,
+            mock_block(1, block_hash, B256::ZERO, vec![]),
+        )?;
+        let event = tokio::time::timeout(Duration::from_secs(5), stream.next())
+            .await?
+            .expect("stream ended")?;
+        assert!(matches!(&event, Event::Log(log) if log.block_hash == Some(block_hash)));
+
+        // The block is reorged out, its log is retracted.
+        mock_ingest.mock_chain_data(
+            Chain::Ethereum,
+            mock_block(1, B256::repeat_byte(2), B256::ZERO, vec![]),
+        )?;
+        let removed = tokio::time::timeout(Duration::from_secs(5), stream.next())
+            .await?
+            .expect("stream ended")?;
+        assert_eq!(removed, Event::Removed(Box::new(event)));
+
+        Ok(())
+    }
+
+    fn mock_pending(tx: &AnyRpcTransaction) -> ChainData {
+        ChainData::AnyRPCNetwork(AnyRPCNetwork::TransactionPool(PendingTransaction::new(
+            tx.clone(),
+        )))
+    }
+
+    #[tokio::test]
+    async fn test_subscribe_pool() -> Result<(), Box<dyn std::error::Error>> {
+        let (sieve, mock_ingest) = setup_test_sieve().await?;
+
+        let filter = FilterBuilder::new().pool(|f| {
+            f.value().gt(U256::from(1000));
+        });
+        let mut stream = sieve.subscribe(filter).await?;
+
+        let tx = generate_random_transaction(2000);
+        mock_ingest.mock_chain_data(Chain::Ethereum, mock_pending(&tx))?;
+        // Transactions seen again are not emitted twice
+        mock_ingest.mock_chain_data(Chain::Ethereum, mock_pending(&tx))?;
+
+        let event = tokio::time::timeout(Duration::from_secs(5), stream.next())
+            .await?
+            .expect("stream ended")?;
+        assert!(matches!(&event, Event::Pool(pending) if pending.transaction == tx));
+        assert!(
+            tokio::time::timeout(Duration::from_millis(100), stream.next())
+                .await
+                .is_err()
+        );
+
+        // Included transactions only match transaction filters
+        mock_ingest.mock_chain_data(
+            Chain::Ethereum,
+            mock_block(1, B256::repeat_byte(1), B256::ZERO, vec![tx]),
+        )?;
+        assert!(
+            tokio::time::timeout(Duration::from_millis(100), stream.next())
+                .await
+                .is_err()
+        );
+
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn test_shutdown() -> Result<(), Box<dyn std::error::Error>> {
+        let (sieve, mock_ingest) = setup_test_sieve().await?;
+
+        let filter = FilterBuilder::new().transaction(|f| {
+            f.value().gt(U256::from(1000));
+        });
+        let mut stream = sieve.subscribe(filter).await?;
+
+        sieve.shutdown().await;
+
+        // Blocks are no longer processed and subscriptions end
+        mock_ingest.mock_chain_data(
+            Chain::Ethereum,
+            mock_block(
+                1,
+                B256::repeat_byte(1),
+                B256::ZERO,
+                vec![generate_random_transaction(1500)],
+            ),
+        )?;
+        let event = tokio::time::timeout(Duration::from_secs(5), stream.next()).await?;
+        assert!(event.is_none());
+        tokio::time::sleep(Duration::from_millis(100)).await;
+        assert!(sieve.canonical_chains.is_empty());
+
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn test_stuck_pool_transactions() -> Result<(), Box<dyn std::error::Error>> {
+        let (sieve, mock_ingest) = setup_test_sieve().await?;
+
+        let filter = FilterBuilder::new().pool(|f| {
+            f.age().gt(Duration::from_millis(200));
+        });
+        let mut stream = sieve.subscribe(filter).await?;
+
+        let stuck = generate_random_transaction(100);
+        let included = generate_random_transaction(200);
+        mock_ingest.mock_chain_data(Chain::Ethereum, mock_pending(&stuck))?;
+        mock_ingest.mock_chain_data(Chain::Ethereum, mock_pending(&included))?;
+        mock_ingest.mock_chain_data(
+            Chain::Ethereum,
+            mock_block(1, B256::repeat_byte(1), B256::ZERO, vec![included]),
+        )?;
+
+        // Only the transaction still pending once old enough is emitted, and only once
+        let event = tokio::time::timeout(Duration::from_secs(5), stream.next())
+            .await?
+            .expect("stream ended")?;
+        match event {
+            Event::Pool(pending) => {
+                assert_eq!(pending.transaction, stuck);
+                assert!(pending.age() > Duration::from_millis(200));
+            }
+            event => panic!("Expected pool event, got {event:?}"),
+        }
+        assert!(
+            tokio::time::timeout(Duration::from_millis(2500), stream.next())
+                .await
+                .is_err()
+        );
+
+        Ok(())
+    }
+
+    async fn next_status(stream: &mut Subscription<Event>) -> (B256, TransactionStatus) {
+        match tokio::time::timeout(Duration::from_secs(5), stream.next()).await {
+            Ok(Some(Ok(Event::Status { hash, status }))) => (hash, status),
+            event => panic!("Expected status event, got {event:?}"),
+        }
+    }
+
+    #[tokio::test]
+    async fn test_transaction_lifecycle() -> Result<(), Box<dyn std::error::Error>> {
+        let (sieve, mock_ingest) = setup_test_sieve().await?;
+
+        let filter = FilterBuilder::new().transaction(|f| {
+            f.value().gt(U256::from(1000));
+        });
+        assert!(matches!(
+            sieve.subscribe_lifecycle(filter).await,
+            Err(SieveError::SubscriptionError(_))
+        ));
+
+        let filter = FilterBuilder::new().pool(|f| {
+            f.value().gt(U256::from(1000));
+        });
+        let mut stream = sieve.subscribe_lifecycle(filter).await?;
+        let hash = |tx: &AnyRpcTransaction| tx.info().hash.unwrap_or_default();
+
+        let sender = Address::repeat_byte(0xaa);
+        let original = generate_transaction(sender, 1, 2000, FEE);
+        let speed_up = generate_transaction(sender, 1, 2000, 2 * FEE);
+        mock_ingest.mock_chain_data(Chain::Ethereum, mock_pending(&original))?;
+        mock_ingest.mock_chain_data(
+            Chain::Ethereum,
+            mock_pending(&generate_transaction(sender, 2, 10, FEE)),
+        )?;
+        mock_ingest.mock_chain_data(Chain::Ethereum, mock_pending(&speed_up))?;
+        mock_ingest.mock_chain_data(
+            Chain::Ethereum,
+            mock_block(
+                1,
+                B256::repeat_byte(1),
+                B256::ZERO,
+                vec![generate_random_transaction(0), speed_up.clone()],
+            ),
+        )?;
+
+        assert_eq!(
+            next_status(&mut stream).await,
+            (hash(&original), TransactionStatus::Pending)
+        );
+        assert_eq!(
+            next_status(&mut stream).await,
+            (
+                hash(&original),
+                TransactionStatus::Replaced {
+                    by: hash(&speed_up)
+                }
+            )
+        );
+        assert_eq!(
+            next_status(&mut stream).await,
+            (hash(&speed_up), TransactionStatus::Pending)
+        );
+        assert_eq!(
+            next_status(&mut stream).await,
+            (
+                hash(&speed_up),
+                TransactionStatus::Included { block: 1, index: 1 }
+            )
+        );
+
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn test_pool_replacements() -> Result<(), Box<dyn std::error::Error>> {
+        let (sieve, mock_ingest) = setup_test_sieve().await?;
+
+        let filter = FilterBuilder::new().pool(|f| {
+            f.is_replacement();
+        });
+        let mut stream = sieve.subscribe(filter).await?;
+
+        let sender = Address::repeat_byte(0xaa);
+        let original = generate_transaction(sender, 1, 100, FEE);
+        let underpriced = generate_transaction(sender, 1, 100, FEE);
+        let speed_up = generate_transaction(sender, 1, 100, 2 * FEE);
+        for tx in [&original, &underpriced, &speed_up] {
+            mock_ingest.mock_chain_data(Chain::Ethereum, mock_pending(tx))?;
+        }
+
+        let event = tokio::time::timeout(Duration::from_secs(5), stream.next())
+            .await?
+            .expect("stream ended")?;
+        match event {
+            Event::PoolReplacement { old, new } => {
+                assert_eq!(old.transaction, original);
+                assert_eq!(new.transaction, speed_up);
+                assert_eq!(new.replaces, original.info().hash);
+            }
+            event => panic!("Expected pool replacement event, got {event:?}"),
+        }
+        assert!(
+            tokio::time::timeout(Duration::from_millis(100), stream.next())
+                .await
+                .is_err()
+        );
+
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn test_event_filter_ignores_transactions() -> Result<(), Box<dyn std::error::Error>> {
+        let (sieve, mock_ingest) = setup_test_sieve().await?;
+
+        let filter = FilterBuilder::new().event(|ev| {
+            ev.block_number().gte(0);
+        });
+        let mut stream = sieve.subscribe(filter).await?;
+
+        let block_hash = B256::repeat_byte(1);
+        mock_ingest.mock_chain_data(
+            Chain::Ethereum,
+            mock_block(
+                1,
+                block_hash,
+                B256::ZERO,
+                vec![generate_random_transaction(100)],
+            ),
+        )?;
+        mock_ingest.mock_chain_data(
+            Chain::Ethereum,
+            ChainData::AnyRPCNetwork(AnyRPCNetwork::Logs(vec![mock_log(
+                1,
+                block_hash,
+                Address::repeat_byte(0xaa),
+            )])),
+        )?;
+
+        // Only the log is emitted, never the transaction of the block.
+        let event = tokio::time::timeout(Duration::from_secs(5), stream.next())
+            .await?
+            .expect("stream ended")?;
+        assert!(matches!(event, Event::Log(_)));
+        assert!(
+            tokio::time::timeout(Duration::from_millis(100), stream.next())
+                .await
+                .is_err()
+        );
+
         Ok(())
     }
 }
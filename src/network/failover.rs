@@ -0,0 +1,438 @@
+//! RPC transport spreading the requests of a chain over its endpoints.
+//!
+//! Requests go to the healthy endpoints with the best priority, rotating between them, and fail
+//! over to the next endpoint on transport errors. Health checks take endpoints that stop
+//! responding or whose head stalls behind the others out of rotation until they catch up.
+use std::{
+    sync::{
+        atomic::{AtomicBool, AtomicUsize, Ordering},
+        Arc, Mutex,
+    },
+    task::{Context, Poll},
+    time::Duration,
+};
+
+use alloy_json_rpc::{RequestPacket, ResponsePacket};
+use alloy_primitives::U64;
+use alloy_rpc_client::RpcClient;
+use alloy_transport::{TransportError, TransportErrorKind, TransportFut};
+use alloy_transport_http::Http;
+use futures::{future, stream::FuturesUnordered, StreamExt};
+use reqwest::Client;
+use tokio::time::{self, Instant};
+use tower::Service;
+use tracing::{debug, warn};
+
+use crate::{config::RpcEndpoint, network::orchestrator::OrchestratorError};
+
+/// Interval between two health checks of the endpoints of a chain.
+pub(crate) const HEALTH_CHECK_INTERVAL: Duration = Duration::from_secs(5);
+
+/// Time an endpoint has to answer a request before the request fails over to the next one.
+const REQUEST_TIMEOUT: Duration = Duration::from_secs(10);
+
+/// Number of blocks an endpoint can lag behind the highest head before it is considered stalled.
+const MAX_HEAD_LAG: u64 = 3;
+
+/// Methods relying on state kept by a single node, these always go to the preferred endpoint.
+const STATEFUL_METHODS: [&str; 6] = [
+    "eth_newFilter",
+    "eth_newBlockFilter",
+    "eth_newPendingTransactionFilter",
+    "eth_getFilterChanges",
+    "eth_getFilterLogs",
+    "eth_uninstallFilter",
+];
+
+/// Spaces out the requests sent to an endpoint to stay under its rate limit.
+#[derive(Debug)]
+struct RateLimiter {
+    period: Duration,
+    next: Mutex<Instant>,
+}
+
+impl RateLimiter {
+    fn new(requests_per_second: u32) -> Self {
+        Self {
+            period: Duration::from_secs(1) / requests_per_second.max(1),
+            next: Mutex::new(Instant::now()),
+        }
+    }
+
+    /// Waits for the next request slot.
+    async fn acquire(&self) {
+        let slot = {
+            let mut next = self.next.lock().unwrap_or_else(|e| e.into_inner());
+            let slot = (*next).max(Instant::now());
+            *next = slot + self.period;
+            slot
+        };
+        time::sleep_until(slot).await;
+    }
+}
+
+#[derive(Debug)]
+struct Endpoint {
+    url: String,
+    priority: u32,
+    transport: Http<Client>,
+    rate_limiter: Option<RateLimiter>,
+    healthy: AtomicBool,
+}
+
+impl Endpoint {
+    fn is_healthy(&self) -> bool {
+        self.healthy.load(Ordering::Relaxed)
+    }
+
+    fn set_healthy(&self, healthy: bool) {
+        if self.healthy.swap(healthy, Ordering::Relaxed) != healthy {
+            match healthy {
+                true => debug!(url = %self.url, "RPC endpoint is healthy again"),
+                false => warn!(url = %self.url, "RPC endpoint is unhealthy"),
+            }
+        }
+    }
+
+    /// Sends a request to this endpoint only, failing when it does not answer in time.
+    async fn send(&self, request: RequestPacket) -> Result<ResponsePacket, TransportError> {
+        if let Some(rate_limiter) = &self.rate_limiter {
+            rate_limiter.acquire().await;
+        }
+        let mut transport = self.transport.clone();
+        time::timeout(REQUEST_TIMEOUT, transport.call(request))
+            .await
+            .unwrap_or_else(|_| Err(TransportErrorKind::custom_str("request timed out")))
+    }
+
+    async fn block_number(&self) -> Option<u64> {
+        if let Some(rate_limiter) = &self.rate_limiter {
+            rate_limiter.acquire().await;
+        }
+        let client = RpcClient::new(self.transport.clone(), false);
+        let request = client.request_noparams::<U64>("eth_blockNumber");
+        match time::timeout(REQUEST_TIMEOUT, request).await {
+            Ok(Ok(head)) => Some(head.to()),
+            Ok(Err(e)) => {
+                debug!(?e, url = %self.url, "Error checking RPC endpoint head");
+                None
+            }
+            Err(_) => None,
+        }
+    }
+}
+
+/// [`FailoverTransport`] is an alloy transport over the RPC endpoints of a chain.
+///
+/// Filters only exist on the node that created them, so filter requests are neither rotated
+/// nor hedged.
+#[derive(Clone, Debug)]
+pub(crate) struct FailoverTransport {
+    /// Endpoints sorted by priority
+    endpoints: Arc<Vec<Endpoint>>,
+    /// Rotates requests between the healthy endpoints of the best priority
+    next: Arc<AtomicUsize>,
+    /// Delay after which a pending request is also sent to the next endpoint
+    hedge_delay: Option<Duration>,
+}
+
+impl FailoverTransport {
+    pub(crate) fn new(endpoints: &[RpcEndpoint]) -> Result<Self, OrchestratorError> {
+        if endpoints.is_empty() {
+            return Err(OrchestratorError::InvalidUrl(
+                "at least one RPC endpoint is required".to_string(),
+            ));
+        }
+
+        let mut endpoints = endpoints
+            .iter()
+            .map(|endpoint| {
+                let url = endpoint
+                    .url()
+                    .parse::<reqwest::Url>()
+                    .map_err(|e| OrchestratorError::InvalidUrl(e.to_string()))?;
+                Ok(Endpoint {
+                    url: endpoint.url().to_string(),
+                    priority: endpoint.priority(),
+                    transport: Http::new(url),
+                    rate_limiter: endpoint.rate_limit().map(RateLimiter::new),
+                    healthy: AtomicBool::new(true),
+                })
+            })
+            .collect::<Result<Vec<_>, OrchestratorError>>()?;
+        endpoints.sort_by_key(|endpoint| endpoint.priority);
+
+        Ok(Self {
+            endpoints: Arc::new(endpoints),
+            next: Arc::new(AtomicUsize::new(0)),
+            hedge_delay: None,
+        })
+    }
+
+    /// Also sends requests that did not complete within `delay` to the next endpoint.
+    pub(crate) fn with_hedge_delay(mut self, delay: Duration) -> Self {
+        self.hedge_delay = Some(delay);
+        self
+    }
+
+    /// Checks the head of every endpoint. Endpoints that do not answer or lag more than
+    /// [`MAX_HEAD_LAG`] blocks behind the highest head are unhealthy until a later check.
+    pub(crate) async fn check_health(&self) {
+        let heads = future::join_all(
+            self.endpoints
+                .iter()
+                .map(|endpoint| endpoint.block_number()),
+        )
+        .await;
+        let highest = heads.iter().flatten().max().copied();
+
+        for (endpoint, head) in self.endpoints.iter().zip(heads) {
+            let healthy = match (head, highest) {
+                (Some(head), Some(highest)) => head.saturating_add(MAX_HEAD_LAG) >= highest,
+                _ => false,
+            };
+            endpoint.set_healthy(healthy);
+        }
+    }
+
+    /// Order in which the endpoints are tried: healthy endpoints by priority, requests rotating
+    /// between those of the best priority unless `stateful`, then unhealthy endpoints as a last
+    /// resort.
+    fn candidates(&self, stateful: bool) -> Vec<usize> {
+        let (mut healthy, unhealthy): (Vec<usize>, Vec<usize>) =
+            (0..self.endpoints.len()).partition(|&index| self.endpoints[index].is_healthy());
+
+        if !stateful {
+            let best = healthy.first().map(|&index| self.endpoints[index].priority);
+            let tier = healthy
+                .iter()
+                .take_while(|&&index| Some(self.endpoints[index].priority) == best)
+                .count();
+            if tier > 1 {
+                let offset = self.next.fetch_add(1, Ordering::Relaxed) % tier;
+                healthy[..tier].rotate_left(offset);
+            }
+        }
+
+        healthy.extend(unhealthy);
+        healthy
+    }
+
+    async fn dispatch(self, request: RequestPacket) -> Result<ResponsePacket, TransportError> {
+        let stateful = is_stateful(&request);
+        let hedge_delay = self.hedge_delay.filter(|_| !stateful);
+        let mut candidates = self.candidates(stateful).into_iter();
+
+        let send = |index: usize| {
+            let endpoint = &self.endpoints[index];
+            let request = request.clone();
+            async move { (index, endpoint.send(request).await) }
+        };
+
+        let mut in_flight = FuturesUnordered::new();
+        in_flight.extend(candidates.next().map(send));
+        let mut last_error = TransportErrorKind::custom_str("no RPC endpoint available");
+
+        loop {
+            let next = match hedge_delay.filter(|_| candidates.len() > 0) {
+                Some(delay) => match time::timeout(delay, in_flight.next()).await {
+                    Ok(next) => next,
+                    Err(_) => {
+                        in_flight.extend(candidates.next().map(send));
+                        continue;
+                    }
+                },
+                None => in_flight.next().await,
+            };
+            let Some((index, result)) = next else {
+                return Err(last_error);
+            };
+
+            match result {
+                Ok(response) => return Ok(response),
+                Err(e) => {
+                    debug!(?e, url = %self.endpoints[index].url, "RPC request failed");
+                    self.endpoints[index].set_healthy(false);
+                    last_error = e;
+                    in_flight.extend(candidates.next().map(send));
+                }
+            }
+        }
+    }
+}
+
+fn is_stateful(request: &RequestPacket) -> bool {
+    let is_stateful = |method: &str| STATEFUL_METHODS.contains(&method);
+    match request {
+        RequestPacket::Single(request) => is_stateful(request.method()),
+        RequestPacket::Batch(requests) => {
+            requests.iter().any(|request| is_stateful(request.method()))
+        }
+    }
+}
+
+impl Service<RequestPacket> for FailoverTransport {
+    type Response = ResponsePacket;
+    type Error = TransportError;
+    type Future = TransportFut<'static>;
+
+    fn poll_ready(&mut self, _cx: &mut Context<'_>) -> Poll<Result<(), Self::Error>> {
+        Poll::Ready(Ok(()))
+    }
+
+    fn call(&mut self, request: RequestPacket) -> Self::Future {
+        Box::pin(self.clone().dispatch(request))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::utils::test_utils::serve_json_rpc;
+    use serde_json::json;
+    use std::sync::atomic::AtomicU64;
+
+    /// Serves `eth_blockNumber` with the head returned by `head`, counting the requests it gets.
+    async fn serve_head(head: Arc<AtomicU64>, requests: Arc<AtomicUsize>) -> String {
+        serve_json_rpc(move |method, _| {
+            requests.fetch_add(1, Ordering::Relaxed);
+            match method {
+                "eth_blockNumber" | "eth_getFilterChanges" => {
+                    Ok(json!(U64::from(head.load(Ordering::Relaxed))))
+                }
+                _ => Err(json!({ "code": -32601, "message": "method not found" })),
+            }
+        })
+        .await
+    }
+
+    /// Url of a local port that accepts connections but never answers.
+    async fn serve_nothing() -> String {
+        let listener = tokio::net::TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let url = format!("http://{}", listener.local_addr().unwrap());
+        tokio::spawn(async move {
+            let mut sockets = Vec::new();
+            while let Ok((socket, _)) = listener.accept().await {
+                sockets.push(socket);
+            }
+        });
+        url
+    }
+
+    /// Url of a local port that refuses connections.
+    async fn refused_url() -> String {
+        let listener = tokio::net::TcpListener::bind("127.0.0.1:0").await.unwrap();
+        format!("http://{}", listener.local_addr().unwrap())
+    }
+
+    async fn block_number(transport: &FailoverTransport) -> u64 {
+        RpcClient::new(transport.clone(), false)
+            .request_noparams::<U64>("eth_blockNumber")
+            .await
+            .unwrap()
+            .to()
+    }
+
+    #[tokio::test]
+    async fn test_fails_over_to_next_endpoint() {
+        let head = Arc::new(AtomicU64::new(100));
+        let backup = serve_head(head, Arc::default()).await;
+        let transport = FailoverTransport::new(&[
+            RpcEndpoint::new(&backup, 1),
+            RpcEndpoint::new(&refused_url().await, 0),
+        ])
+        .unwrap();
+        assert_eq!(transport.candidates(false), vec![0, 1]);
+
+        assert_eq!(block_number(&transport).await, 100);
+        // The failing endpoint is skipped until it is healthy again
+        assert_eq!(transport.candidates(false), vec![1, 0]);
+    }
+
+    #[tokio::test]
+    async fn test_load_balances_endpoints_of_the_same_priority() {
+        let head = Arc::new(AtomicU64::new(100));
+        let requests: [Arc<AtomicUsize>; 3] = Default::default();
+        let mut endpoints = Vec::new();
+        for (requests, priority) in requests.iter().zip([0, 0, 1]) {
+            let url = serve_head(head.clone(), requests.clone()).await;
+            endpoints.push(RpcEndpoint::new(&url, priority));
+        }
+        let transport = FailoverTransport::new(&endpoints).unwrap();
+
+        for _ in 0..10 {
+            block_number(&transport).await;
+        }
+        let counts = requests
+            .each_ref()
+            .map(|requests| requests.load(Ordering::Relaxed));
+        assert_eq!(counts, [5, 5, 0]);
+
+        // Filters live on a single node
+        for _ in 0..10 {
+            RpcClient::new(transport.clone(), false)
+                .request::<_, U64>("eth_getFilterChanges", (U64::from(1),))
+                .await
+                .unwrap();
+        }
+        let filter_requests = requests
+            .each_ref()
+            .map(|requests| requests.load(Ordering::Relaxed))
+            .into_iter()
+            .zip(counts)
+            .map(|(total, balanced)| total - balanced)
+            .filter(|&count| count > 0)
+            .collect::<Vec<_>>();
+        assert_eq!(filter_requests, vec![10]);
+    }
+
+    #[tokio::test]
+    async fn test_health_check_detects_stalled_heads() {
+        let stalled = Arc::new(AtomicU64::new(100));
+        let synced = Arc::new(AtomicU64::new(100 + MAX_HEAD_LAG + 1));
+        let transport = FailoverTransport::new(&[
+            RpcEndpoint::new(&serve_head(stalled.clone(), Arc::default()).await, 0),
+            RpcEndpoint::new(&serve_head(synced.clone(), Arc::default()).await, 1),
+            RpcEndpoint::new(&refused_url().await, 0),
+        ])
+        .unwrap();
+
+        transport.check_health().await;
+        assert_eq!(transport.candidates(false), vec![2, 0, 1]);
+        assert_eq!(block_number(&transport).await, 100 + MAX_HEAD_LAG + 1);
+
+        // Caught up
+        stalled.store(100 + MAX_HEAD_LAG + 1, Ordering::Relaxed);
+        transport.check_health().await;
+        assert_eq!(transport.candidates(true), vec![0, 2, 1]);
+    }
+
+    #[tokio::test]
+    async fn test_rate_limits_endpoints() {
+        let head = Arc::new(AtomicU64::new(100));
+        let requests = Arc::new(AtomicUsize::new(0));
+        let url = serve_head(head, requests.clone()).await;
+        let transport =
+            FailoverTransport::new(&[RpcEndpoint::new(&url, 0).with_rate_limit(20)]).unwrap();
+
+        let start = Instant::now();
+        future::join_all((0..5).map(|_| block_number(&transport))).await;
+        // The first request goes out right away, the others every 50ms
+        assert!(start.elapsed() >= Duration::from_millis(200));
+        assert_eq!(requests.load(Ordering::Relaxed), 5);
+    }
+
+    #[tokio::test]
+    async fn test_hedges_slow_requests() {
+        let head = Arc::new(AtomicU64::new(100));
+        let transport = FailoverTransport::new(&[
+            RpcEndpoint::new(&serve_nothing().await, 0),
+            RpcEndpoint::new(&serve_head(head, Arc::default()).await, 1),
+        ])
+        .unwrap()
+        .with_hedge_delay(Duration::from_millis(50));
+
+        let head = time::timeout(Duration::from_secs(1), block_number(&transport)).await;
+        assert_eq!(head.unwrap(), 100);
+    }
+}
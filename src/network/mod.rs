@@ -1,3 +1,5 @@
 pub(crate) mod ethereum;
+pub(crate) mod failover;
 pub(crate) mod orchestrator;
 pub(crate) mod rpc;
+pub(crate) mod ws;
@@ -1,17 +1,150 @@
-use alloy_network::{AnyRpcBlock, AnyRpcTransaction};
+use alloy_consensus::BlockHeader;
+use alloy_network::{AnyNetwork, AnyRpcBlock, AnyRpcTransaction};
+use alloy_primitives::B256;
+use alloy_provider::Provider;
+use alloy_rpc_types::{BlockId, BlockTransactionsKind, Log};
+use alloy_transport::{Transport, TransportError};
 use async_trait::async_trait;
+use std::{
+    collections::VecDeque,
+    time::{Duration, SystemTime, UNIX_EPOCH},
+};
 use thiserror::Error;
 use tokio::sync::mpsc::Receiver;
 
+use crate::ingest::reorg::MAX_REORG_DEPTH;
+
+/// Maximum number of transactions or blocks fetched concurrently from a node when only their
+/// hashes were reported.
+pub(crate) const MAX_CONCURRENT_FETCHES: usize = 16;
+
+/// Most recent blocks delivered by an orchestrator, oldest first. Used to deliver every block of
+/// the canonical chain after its parent, including the blocks of a fork replacing delivered ones.
+#[derive(Debug, Default)]
+pub(crate) struct DeliveredBlocks {
+    blocks: VecDeque<(u64, B256)>,
+}
+
+impl DeliveredBlocks {
+    /// Number of the last delivered block.
+    pub(crate) fn last(&self) -> Option<u64> {
+        self.blocks.back().map(|(number, _)| *number)
+    }
+
+    /// Whether a block with this hash was delivered.
+    pub(crate) fn contains(&self, hash: &B256) -> bool {
+        self.blocks.iter().any(|(_, delivered)| delivered == hash)
+    }
+
+    /// Whether a block builds on top of a delivered block. Blocks are also considered linked when
+    /// nothing was delivered yet, or when their parent is older than every delivered block.
+    fn links(&self, number: u64, parent_hash: &B256) -> bool {
+        match self.blocks.front() {
+            None => true,
+            Some((oldest, _)) if number <= *oldest => true,
+            Some(_) => self
+                .blocks
+                .iter()
+                .any(|(delivered, hash)| delivered + 1 == number && hash == parent_hash),
+        }
+    }
+
+    /// Records a delivered block, forgetting the blocks at or above its height it replaces.
+    pub(crate) fn record(&mut self, number: u64, hash: B256) {
+        while self
+            .blocks
+            .back()
+            .is_some_and(|(delivered, _)| *delivered >= number)
+        {
+            self.blocks.pop_back();
+        }
+        self.blocks.push_back((number, hash));
+        while self.blocks.len() > MAX_REORG_DEPTH {
+            self.blocks.pop_front();
+        }
+    }
+}
+
+/// Returns `block` preceded by its ancestors that were never delivered, oldest first. Ancestors
+/// are fetched back by parent hash until one builds on top of a delivered block, i.e. up to the
+/// common ancestor of a fork, or to the last delivered block after a gap.
+pub(crate) async fn with_undelivered_ancestors<T, P>(
+    provider: &P,
+    delivered: &DeliveredBlocks,
+    block: AnyRpcBlock,
+) -> Result<Vec<AnyRpcBlock>, TransportError>
+where
+    T: Transport + Clone,
+    P: Provider<T, AnyNetwork>,
+{
+    let mut blocks = vec![block];
+    while let Some(oldest) = blocks.last().map(|block| &block.header) {
+        if blocks.len() > MAX_REORG_DEPTH || delivered.links(oldest.number(), &oldest.parent_hash())
+        {
+            break;
+        }
+        let parent = BlockId::Hash(oldest.parent_hash().into());
+        match provider
+            .get_block(parent, BlockTransactionsKind::Full)
+            .await?
+        {
+            Some(parent) => blocks.push(parent),
+            None => break,
+        }
+    }
+
+    blocks.reverse();
+    Ok(blocks)
+}
+
 #[derive(Debug, PartialEq, Clone)]
 pub enum ChainData {
     AnyRPCNetwork(AnyRPCNetwork),
 }
 
 #[derive(Debug, PartialEq, Clone)]
+#[allow(clippy::large_enum_variant)]
 pub enum AnyRPCNetwork {
     Block(AnyRpcBlock),
-    TransactionPool(AnyRpcTransaction),
+    TransactionPool(PendingTransaction),
+    /// Hashes of transactions that left the node's pool, either included or dropped
+    TransactionPoolRemovals(Vec<B256>),
+    Logs(Vec<Log>),
+}
+
+/// A transaction from the mempool along with when it was first seen
+#[derive(Debug, PartialEq, Eq, Clone)]
+pub struct PendingTransaction {
+    /// The pending transaction
+    pub transaction: AnyRpcTransaction,
+    /// When the transaction was first received from the node
+    pub first_seen: SystemTime,
+    /// Hash of the pending transaction with the same sender and nonce it replaced
+    pub replaces: Option<B256>,
+}
+
+impl PendingTransaction {
+    /// Creates a [`PendingTransaction`] first seen now
+    pub(crate) fn new(transaction: AnyRpcTransaction) -> Self {
+        Self {
+            transaction,
+            first_seen: SystemTime::now(),
+            replaces: None,
+        }
+    }
+
+    /// Unix timestamp, in seconds, of when the transaction was first seen
+    pub fn timestamp(&self) -> u64 {
+        self.first_seen
+            .duration_since(UNIX_EPOCH)
+            .unwrap_or_default()
+            .as_secs()
+    }
+
+    /// How long the transaction has been pending since it was first seen
+    pub fn age(&self) -> Duration {
+        self.first_seen.elapsed().unwrap_or_default()
+    }
 }
 
 #[derive(Error, Debug)]
@@ -19,11 +152,14 @@ pub enum OrchestratorError {
     #[error("Orchestrator '{name}' is already running")]
     AlreadyRunning { name: String },
 
-    #[error("Failed to create transaction filter: {0}")]
-    FilterCreation(String),
-
     #[error("Invalid RPC URL: {0}")]
     InvalidUrl(String),
+
+    #[error("Failed to connect: {0}")]
+    Connection(String),
+
+    #[error("Failed to subscribe: {0}")]
+    Subscription(String),
 }
 
 /// [`ChainOrchestrator`] Orchestrates the lifecycle of chain data polling and retrieval.
@@ -33,7 +169,7 @@ pub enum OrchestratorError {
 #[async_trait]
 pub trait ChainOrchestrator: Send + Sync {
     /// Starts the orchestrator and returns a receiver for chain data events.
-    /// The receiver will stream Block or TransactionPool updates.
+    /// The receiver will stream Block, TransactionPool or Logs updates.
     async fn start(&mut self) -> Result<Receiver<ChainData>, OrchestratorError>;
 
     /// Gracefully stops the orchestrator and cleans up any resources.
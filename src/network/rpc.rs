@@ -1,7 +1,9 @@
 //! RPC connection to the Ethereum network with alloy
 //! Ref: https://alloy.rs/building-with-alloy/connecting-to-a-blockchain/setting-up-a-provider
 use std::{
+    collections::{HashSet, VecDeque},
     future::Future,
+    ops::RangeInclusive,
     pin::Pin,
     sync::{
         atomic::{AtomicBool, Ordering},
@@ -11,51 +13,209 @@ use std::{
     time::Duration,
 };
 
-use alloy_network::AnyNetwork;
-use alloy_primitives::U256;
+use alloy_network::{AnyNetwork, AnyRpcTransaction};
+use alloy_primitives::{B256, U256};
 use alloy_provider::{Provider, ProviderBuilder, RootProvider};
-use alloy_rpc_types::{BlockId, BlockNumberOrTag, BlockTransactionsKind};
-use alloy_transport_http::Http;
+use alloy_rpc_client::RpcClient;
+use alloy_rpc_types::{BlockId, BlockNumberOrTag, BlockTransactionsKind, Filter};
+use alloy_rpc_types_txpool::TxpoolContent;
+use alloy_transport::TransportError;
 use async_trait::async_trait;
-use futures::{Stream, StreamExt};
+use futures::{stream, Stream, StreamExt};
 use pin_project_lite::pin_project;
-use reqwest::Client;
 use tokio::{
     sync::mpsc::{self, Receiver},
     task::JoinHandle,
     time,
 };
-use tracing::{debug, error};
-
-use crate::network::orchestrator::{
-    AnyRPCNetwork, ChainData, ChainOrchestrator, OrchestratorError,
+use tracing::{debug, error, warn};
+
+use crate::{
+    config::{Backoff, MempoolSource, RpcEndpoint},
+    network::failover::{FailoverTransport, HEALTH_CHECK_INTERVAL},
+    network::orchestrator::{
+        with_undelivered_ancestors, AnyRPCNetwork, ChainData, ChainOrchestrator, DeliveredBlocks,
+        OrchestratorError, PendingTransaction, MAX_CONCURRENT_FETCHES,
+    },
 };
 
+/// Maximum number of blocks fetched in a single poll when catching up with the head.
+const MAX_BLOCKS_PER_POLL: u64 = 32;
+
+type RpcProvider = RootProvider<FailoverTransport, AnyNetwork>;
+
+fn provider(transport: FailoverTransport) -> RpcProvider {
+    ProviderBuilder::new()
+        .network::<AnyNetwork>()
+        .on_client(RpcClient::new(transport, false))
+}
+
+/// Polling schedule of a stream: polls every interval, backing off exponentially after failed
+/// polls until a poll succeeds.
+struct PollSchedule {
+    interval: time::Interval,
+    backoff: Backoff,
+    failures: u32,
+}
+
+impl PollSchedule {
+    fn new(poll_interval: Duration, backoff: Backoff) -> Self {
+        Self {
+            interval: time::interval(poll_interval),
+            backoff,
+            failures: 0,
+        }
+    }
+
+    fn poll_tick(&mut self, cx: &mut Context<'_>) -> Poll<()> {
+        self.interval.poll_tick(cx).map(|_| ())
+    }
+
+    /// Records the outcome of a poll, the next poll is delayed after failures.
+    fn record(&mut self, succeeded: bool) {
+        if succeeded {
+            self.failures = 0;
+            return;
+        }
+
+        self.failures = self.failures.saturating_add(1);
+        let delay = self.backoff.delay(self.failures);
+        debug!(?delay, failures = self.failures, "Backing off RPC polling");
+        self.interval.reset_after(delay);
+    }
+}
+
+/// Whether the node no longer knows a filter, e.g. it expired or the node restarted.
+fn is_filter_not_found(error: &TransportError) -> bool {
+    error.as_error_resp().is_some_and(|payload| {
+        let message = payload.message.to_lowercase();
+        message.contains("filter not found")
+            || (message.contains("filter") && message.contains("does not exist"))
+    })
+}
+
 // [`BlockStream`] is a self-contained stream that fetches block data from an external source.
 // It operates only when polled, which is preferable to manual busy-polling as it leverages
 // tokio's runtime scheduler to handle the polling logic efficiently.
+//
+// Every block is followed by the logs it emitted (`eth_getLogs` by block hash), if any.
+//
+// The stream remembers the last blocks it delivered, when the head moves by more than one block
+// between two polls every intermediate block is fetched and emitted in order so that no block
+// is skipped on chains with block times shorter than the poll interval. A block that does not
+// build on top of the last delivered one starts a fork: its ancestors are fetched back by hash
+// to the common ancestor and emitted first, so replacement blocks at heights that were already
+// delivered are not skipped either.
 pin_project! {
     struct BlockStream {
         #[pin]
-        provider: Arc<RootProvider<Http<Client>, AnyNetwork> >,
-        interval: time::Interval,
-        future: Option<Pin<Box<dyn Future<Output = Option<ChainData>> + Send>>>,
+        provider: Arc<RpcProvider>,
+        schedule: PollSchedule,
+        delivered: DeliveredBlocks,
+        buffered: VecDeque<ChainData>,
+        future: Option<Pin<Box<dyn Future<Output = (DeliveredBlocks, Vec<ChainData>, bool)> + Send>>>,
+    }
+}
+
+impl BlockStream {
+    fn new(provider: Arc<RpcProvider>, schedule: PollSchedule) -> Self {
+        Self {
+            provider,
+            schedule,
+            delivered: DeliveredBlocks::default(),
+            buffered: VecDeque::new(),
+            future: None,
+        }
+    }
+
+    /// Fetches every block after the last delivered one up to the current head (bounded by
+    /// [`MAX_BLOCKS_PER_POLL`]) in order, each preceded by the blocks of its fork that were never
+    /// delivered and followed by its logs. Returns the delivered blocks alongside the data and
+    /// whether every request succeeded, fetching stops at the first failure so it is retried on
+    /// the next poll.
+    async fn fetch_blocks(
+        provider: Arc<RpcProvider>,
+        mut delivered: DeliveredBlocks,
+    ) -> (DeliveredBlocks, Vec<ChainData>, bool) {
+        let head = match provider.get_block_number().await {
+            Ok(head) => head,
+            Err(e) => {
+                error!(?e, "Error polling block number");
+                return (delivered, Vec::new(), false);
+            }
+        };
+
+        let mut data = Vec::new();
+        for number in next_block_range(delivered.last(), head, MAX_BLOCKS_PER_POLL) {
+            let block = match provider
+                .get_block(
+                    BlockId::Number(BlockNumberOrTag::Number(number)),
+                    BlockTransactionsKind::Full,
+                )
+                .await
+            {
+                Ok(Some(block)) => block,
+                Ok(None) => break,
+                Err(e) => {
+                    error!(?e, number, "Error polling blocks");
+                    return (delivered, data, false);
+                }
+            };
+
+            let blocks = match with_undelivered_ancestors(&*provider, &delivered, block).await {
+                Ok(blocks) => blocks,
+                Err(e) => {
+                    error!(?e, number, "Error fetching forked blocks");
+                    return (delivered, data, false);
+                }
+            };
+            for block in blocks {
+                let filter = Filter::new().at_block_hash(block.header.hash);
+                let logs = match provider.get_logs(&filter).await {
+                    Ok(logs) => logs,
+                    Err(e) => {
+                        error!(?e, number = block.header.number, "Error polling logs");
+                        return (delivered, data, false);
+                    }
+                };
+
+                delivered.record(block.header.number, block.header.hash);
+                data.push(ChainData::AnyRPCNetwork(AnyRPCNetwork::Block(block)));
+                if !logs.is_empty() {
+                    data.push(ChainData::AnyRPCNetwork(AnyRPCNetwork::Logs(logs)));
+                }
+            }
+        }
+
+        (delivered, data, true)
     }
 }
 
+/// Returns the range of block numbers to fetch given the last delivered block and the current head.
+/// Without a previously delivered block only the head is fetched.
+fn next_block_range(last_block: Option<u64>, head: u64, max_blocks: u64) -> RangeInclusive<u64> {
+    let start = last_block.map_or(head, |last| last.saturating_add(1));
+    let end = head.min(start.saturating_add(max_blocks.saturating_sub(1)));
+    start..=end
+}
+
 impl Stream for BlockStream {
     type Item = ChainData;
 
     fn poll_next(mut self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Option<Self::Item>> {
         let this = self.as_mut().project();
 
+        if let Some(block) = this.buffered.pop_front() {
+            return Poll::Ready(Some(block));
+        }
+
         if let Some(fut) = this.future {
             match fut.as_mut().poll(cx) {
-                Poll::Ready(item) => {
+                Poll::Ready((delivered, blocks, succeeded)) => {
                     *this.future = None;
-                    if item.is_some() {
-                        return Poll::Ready(item);
-                    }
+                    *this.delivered = delivered;
+                    this.schedule.record(succeeded);
+                    this.buffered.extend(blocks);
                     // continue polling
                     return self.poll_next(cx);
                 }
@@ -63,31 +223,12 @@ impl Stream for BlockStream {
             }
         }
 
-        match this.interval.poll_tick(cx) {
+        match this.schedule.poll_tick(cx) {
             Poll::Ready(_) => {
                 let provider = this.provider.clone();
+                let delivered = std::mem::take(this.delivered);
 
-                *this.future = Some(Box::pin(async move {
-                    match provider
-                        .get_block(
-                            BlockId::Number(BlockNumberOrTag::Latest),
-                            BlockTransactionsKind::Full,
-                        )
-                        .await
-                    {
-                        Ok(block) => {
-                            if let Some(block_data) = block {
-                                return Some(ChainData::AnyRPCNetwork(AnyRPCNetwork::Block(
-                                    block_data,
-                                )));
-                            }
-                        }
-                        Err(e) => {
-                            error!(?e, "Error polling blocks");
-                        }
-                    }
-                    None
-                }));
+                *this.future = Some(Box::pin(Self::fetch_blocks(provider, delivered)));
                 self.poll_next(cx)
             }
             Poll::Pending => Poll::Pending,
@@ -96,31 +237,99 @@ impl Stream for BlockStream {
 }
 
 // [`PendingTxPoolStream`] is a self-contained stream that has all it needs to get pending transaction data
+//
+// Every poll fetches all the transactions whose hashes the pending transaction filter reported
+// since the previous poll, and emits them one by one.
+//
+// The filter is created on the first poll, and created again whenever the node forgets it (e.g.
+// it expired or the node restarted).
+//
+// The filter does not report a hash twice, so transactions that could not be fetched are kept and
+// fetched again on the next poll.
 pin_project! {
     struct PendingTxPoolStream {
-        provider: Arc<RootProvider<Http<Client>, AnyNetwork> >,
-        filter_id: U256,
-        interval: time::Interval,
-        future: Option<Pin<Box<dyn Future<Output = Option<ChainData>> + Send>>>,
+        provider: Arc<RpcProvider>,
+        filter_id: Option<U256>,
+        unfetched: Vec<B256>,
+        schedule: PollSchedule,
+        buffered: VecDeque<ChainData>,
+        future: Option<Pin<Box<dyn Future<Output = TxPoolPoll> + Send>>>,
     }
 }
 
-impl PendingTxPoolStream {
-    async fn new(
-        provider: Arc<RootProvider<Http<Client>, AnyNetwork>>,
-        poll_interval: Duration,
-    ) -> Result<Self, OrchestratorError> {
-        let filter_id = provider
-            .new_pending_transactions_filter(false)
-            .await
-            .map_err(|e| OrchestratorError::FilterCreation(e.to_string()))?;
+/// Filter to poll next, hashes to fetch again, fetched transactions and whether polling the
+/// filter succeeded.
+type TxPoolPoll = (Option<U256>, Vec<B256>, Vec<ChainData>, bool);
 
-        Ok(Self {
-            interval: time::interval(poll_interval),
+impl PendingTxPoolStream {
+    fn new(provider: Arc<RpcProvider>, schedule: PollSchedule) -> Self {
+        Self {
+            schedule,
+            buffered: VecDeque::new(),
             future: None,
             provider,
-            filter_id,
-        })
+            filter_id: None,
+            unfetched: Vec::new(),
+        }
+    }
+
+    /// Fetches the `unfetched` transactions and the ones reported by the filter, at most
+    /// [`MAX_CONCURRENT_FETCHES`] at a time. Transactions that already left the pool are skipped,
+    /// the ones whose request failed are returned to be fetched again.
+    async fn fetch_transactions(
+        provider: Arc<RpcProvider>,
+        filter_id: Option<U256>,
+        unfetched: Vec<B256>,
+    ) -> TxPoolPoll {
+        let filter_id = match filter_id {
+            Some(filter_id) => filter_id,
+            None => match provider.new_pending_transactions_filter(false).await {
+                Ok(filter_id) => filter_id,
+                Err(e) => {
+                    error!(?e, "Error creating pending transaction filter");
+                    return (None, unfetched, Vec::new(), false);
+                }
+            },
+        };
+
+        let hashes = match provider.get_filter_changes::<B256>(filter_id).await {
+            Ok(hashes) => hashes,
+            Err(e) if is_filter_not_found(&e) => {
+                // Created again on the next poll
+                warn!(%filter_id, "Pending transaction filter not found");
+                return (None, unfetched, Vec::new(), true);
+            }
+            Err(e) => {
+                error!(?e, "Error polling pending transactions");
+                return (Some(filter_id), unfetched, Vec::new(), false);
+            }
+        };
+
+        let results = stream::iter(unfetched.into_iter().chain(hashes))
+            .map(|hash| {
+                let provider = provider.clone();
+                async move { (hash, provider.get_transaction_by_hash(hash).await) }
+            })
+            .buffered(MAX_CONCURRENT_FETCHES)
+            .collect::<Vec<_>>()
+            .await;
+
+        let mut unfetched = Vec::new();
+        let mut transactions = Vec::new();
+        for (hash, result) in results {
+            match result {
+                Ok(Some(tx)) => transactions.push(ChainData::AnyRPCNetwork(
+                    AnyRPCNetwork::TransactionPool(PendingTransaction::new(tx)),
+                )),
+                Ok(None) => {}
+                Err(e) => {
+                    debug!(?e, %hash, "Error fetching pending transaction, retrying on next poll");
+                    unfetched.push(hash);
+                }
+            }
+        }
+
+        (Some(filter_id), unfetched, transactions, true)
     }
 }
 
@@ -129,37 +338,153 @@ impl Stream for PendingTxPoolStream {
 
     fn poll_next(mut self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Option<Self::Item>> {
         let this = self.as_mut().project();
+
+        if let Some(tx) = this.buffered.pop_front() {
+            return Poll::Ready(Some(tx));
+        }
+
         if let Some(fut) = this.future {
             match fut.as_mut().poll(cx) {
-                Poll::Ready(item) => {
+                Poll::Ready((filter_id, unfetched, txs, succeeded)) => {
                     *this.future = None;
-                    if item.is_some() {
-                        return Poll::Ready(item);
-                    }
-                    // If no transaction was found, continue polling
+                    *this.filter_id = filter_id;
+                    *this.unfetched = unfetched;
+                    this.schedule.record(succeeded);
+                    this.buffered.extend(txs);
+                    // continue polling
                     return self.poll_next(cx);
                 }
                 Poll::Pending => return Poll::Pending,
             }
         }
 
-        match this.interval.poll_tick(cx) {
+        match this.schedule.poll_tick(cx) {
             Poll::Ready(_) => {
                 let provider = this.provider.clone();
                 let filter_id = *this.filter_id;
+                let unfetched = std::mem::take(this.unfetched);
 
-                *this.future = Some(Box::pin(async move {
-                    if let Ok(changes) = provider.get_filter_changes(filter_id).await {
-                        for tx_hash in changes {
-                            if let Ok(Some(tx)) = provider.get_transaction_by_hash(tx_hash).await {
-                                return Some(ChainData::AnyRPCNetwork(
-                                    AnyRPCNetwork::TransactionPool(tx),
-                                ));
-                            }
-                        }
-                    }
-                    None
-                }));
+                *this.future = Some(Box::pin(Self::fetch_transactions(
+                    provider, filter_id, unfetched,
+                )));
+                self.poll_next(cx)
+            }
+            Poll::Pending => Poll::Pending,
+        }
+    }
+}
+
+// [`TxPoolSnapshotStream`] is a self-contained stream that follows the node's transaction pool
+// through `txpool_content` snapshots, for nodes that do not support pending transaction filters.
+//
+// Every poll diffs the snapshot against the previous one: transactions that appeared are emitted
+// one by one, followed by the hashes of the transactions that left the pool.
+pin_project! {
+    struct TxPoolSnapshotStream {
+        provider: Arc<RpcProvider>,
+        schedule: PollSchedule,
+        known: HashSet<B256>,
+        buffered: VecDeque<ChainData>,
+        future: Option<Pin<Box<dyn Future<Output = (HashSet<B256>, Vec<ChainData>, bool)> + Send>>>,
+    }
+}
+
+impl TxPoolSnapshotStream {
+    fn new(provider: Arc<RpcProvider>, schedule: PollSchedule) -> Self {
+        Self {
+            provider,
+            schedule,
+            known: HashSet::new(),
+            buffered: VecDeque::new(),
+            future: None,
+        }
+    }
+
+    /// Fetches a snapshot of the pool and diffs it against the `known` transactions, the known
+    /// transactions are kept as is when the snapshot fails.
+    async fn fetch_snapshot(
+        provider: Arc<RpcProvider>,
+        known: HashSet<B256>,
+    ) -> (HashSet<B256>, Vec<ChainData>, bool) {
+        match provider
+            .client()
+            .request_noparams::<TxpoolContent<AnyRpcTransaction>>("txpool_content")
+            .await
+        {
+            Ok(content) => {
+                let (snapshot, changes) = diff_snapshot(&known, content);
+                (snapshot, changes, true)
+            }
+            Err(e) => {
+                error!(?e, "Error fetching txpool content");
+                (known, Vec::new(), false)
+            }
+        }
+    }
+}
+
+/// Diffs a `txpool_content` snapshot against the `known` transactions of the previous one,
+/// returns the transactions of the snapshot along with the additions and removals.
+fn diff_snapshot(
+    known: &HashSet<B256>,
+    content: TxpoolContent<AnyRpcTransaction>,
+) -> (HashSet<B256>, Vec<ChainData>) {
+    let mut snapshot = HashSet::new();
+    let mut changes = Vec::new();
+
+    for tx in [content.pending, content.queued]
+        .into_iter()
+        .flat_map(|pool| pool.into_values())
+        .flat_map(|txs| txs.into_values())
+    {
+        let hash = tx.info().hash.unwrap_or_default();
+        if snapshot.insert(hash) && !known.contains(&hash) {
+            changes.push(ChainData::AnyRPCNetwork(AnyRPCNetwork::TransactionPool(
+                PendingTransaction::new(tx),
+            )));
+        }
+    }
+
+    let removals: Vec<B256> = known.difference(&snapshot).copied().collect();
+    if !removals.is_empty() {
+        changes.push(ChainData::AnyRPCNetwork(
+            AnyRPCNetwork::TransactionPoolRemovals(removals),
+        ));
+    }
+
+    (snapshot, changes)
+}
+
+impl Stream for TxPoolSnapshotStream {
+    type Item = ChainData;
+
+    fn poll_next(mut self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Option<Self::Item>> {
+        let this = self.as_mut().project();
+
+        if let Some(data) = this.buffered.pop_front() {
+            return Poll::Ready(Some(data));
+        }
+
+        if let Some(fut) = this.future {
+            match fut.as_mut().poll(cx) {
+                Poll::Ready((known, changes, succeeded)) => {
+                    *this.future = None;
+                    *this.known = known;
+                    this.schedule.record(succeeded);
+                    this.buffered.extend(changes);
+                    // continue polling
+                    return self.poll_next(cx);
+                }
+                Poll::Pending => return Poll::Pending,
+            }
+        }
+
+        match this.schedule.poll_tick(cx) {
+            Poll::Ready(_) => {
+                let provider = this.provider.clone();
+                let known = std::mem::take(this.known);
+
+                *this.future = Some(Box::pin(Self::fetch_snapshot(provider, known)));
                 self.poll_next(cx)
             }
             Poll::Pending => Poll::Pending,
@@ -168,50 +493,81 @@ impl Stream for PendingTxPoolStream {
 }
 
 pub struct RpcOrchestrator {
-    provider: Arc<RootProvider<Http<Client>, AnyNetwork>>,
+    transport: FailoverTransport,
+    provider: Arc<RpcProvider>,
     poll_interval: Duration,
+    backoff: Backoff,
+    mempool_source: MempoolSource,
     is_running: Arc<AtomicBool>,
     name: String,
     block_task: Option<JoinHandle<()>>,
     tx_pool_task: Option<JoinHandle<()>>,
+    health_task: Option<JoinHandle<()>>,
 }
 
 impl RpcOrchestrator {
+    /// Creates an orchestrator polling the chain through `endpoints`, failing over between them.
     pub fn new(
         name: String,
-        rpc_url: String,
+        endpoints: &[RpcEndpoint],
         poll_interval: Duration,
     ) -> Result<Self, OrchestratorError> {
-        let parsed_rpc_url = rpc_url
-            .parse::<reqwest::Url>()
-            .map_err(|e| OrchestratorError::InvalidUrl(e.to_string()))?;
-
-        let provider: RootProvider<Http<Client>, AnyNetwork> = ProviderBuilder::new()
-            .network::<AnyNetwork>()
-            .on_http(parsed_rpc_url);
+        let transport = FailoverTransport::new(endpoints)?;
 
         Ok(Self {
-            provider: Arc::new(provider),
+            provider: Arc::new(provider(transport.clone())),
+            transport,
             poll_interval,
+            backoff: Backoff::default(),
+            mempool_source: MempoolSource::default(),
             is_running: Arc::new(AtomicBool::new(false)),
             name,
             block_task: None,
             tx_pool_task: None,
+            health_task: None,
         })
     }
 
+    /// Also sends requests that did not complete within `delay` to the next endpoint, using
+    /// whichever response comes first.
+    pub fn with_hedge_delay(mut self, delay: Duration) -> Self {
+        self.transport = self.transport.with_hedge_delay(delay);
+        self.provider = Arc::new(provider(self.transport.clone()));
+        self
+    }
+
+    /// Sets the backoff of polling after failed polls, defaults to [`Backoff::default`].
+    pub fn with_backoff(mut self, backoff: Backoff) -> Self {
+        self.backoff = backoff;
+        self
+    }
+
+    /// Sets where pending transactions come from, defaults to
+    /// [`MempoolSource::PendingTransactions`].
+    pub fn with_mempool_source(mut self, source: MempoolSource) -> Self {
+        self.mempool_source = source;
+        self
+    }
+
+    fn schedule(&self) -> PollSchedule {
+        PollSchedule::new(self.poll_interval, self.backoff)
+    }
+
     async fn block_stream(&self) -> BlockStream {
-        BlockStream {
-            provider: Arc::clone(&self.provider),
-            interval: time::interval(self.poll_interval),
-            future: None,
-        }
+        BlockStream::new(Arc::clone(&self.provider), self.schedule())
     }
 
-    async fn pending_tx_pool_stream(&self) -> PendingTxPoolStream {
-        let pending_tx_pool_stream =
-            PendingTxPoolStream::new(self.provider.clone(), self.poll_interval).await;
-        pending_tx_pool_stream.expect("unable to stream pending transaction")
+    async fn mempool_stream(&self) -> Pin<Box<dyn Stream<Item = ChainData> + Send>> {
+        match self.mempool_source {
+            MempoolSource::PendingTransactions => Box::pin(PendingTxPoolStream::new(
+                self.provider.clone(),
+                self.schedule(),
+            )),
+            MempoolSource::TxPool => Box::pin(TxPoolSnapshotStream::new(
+                self.provider.clone(),
+                self.schedule(),
+            )),
+        }
     }
 }
 
@@ -243,13 +599,13 @@ impl ChainOrchestrator for RpcOrchestrator {
         });
 
         // Start txpool polling stream
-        let mut pending_tx_stream = self.pending_tx_pool_stream().await;
+        let mut pending_tx_stream = self.mempool_stream().await;
         let pool_tx = tx.clone();
         let pool_is_running = self.is_running.clone();
 
         let pool_task = tokio::spawn(async move {
             while let Some(tx) = pending_tx_stream.next().await {
-                println!("new pending transaction: {tx:#?}");
+                debug!("new pending transaction: {tx:#?}");
                 if !pool_is_running.load(Ordering::Relaxed) {
                     break;
                 }
@@ -257,8 +613,19 @@ impl ChainOrchestrator for RpcOrchestrator {
             }
         });
 
+        // Start endpoint health checks
+        let transport = self.transport.clone();
+        let health_task = tokio::spawn(async move {
+            let mut interval = time::interval(HEALTH_CHECK_INTERVAL);
+            loop {
+                interval.tick().await;
+                transport.check_health().await;
+            }
+        });
+
         self.block_task = Some(block_task);
         self.tx_pool_task = Some(pool_task);
+        self.health_task = Some(health_task);
 
         Ok(rx)
     }
@@ -274,6 +641,10 @@ impl ChainOrchestrator for RpcOrchestrator {
             task.abort();
         }
 
+        if let Some(task) = &self.health_task {
+            task.abort();
+        }
+
         Ok(())
     }
 
@@ -281,3 +652,323 @@ impl ChainOrchestrator for RpcOrchestrator {
         &self.name
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::utils::test_utils::{generate_random_transaction, serve_json_rpc};
+    use alloy_network::{AnyHeader, AnyRpcBlock};
+    use alloy_primitives::U64;
+    use alloy_rpc_types::{Block, Header};
+    use futures::future;
+    use serde_json::{json, Value};
+    use std::{collections::HashMap, sync::atomic::AtomicUsize};
+
+    fn provider(url: &str) -> Arc<RpcProvider> {
+        let transport = FailoverTransport::new(&[RpcEndpoint::new(url, 0)]).unwrap();
+        Arc::new(super::provider(transport))
+    }
+
+    fn schedule() -> PollSchedule {
+        PollSchedule::new(Duration::from_millis(10), Backoff::default())
+    }
+
+    /// Waits for the next tick of `schedule`, returns how long it took.
+    async fn tick(schedule: &mut PollSchedule) -> Duration {
+        let start = time::Instant::now();
+        future::poll_fn(|cx| schedule.poll_tick(cx)).await;
+        start.elapsed()
+    }
+
+    #[tokio::test]
+    async fn test_pending_tx_pool_stream_recreates_forgotten_filter() {
+        let tx = generate_random_transaction(1);
+        let hash = tx.info().hash.unwrap();
+        let tx = serde_json::to_value(tx).unwrap();
+
+        // The node forgets the first filter, e.g. after a restart
+        let filters = AtomicUsize::new(0);
+        let polls = AtomicUsize::new(0);
+        let url = serve_json_rpc(move |method, params| match method {
+            "eth_newPendingTransactionFilter" => Ok(json!(U256::from(
+                filters.fetch_add(1, Ordering::Relaxed) + 1
+            ))),
+            "eth_getFilterChanges" if params[0] == json!(U256::from(1)) => {
+                Err(json!({ "code": -32000, "message": "filter not found" }))
+            }
+            "eth_getFilterChanges" if polls.fetch_add(1, Ordering::Relaxed) == 0 => {
+                Ok(json!([hash]))
+            }
+            "eth_getFilterChanges" => Ok(json!([])),
+            "eth_getTransactionByHash" => Ok(tx.clone()),
+            _ => Err(json!({ "code": -32601, "message": "method not found" })),
+        })
+        .await;
+
+        let mut stream = PendingTxPoolStream::new(provider(&url), schedule());
+        let received = time::timeout(Duration::from_secs(5), stream.next())
+            .await
+            .unwrap();
+        assert!(matches!(
+            received,
+            Some(ChainData::AnyRPCNetwork(AnyRPCNetwork::TransactionPool(pending)))
+                if pending.transaction.info().hash == Some(hash)
+        ));
+        assert_eq!(stream.filter_id, Some(U256::from(2)));
+    }
+
+    fn block(number: u64, hash: u8, parent_hash: u8) -> AnyRpcBlock {
+        AnyRpcBlock::new(Block {
+            header: Header {
+                hash: B256::repeat_byte(hash),
+                inner: AnyHeader {
+                    number,
+                    parent_hash: B256::repeat_byte(parent_hash),
+                    ..Default::default()
+                },
+                ..Default::default()
+            },
+            ..Default::default()
+        })
+    }
+
+    #[tokio::test]
+    async fn test_block_stream_emits_replacement_blocks() {
+        // Block 2 is replaced while block 3 is built, the stream only asks for blocks by number
+        // after the last one it delivered.
+        let blocks = [
+            block(1, 1, 0),
+            block(2, 2, 1),
+            block(2, 0x22, 1),
+            block(3, 0x33, 0x22),
+        ];
+        let by_hash: HashMap<B256, Value> = blocks
+            .iter()
+            .map(|block| (block.header.hash, serde_json::to_value(block).unwrap()))
+            .collect();
+        let heads = AtomicUsize::new(0);
+        let url = serve_json_rpc(move |method, params| match method {
+            "eth_blockNumber" => Ok(json!(U64::from(
+                (heads.fetch_add(1, Ordering::Relaxed) + 1).min(3)
+            ))),
+            "eth_getBlockByNumber" => {
+                let number: U64 = serde_json::from_value(params[0].clone()).unwrap();
+                let hash = match number.to::<u64>() {
+                    1 => B256::repeat_byte(1),
+                    2 if heads.load(Ordering::Relaxed) < 3 => B256::repeat_byte(2),
+                    2 => B256::repeat_byte(0x22),
+                    _ => B256::repeat_byte(0x33),
+                };
+                Ok(by_hash[&hash].clone())
+            }
+            "eth_getBlockByHash" => {
+                let hash: B256 = serde_json::from_value(params[0].clone()).unwrap();
+                Ok(by_hash.get(&hash).cloned().unwrap_or(Value::Null))
+            }
+            "eth_getLogs" => Ok(json!([])),
+            _ => Err(json!({ "code": -32601, "message": "method not found" })),
+        })
+        .await;
+
+        let stream = BlockStream::new(provider(&url), schedule());
+        let received: Vec<ChainData> =
+            time::timeout(Duration::from_secs(5), stream.take(4).collect())
+                .await
+                .unwrap();
+        let received: Vec<(u64, B256)> = received
+            .into_iter()
+            .map(|data| match data {
+                ChainData::AnyRPCNetwork(AnyRPCNetwork::Block(block)) => {
+                    (block.header.number, block.header.hash)
+                }
+                data => panic!("Expected block, got {data:?}"),
+            })
+            .collect();
+        assert_eq!(
+            received,
+            blocks.map(|block| (block.header.number, block.header.hash))
+        );
+    }
+
+    #[tokio::test]
+    async fn test_poll_schedule_backs_off_after_failures() {
+        let backoff = Backoff::new(Duration::from_millis(100), Duration::from_millis(250));
+        assert_eq!(
+            [1, 2, 3, 40].map(|failures| backoff.delay(failures)),
+            [100, 200, 250, 250].map(Duration::from_millis)
+        );
+
+        let mut schedule = PollSchedule::new(Duration::from_millis(10), backoff);
+        tick(&mut schedule).await;
+
+        schedule.record(false);
+        schedule.record(false);
+        assert!(tick(&mut schedule).await >= Duration::from_millis(200));
+
+        // Back to the poll interval
+        schedule.record(true);
+        assert!(tick(&mut schedule).await < Duration::from_millis(100));
+    }
+
+    #[tokio::test]
+    async fn test_pending_tx_pool_stream_emits_every_transaction() {
+        let txs: HashMap<B256, Value> = (0..20)
+            .map(|value| {
+                let tx = generate_random_transaction(value);
+                (tx.info().hash.unwrap(), serde_json::to_value(tx).unwrap())
+            })
+            .collect();
+        // A transaction that left the pool before it was fetched
+        let mut expected: Vec<B256> = txs.keys().copied().collect();
+        let mut hashes = expected.clone();
+        hashes.push(B256::repeat_byte(1));
+
+        let polls = AtomicUsize::new(0);
+        let url = serve_json_rpc(move |method, params| match method {
+            "eth_newPendingTransactionFilter" => Ok(json!("0x1")),
+            "eth_getFilterChanges" if polls.fetch_add(1, Ordering::Relaxed) == 0 => {
+                Ok(json!(hashes))
+            }
+            "eth_getFilterChanges" => Ok(json!([])),
+            "eth_getTransactionByHash" => {
+                let hash: B256 = serde_json::from_value(params[0].clone()).unwrap();
+                Ok(txs.get(&hash).cloned().unwrap_or(Value::Null))
+            }
+            _ => Err(json!({ "code": -32601, "message": "method not found" })),
+        })
+        .await;
+
+        let stream = PendingTxPoolStream::new(provider(&url), schedule());
+        let received: Vec<ChainData> =
+            time::timeout(Duration::from_secs(5), stream.take(20).collect())
+                .await
+                .unwrap();
+
+        let mut received: Vec<B256> = received
+            .into_iter()
+            .map(|data| match data {
+                ChainData::AnyRPCNetwork(AnyRPCNetwork::TransactionPool(tx)) => {
+                    tx.transaction.info().hash.unwrap()
+                }
+                data => panic!("Expected pending transaction, got {data:?}"),
+            })
+            .collect();
+        received.sort();
+        expected.sort();
+        assert_eq!(received, expected);
+    }
+
+    #[tokio::test]
+    async fn test_pending_tx_pool_stream_retries_failed_fetch() {
+        let tx = generate_random_transaction(1);
+        let hash = tx.info().hash.unwrap();
+        let tx = serde_json::to_value(tx).unwrap();
+
+        // The filter reports the hash once, and the first fetch fails
+        let polls = AtomicUsize::new(0);
+        let fetches = AtomicUsize::new(0);
+        let url = serve_json_rpc(move |method, _| match method {
+            "eth_newPendingTransactionFilter" => Ok(json!("0x1")),
+            "eth_getFilterChanges" if polls.fetch_add(1, Ordering::Relaxed) == 0 => {
+                Ok(json!([hash]))
+            }
+            "eth_getFilterChanges" => Ok(json!([])),
+            "eth_getTransactionByHash" if fetches.fetch_add(1, Ordering::Relaxed) == 0 => {
+                Err(json!({ "code": -32603, "message": "internal error" }))
+            }
+            "eth_getTransactionByHash" => Ok(tx.clone()),
+            _ => Err(json!({ "code": -32601, "message": "method not found" })),
+        })
+        .await;
+
+        let mut stream = PendingTxPoolStream::new(provider(&url), schedule());
+        let received = time::timeout(Duration::from_secs(5), stream.next())
+            .await
+            .unwrap();
+        assert!(matches!(
+            received,
+            Some(ChainData::AnyRPCNetwork(AnyRPCNetwork::TransactionPool(pending)))
+                if pending.transaction.info().hash == Some(hash)
+        ));
+        assert!(stream.unfetched.is_empty());
+    }
+
+    #[tokio::test]
+    async fn test_txpool_snapshot_stream_emits_additions_and_removals() {
+        let [a, b, c] = [1, 2, 3].map(generate_random_transaction);
+        let content = |txs: &[&AnyRpcTransaction]| {
+            let mut content = TxpoolContent::<AnyRpcTransaction>::default();
+            for (nonce, tx) in txs.iter().enumerate() {
+                content
+                    .pending
+                    .entry(tx.from)
+                    .or_default()
+                    .insert(nonce.to_string(), (*tx).clone());
+            }
+            serde_json::to_value(content).unwrap()
+        };
+        let first = content(&[&a, &b]);
+        let next = content(&[&b, &c]);
+        let hash = |tx: &AnyRpcTransaction| tx.info().hash.unwrap();
+        let expected = [hash(&a), hash(&b), hash(&c)];
+
+        let polls = AtomicUsize::new(0);
+        let url = serve_json_rpc(move |method, _| match method {
+            "txpool_content" if polls.fetch_add(1, Ordering::Relaxed) == 0 => Ok(first.clone()),
+            "txpool_content" => Ok(next.clone()),
+            _ => Err(json!({ "code": -32601, "message": "method not found" })),
+        })
+        .await;
+
+        let stream = TxPoolSnapshotStream::new(provider(&url), schedule());
+        let received: Vec<ChainData> =
+            time::timeout(Duration::from_secs(5), stream.take(4).collect())
+                .await
+                .unwrap();
+
+        let mut added: Vec<B256> = received[..3]
+            .iter()
+            .map(|data| match data {
+                ChainData::AnyRPCNetwork(AnyRPCNetwork::TransactionPool(tx)) => {
+                    hash(&tx.transaction)
+                }
+                data => panic!("Expected pending transaction, got {data:?}"),
+            })
+            .collect();
+        added[..2].sort();
+        let mut first_snapshot = [expected[0], expected[1]];
+        first_snapshot.sort();
+        assert_eq!(added[..2], first_snapshot);
+        assert_eq!(added[2], expected[2]);
+        assert!(matches!(
+            &received[3],
+            ChainData::AnyRPCNetwork(AnyRPCNetwork::TransactionPoolRemovals(hashes))
+                if hashes == &[expected[0]]
+        ));
+    }
+
+    #[test]
+    fn test_next_block_range_starts_at_head() {
+        assert_eq!(next_block_range(None, 100, MAX_BLOCKS_PER_POLL), 100..=100);
+    }
+
+    #[test]
+    fn test_next_block_range_backfills_gap() {
+        // Head jumped from 100 to 103, 101 & 102 must not be skipped.
+        let range = next_block_range(Some(100), 103, MAX_BLOCKS_PER_POLL);
+        assert_eq!(range.collect::<Vec<_>>(), vec![101, 102, 103]);
+    }
+
+    #[test]
+    fn test_next_block_range_no_new_block() {
+        assert!(next_block_range(Some(100), 100, MAX_BLOCKS_PER_POLL).is_empty());
+        // A lagging node reports an older head, nothing is fetched again.
+        assert!(next_block_range(Some(100), 98, MAX_BLOCKS_PER_POLL).is_empty());
+    }
+
+    #[test]
+    fn test_next_block_range_is_bounded() {
+        let range = next_block_range(Some(100), 1_000, MAX_BLOCKS_PER_POLL);
+        assert_eq!(range, 101..=132);
+    }
+}
@@ -0,0 +1,592 @@
+//! WebSocket connection to the Ethereum network with alloy
+//! Ref: https://alloy.rs/building-with-alloy/connecting-to-a-blockchain/ws-provider
+use std::{
+    pin::Pin,
+    sync::{
+        atomic::{AtomicBool, Ordering},
+        Arc,
+    },
+    time::Duration,
+};
+
+use alloy_network::{AnyNetwork, AnyRpcBlock, AnyRpcTransaction};
+use alloy_primitives::B256;
+use alloy_provider::{Provider, ProviderBuilder, RootProvider, WsConnect};
+use alloy_pubsub::PubSubFrontend;
+use alloy_rpc_types::{BlockId, BlockNumberOrTag, BlockTransactionsKind, Filter, Log};
+use async_trait::async_trait;
+use futures::{stream, Stream, StreamExt};
+use tokio::{
+    sync::mpsc::{self, Receiver, Sender},
+    task::JoinHandle,
+    time,
+};
+use tracing::{debug, error, info, warn};
+
+use crate::network::orchestrator::{
+    with_undelivered_ancestors, AnyRPCNetwork, ChainData, ChainOrchestrator, DeliveredBlocks,
+    OrchestratorError, PendingTransaction, MAX_CONCURRENT_FETCHES,
+};
+
+type WsProvider = RootProvider<PubSubFrontend, AnyNetwork>;
+
+/// Notifications received from the `eth_subscribe` subscriptions of a single connection.
+#[allow(clippy::large_enum_variant)]
+enum WsNotification {
+    NewHead(B256),
+    PendingTransaction(B256),
+    FullPendingTransaction(AnyRpcTransaction),
+    Log(Log),
+}
+
+/// [`WsOrchestrator`] drives `eth_subscribe` subscriptions (`newHeads`, `newPendingTransactions`
+/// and `logs`) over a WebSocket connection. Unlike [`super::rpc::RpcOrchestrator`] it does not poll,
+/// data is pushed by the node as soon as it is available.
+///
+/// When the connection drops, the orchestrator reconnects and re-subscribes after `reconnect_delay`.
+/// Blocks are always delivered after their parent: when a new head does not build on the last
+/// delivered block, e.g. after a reconnect or a reorg, its missing ancestors are fetched back by
+/// parent hash, up to [`crate::ingest::reorg::MAX_REORG_DEPTH`] blocks, and delivered first
+/// along with their logs.
+pub struct WsOrchestrator {
+    ws_url: String,
+    reconnect_delay: Duration,
+    is_running: Arc<AtomicBool>,
+    name: String,
+    task: Option<JoinHandle<()>>,
+}
+
+impl WsOrchestrator {
+    pub fn new(
+        name: String,
+        ws_url: String,
+        reconnect_delay: Duration,
+    ) -> Result<Self, OrchestratorError> {
+        ws_url
+            .parse::<reqwest::Url>()
+            .map_err(|e| OrchestratorError::InvalidUrl(e.to_string()))?;
+
+        Ok(Self {
+            ws_url,
+            reconnect_delay,
+            is_running: Arc::new(AtomicBool::new(false)),
+            name,
+            task: None,
+        })
+    }
+
+    /// Connects to the node and subscribes to all supported subscriptions, returning a single
+    /// merged stream of notifications. Subscriptions the node rejects are skipped.
+    async fn subscribe(
+        ws_url: &str,
+    ) -> Result<
+        (
+            WsProvider,
+            Pin<Box<dyn Stream<Item = WsNotification> + Send>>,
+        ),
+        OrchestratorError,
+    > {
+        let provider: WsProvider = ProviderBuilder::new()
+            .network::<AnyNetwork>()
+            .on_ws(WsConnect::new(ws_url))
+            .await
+            .map_err(|e| OrchestratorError::Connection(e.to_string()))?;
+
+        let mut streams: Vec<Pin<Box<dyn Stream<Item = WsNotification> + Send>>> = Vec::new();
+
+        match provider.subscribe_blocks().await {
+            Ok(sub) => streams.push(Box::pin(
+                sub.into_stream()
+                    .map(|header| WsNotification::NewHead(header.hash)),
+            )),
+            Err(e) => warn!(?e, "Unable to subscribe to newHeads"),
+        }
+
+        // Full transactions save fetching every pending transaction, not every node pushes them.
+        match provider.subscribe_full_pending_transactions().await {
+            Ok(sub) => streams.push(Box::pin(
+                sub.into_stream()
+                    .map(WsNotification::FullPendingTransaction),
+            )),
+            Err(e) => {
+                debug!(
+                    ?e,
+                    "Full pending transactions unsupported, subscribing to hashes"
+                );
+                match provider.subscribe_pending_transactions().await {
+                    Ok(sub) => streams.push(Box::pin(
+                        sub.into_stream().map(WsNotification::PendingTransaction),
+                    )),
+                    Err(e) => warn!(?e, "Unable to subscribe to newPendingTransactions"),
+                }
+            }
+        }
+
+        match provider.subscribe_logs(&Filter::new()).await {
+            Ok(sub) => streams.push(Box::pin(sub.into_stream().map(WsNotification::Log))),
+            Err(e) => warn!(?e, "Unable to subscribe to logs"),
+        }
+
+        if streams.is_empty() {
+            return Err(OrchestratorError::Subscription(
+                "node rejected every subscription".to_string(),
+            ));
+        }
+
+        Ok((provider, Box::pin(stream::select_all(streams))))
+    }
+
+    /// Resolves a notification into [`ChainData`], fetching the full block or transaction
+    /// when the node only pushed its hash.
+    async fn resolve(provider: &WsProvider, notification: WsNotification) -> Option<ChainData> {
+        match notification {
+            WsNotification::NewHead(hash) => {
+                match provider
+                    .get_block(BlockId::Hash(hash.into()), BlockTransactionsKind::Full)
+                    .await
+                {
+                    Ok(Some(block)) => Some(ChainData::AnyRPCNetwork(AnyRPCNetwork::Block(block))),
+                    Ok(None) => None,
+                    Err(e) => {
+                        error!(?e, %hash, "Error fetching block");
+                        None
+                    }
+                }
+            }
+            WsNotification::PendingTransaction(hash) => {
+                match provider.get_transaction_by_hash(hash).await {
+                    Ok(Some(tx)) => Some(ChainData::AnyRPCNetwork(AnyRPCNetwork::TransactionPool(
+                        PendingTransaction::new(tx),
+                    ))),
+                    Ok(None) => None,
+                    Err(e) => {
+                        debug!(?e, %hash, "Error fetching pending transaction");
+                        None
+                    }
+                }
+            }
+            WsNotification::FullPendingTransaction(tx) => Some(ChainData::AnyRPCNetwork(
+                AnyRPCNetwork::TransactionPool(PendingTransaction::new(tx)),
+            )),
+            WsNotification::Log(log) => {
+                Some(ChainData::AnyRPCNetwork(AnyRPCNetwork::Logs(vec![log])))
+            }
+        }
+    }
+
+    /// Returns the data to deliver for a new head: nothing if it was already delivered, otherwise
+    /// the head preceded by its undelivered ancestors and their logs. Logs of the head itself are
+    /// only fetched when `head_logs` is set, those of notified heads are pushed by the `logs`
+    /// subscription.
+    async fn undelivered_blocks(
+        provider: &WsProvider,
+        delivered: &mut DeliveredBlocks,
+        head: AnyRpcBlock,
+        head_logs: bool,
+    ) -> Vec<ChainData> {
+        let head_hash = head.header.hash;
+        if delivered.contains(&head_hash) {
+            return Vec::new();
+        }
+
+        let blocks = match with_undelivered_ancestors(provider, delivered, head.clone()).await {
+            Ok(blocks) => blocks,
+            Err(e) => {
+                error!(?e, %head_hash, "Error fetching missing blocks");
+                vec![head]
+            }
+        };
+
+        let mut data = Vec::new();
+        for block in blocks {
+            let hash = block.header.hash;
+            let logs = if hash != head_hash || head_logs {
+                match provider.get_logs(&Filter::new().at_block_hash(hash)).await {
+                    Ok(logs) => logs,
+                    Err(e) => {
+                        error!(?e, %hash, "Error fetching logs");
+                        Vec::new()
+                    }
+                }
+            } else {
+                Vec::new()
+            };
+
+            delivered.record(block.header.number, hash);
+            data.push(ChainData::AnyRPCNetwork(AnyRPCNetwork::Block(block)));
+            if !logs.is_empty() {
+                data.push(ChainData::AnyRPCNetwork(AnyRPCNetwork::Logs(logs)));
+            }
+        }
+        data
+    }
+
+    /// Fetches the blocks produced since the last delivered block, e.g. while reconnecting.
+    async fn backfill(provider: &WsProvider, delivered: &mut DeliveredBlocks) -> Vec<ChainData> {
+        if delivered.last().is_none() {
+            return Vec::new();
+        }
+
+        match provider
+            .get_block(
+                BlockId::Number(BlockNumberOrTag::Latest),
+                BlockTransactionsKind::Full,
+            )
+            .await
+        {
+            Ok(Some(head)) => Self::undelivered_blocks(provider, delivered, head, true).await,
+            Ok(None) => Vec::new(),
+            Err(e) => {
+                error!(?e, "Error fetching head to backfill missed blocks");
+                Vec::new()
+            }
+        }
+    }
+
+    /// Runs the subscription loop until the orchestrator is stopped or the receiver is dropped,
+    /// reconnecting whenever the connection is lost. Up to [`MAX_CONCURRENT_FETCHES`]
+    /// notifications are resolved concurrently, they are sent in the order they were received.
+    async fn run(
+        ws_url: String,
+        reconnect_delay: Duration,
+        is_running: Arc<AtomicBool>,
+        sender: Sender<ChainData>,
+    ) {
+        let mut delivered = DeliveredBlocks::default();
+        while is_running.load(Ordering::Relaxed) {
+            match Self::subscribe(&ws_url).await {
+                Ok((provider, notifications)) => {
+                    info!("Subscribed to {ws_url}");
+
+                    for data in Self::backfill(&provider, &mut delivered).await {
+                        if sender.send(data).await.is_err() {
+                            return;
+                        }
+                    }
+
+                    let mut resolved = notifications
+                        .map(|notification| Self::resolve(&provider, notification))
+                        .buffered(MAX_CONCURRENT_FETCHES);
+                    while let Some(data) = resolved.next().await {
+                        if !is_running.load(Ordering::Relaxed) {
+                            return;
+                        }
+                        let data = match data {
+                            Some(ChainData::AnyRPCNetwork(AnyRPCNetwork::Block(block))) => {
+                                Self::undelivered_blocks(&provider, &mut delivered, block, false)
+                                    .await
+                            }
+                            Some(data) => vec![data],
+                            None => Vec::new(),
+                        };
+                        for data in data {
+                            if sender.send(data).await.is_err() {
+                                return;
+                            }
+                        }
+                    }
+                    warn!("Subscriptions to {ws_url} ended, reconnecting...");
+                }
+                Err(e) => {
+                    error!(?e, "Unable to subscribe to {ws_url}");
+                }
+            }
+            time::sleep(reconnect_delay).await;
+        }
+    }
+}
+
+#[async_trait]
+impl ChainOrchestrator for WsOrchestrator {
+    async fn start(&mut self) -> Result<Receiver<ChainData>, OrchestratorError> {
+        if self.is_running.load(Ordering::Relaxed) {
+            return Err(OrchestratorError::AlreadyRunning {
+                name: self.name.clone(),
+            });
+        }
+
+        self.is_running.store(true, Ordering::Relaxed);
+        let (tx, rx) = mpsc::channel(10_000);
+
+        self.task = Some(tokio::spawn(Self::run(
+            self.ws_url.clone(),
+            self.reconnect_delay,
+            self.is_running.clone(),
+            tx,
+        )));
+
+        Ok(rx)
+    }
+
+    async fn stop(&self) -> Result<(), OrchestratorError> {
+        self.is_running.store(false, Ordering::Relaxed);
+
+        if let Some(task) = &self.task {
+            task.abort();
+        }
+
+        Ok(())
+    }
+
+    fn name(&self) -> &str {
+        &self.name
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::utils::test_utils::generate_random_transaction;
+    use alloy_network::AnyHeader;
+    use alloy_primitives::LogData;
+    use alloy_rpc_types::{Block, BlockTransactions, Header};
+    use futures::SinkExt;
+    use serde_json::{json, Value};
+    use tokio::net::TcpListener;
+    use tokio_tungstenite::tungstenite::Message;
+
+    const NEW_HEADS_ID: &str = "0x1";
+    const PENDING_TX_ID: &str = "0x2";
+    const LOGS_ID: &str = "0x3";
+
+    fn notification(subscription: &str, result: Value) -> Message {
+        Message::Text(
+            json!({
+                "jsonrpc": "2.0",
+                "method": "eth_subscription",
+                "params": { "subscription": subscription, "result": result }
+            })
+            .to_string(),
+        )
+    }
+
+    fn block(number: u64, hash: u8, parent_hash: u8) -> AnyRpcBlock {
+        AnyRpcBlock::new(Block {
+            header: Header {
+                hash: B256::repeat_byte(hash),
+                inner: AnyHeader {
+                    number,
+                    parent_hash: B256::repeat_byte(parent_hash),
+                    ..Default::default()
+                },
+                ..Default::default()
+            },
+            ..Default::default()
+        })
+    }
+
+    fn response(request: &Value, result: Value) -> Message {
+        Message::Text(
+            json!({ "jsonrpc": "2.0", "id": request["id"], "result": result }).to_string(),
+        )
+    }
+
+    /// Serves a single WebSocket connection, answering subscription and fetch requests and
+    /// pushing one notification per subscription, `block` being the new head, before closing the
+    /// connection. Full pending transaction subscriptions are rejected unless `full_pending` is set.
+    async fn serve_connection(listener: &TcpListener, full_pending: bool, block: AnyRpcBlock) {
+        let (socket, _) = listener.accept().await.unwrap();
+        let mut ws = tokio_tungstenite::accept_async(socket).await.unwrap();
+
+        let tx = generate_random_transaction(100);
+        let mut block = block;
+        block.transactions = BlockTransactions::Full(vec![tx.clone()]);
+
+        let mut subscriptions = 0;
+        let mut fetches = 0;
+        // Without full pending transactions the pending transaction is fetched as well
+        let expected_fetches = if full_pending { 1 } else { 2 };
+        while let Some(Ok(Message::Text(text))) = ws.next().await {
+            let request: Value = serde_json::from_str(&text).unwrap();
+            let result = match request["method"].as_str().unwrap() {
+                "eth_subscribe" => match request["params"][0].as_str().unwrap() {
+                    "newHeads" => Ok(json!(NEW_HEADS_ID)),
+                    "newPendingTransactions" if request["params"][1] == true && !full_pending => {
+                        Err(json!({ "code": -32602, "message": "invalid params" }))
+                    }
+                    "newPendingTransactions" => Ok(json!(PENDING_TX_ID)),
+                    _ => Ok(json!(LOGS_ID)),
+                },
+                "eth_getBlockByHash" => {
+                    fetches += 1;
+                    Ok(serde_json::to_value(&block).unwrap())
+                }
+                "eth_getTransactionByHash" => {
+                    fetches += 1;
+                    Ok(serde_json::to_value(&tx).unwrap())
+                }
+                _ => Ok(Value::Null),
+            };
+            let response = match result {
+                Ok(result) => {
+                    if request["method"] == "eth_subscribe" {
+                        subscriptions += 1;
+                    }
+                    json!({ "jsonrpc": "2.0", "id": request["id"], "result": result })
+                }
+                Err(error) => json!({ "jsonrpc": "2.0", "id": request["id"], "error": error }),
+            };
+            ws.send(Message::Text(response.to_string())).await.unwrap();
+
+            if subscriptions == 3 {
+                subscriptions = 0;
+                let header = serde_json::to_value(&block.header).unwrap();
+                let pending = if full_pending {
+                    serde_json::to_value(&tx).unwrap()
+                } else {
+                    serde_json::to_value(tx.info().hash.unwrap_or_default()).unwrap()
+                };
+                let log = serde_json::to_value(Log::<LogData>::default()).unwrap();
+                ws.send(notification(NEW_HEADS_ID, header)).await.unwrap();
+                ws.send(notification(PENDING_TX_ID, pending)).await.unwrap();
+                ws.send(notification(LOGS_ID, log)).await.unwrap();
+            }
+
+            // Once every notification has been resolved we drop the connection.
+            if fetches == expected_fetches {
+                break;
+            }
+        }
+    }
+
+    async fn expect_all_notifications(receiver: &mut Receiver<ChainData>) {
+        let mut blocks = 0;
+        let mut pending = 0;
+        let mut logs = 0;
+        while blocks + pending + logs < 3 {
+            let data = time::timeout(Duration::from_secs(5), receiver.recv())
+                .await
+                .expect("timed out waiting for chain data")
+                .expect("orchestrator channel closed");
+            match data {
+                ChainData::AnyRPCNetwork(AnyRPCNetwork::Block(_)) => blocks += 1,
+                ChainData::AnyRPCNetwork(AnyRPCNetwork::TransactionPool(_)) => pending += 1,
+                ChainData::AnyRPCNetwork(AnyRPCNetwork::Logs(_)) => logs += 1,
+                data => panic!("Unexpected chain data {data:?}"),
+            }
+        }
+        assert_eq!((blocks, pending, logs), (1, 1, 1));
+    }
+
+    #[tokio::test]
+    async fn test_ws_orchestrator_resubscribes_on_disconnect() {
+        // Nodes pushing full pending transactions, and nodes only pushing their hashes
+        for full_pending in [true, false] {
+            let listener = TcpListener::bind("127.0.0.1:0").await.unwrap();
+            let url = format!("ws://{}", listener.local_addr().unwrap());
+
+            let server = tokio::spawn(async move {
+                // First connection, then a second one once the client has reconnected.
+                serve_connection(&listener, full_pending, block(1, 1, 0)).await;
+                serve_connection(&listener, full_pending, block(2, 2, 1)).await;
+            });
+
+            let mut orchestrator =
+                WsOrchestrator::new("test".to_string(), url, Duration::from_millis(50)).unwrap();
+            let mut receiver = orchestrator.start().await.unwrap();
+
+            expect_all_notifications(&mut receiver).await;
+            expect_all_notifications(&mut receiver).await;
+
+            orchestrator.stop().await.unwrap();
+            server.await.unwrap();
+        }
+    }
+
+    /// Serves a single WebSocket connection of a node whose chain is `chain`, pushing its last
+    /// block as new head once subscribed. With `disconnect` the connection is closed once the head
+    /// was fetched.
+    async fn serve_chain(listener: &TcpListener, chain: &[AnyRpcBlock], disconnect: bool) {
+        let (socket, _) = listener.accept().await.unwrap();
+        let mut ws = tokio_tungstenite::accept_async(socket).await.unwrap();
+
+        let mut subscriptions = 0;
+        while let Some(Ok(Message::Text(text))) = ws.next().await {
+            let request: Value = serde_json::from_str(&text).unwrap();
+            let result = match request["method"].as_str().unwrap() {
+                "eth_subscribe" => {
+                    subscriptions += 1;
+                    match request["params"][0].as_str().unwrap() {
+                        "newHeads" => json!(NEW_HEADS_ID),
+                        "newPendingTransactions" => json!(PENDING_TX_ID),
+                        _ => json!(LOGS_ID),
+                    }
+                }
+                "eth_getBlockByNumber" => serde_json::to_value(chain.last()).unwrap(),
+                "eth_getBlockByHash" => {
+                    let hash: B256 = serde_json::from_value(request["params"][0].clone()).unwrap();
+                    serde_json::to_value(chain.iter().find(|block| block.header.hash == hash))
+                        .unwrap()
+                }
+                "eth_getLogs" => json!([]),
+                _ => Value::Null,
+            };
+            ws.send(response(&request, result)).await.unwrap();
+
+            let head = chain.last().unwrap();
+            if subscriptions == 3 {
+                subscriptions += 1;
+                let header = serde_json::to_value(&head.header).unwrap();
+                ws.send(notification(NEW_HEADS_ID, header)).await.unwrap();
+            } else if disconnect
+                && request["method"] == "eth_getBlockByHash"
+                && request["params"][0] == json!(head.header.hash)
+            {
+                break;
+            }
+        }
+    }
+
+    async fn expect_block(receiver: &mut Receiver<ChainData>, hash: u8) {
+        let data = time::timeout(Duration::from_secs(5), receiver.recv())
+            .await
+            .expect("timed out waiting for chain data")
+            .expect("orchestrator channel closed");
+        match data {
+            ChainData::AnyRPCNetwork(AnyRPCNetwork::Block(block)) => {
+                assert_eq!(block.header.hash, B256::repeat_byte(hash))
+            }
+            data => panic!("Unexpected chain data {data:?}"),
+        }
+    }
+
+    #[tokio::test]
+    async fn test_ws_orchestrator_fetches_blocks_missed_while_reconnecting() {
+        let listener = TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let url = format!("ws://{}", listener.local_addr().unwrap());
+
+        let server = tokio::spawn(async move {
+            serve_chain(&listener, &[block(1, 1, 0), block(2, 2, 1)], true).await;
+            // Block 2 was replaced, and blocks 3 and 4 built on top while reconnecting
+            let chain = [
+                block(1, 1, 0),
+                block(2, 0x22, 1),
+                block(3, 0x33, 0x22),
+                block(4, 0x44, 0x33),
+            ];
+            serve_chain(&listener, &chain, false).await;
+        });
+
+        let mut orchestrator =
+            WsOrchestrator::new("test".to_string(), url, Duration::from_millis(50)).unwrap();
+        let mut receiver = orchestrator.start().await.unwrap();
+
+        expect_block(&mut receiver, 2).await;
+        expect_block(&mut receiver, 0x22).await;
+        expect_block(&mut receiver, 0x33).await;
+        expect_block(&mut receiver, 0x44).await;
+
+        orchestrator.stop().await.unwrap();
+        server.abort();
+    }
+
+    #[test]
+    fn test_ws_orchestrator_invalid_url() {
+        let result = WsOrchestrator::new(
+            "test".to_string(),
+            "not a url".to_string(),
+            Duration::from_secs(1),
+        );
+        assert!(matches!(result, Err(OrchestratorError::InvalidUrl(_))));
+    }
+}
@@ -1,6 +1,10 @@
-use alloy_dyn_abi::{DynSolCall, DynSolEvent, DynSolReturns, DynSolType, DynSolValue};
-use alloy_primitives::{keccak256, LogData, B256};
+use crate::utils::json::dyn_sol_to_json;
+use alloy_dyn_abi::{DynSolCall, DynSolEvent, DynSolReturns, DynSolType, DynSolValue, Specifier};
+use alloy_json_abi::{Event, Function, Param};
+use alloy_primitives::{keccak256, LogData, Selector, B256};
+use serde_json::{Map, Value};
 use std::collections::HashMap;
+use thiserror::Error;
 
 #[derive(Debug, Clone)]
 #[allow(dead_code)]
@@ -16,11 +20,15 @@ pub(crate) struct DecodedParam {
     pub indexed: bool,
 }
 
-#[derive(Debug)]
+#[derive(Debug, Error)]
 pub enum DecodeError {
+    #[error("expected `name(type name, ...)`")]
     InvalidFormat,
+    #[error("invalid parameter declaration")]
     InvalidParameter,
+    #[error("unable to decode data")]
     UnableToDecode,
+    #[error("unsupported parameter type")]
     UnsupportedType,
 }
 
@@ -37,70 +45,48 @@ pub(crate) struct EventDefinition {
     pub definitions: Vec<(String, String, bool)>, // (name, type, indexed)
 }
 
-/// Parses a Solidity signature into its name and parameters; does not support nested signatures.
-/// Why not ? nested signatures are quite complicated and prove to be less useful because:
-/// 1. parameter names are not known at runtime without abi
-/// 2. nested structures are just positional , making it painfuly hard to come up with the best filter approach
-///    without high cognitive load just to write filters for filtering contract data.
+/// Parses a Solidity signature into its name and parameters; supports every solidity type
+/// (`uint24`, `bytes20`, `address[]`, `uint256[2]`, `(address,uint256)[]` ...).
+///
+/// Tuple components are unnamed, import the contract JSON ABI to name them, see
+/// [`CallDefinition::from_abi`] and [`EventDefinition::from_abi`].
+///
+/// Unnamed parameters (e.g. `transfer(address,uint256)`) are named after their position.
 ///
 /// # Parameters
 /// - `sig`: The Solidity signature as a string (e.g., `transfer(address indexed to, uint256 value)`).
 pub(crate) fn parse_signature(sig: &str) -> Result<(String, Vec<ParsedParameter>), DecodeError> {
-    let (name, params) = match sig.split_once('(') {
-        Some((n, p)) => (n.trim(), p.trim_end_matches(')')),
+    let (name, params) = match sig.trim().strip_suffix(')').and_then(|s| s.split_once('(')) {
+        Some((n, p)) => (n.trim(), p),
         None => return Err(DecodeError::InvalidFormat),
     };
-    let mut parameters = Vec::new();
-
-    for param in params.split(',').filter(|s| !s.is_empty()) {
-        let parts = param.split_whitespace().collect::<Vec<&str>>();
+    if name.is_empty() || !name.chars().all(|c| c.is_ascii_alphanumeric() || c == '_') {
+        return Err(DecodeError::InvalidFormat);
+    }
+    if params.trim().is_empty() {
+        return Ok((name.to_string(), Vec::new()));
+    }
 
-        let (ty, is_indexed, name) = match parts.len() {
-            2 => (parts[0], false, parts[1]),
-            3 if parts[1] == "indexed" => (parts[0], true, parts[2]),
+    let mut parameters = Vec::new();
+    for (position, param) in split_top_level(params)?.into_iter().enumerate() {
+        let (ty, rest) = split_type(param.trim());
+        let position = position.to_string();
+        let (is_indexed, name) = match rest.split_whitespace().collect::<Vec<&str>>().as_slice() {
+            [] => (false, position.as_str()),
+            ["indexed"] => (true, position.as_str()),
+            [name] => (false, *name),
+            ["indexed", name] => (true, *name),
             _ => return Err(DecodeError::InvalidParameter),
         };
+        if ty.is_empty() {
+            return Err(DecodeError::InvalidParameter);
+        }
 
-        let sol_type = match ty {
-            // Address
-            "address" => DynSolType::Address,
-
-            // Integers
-            "uint256" => DynSolType::Uint(256),
-            "uint128" => DynSolType::Uint(128),
-            "uint64" => DynSolType::Uint(64),
-            "uint32" => DynSolType::Uint(32),
-            "uint16" => DynSolType::Uint(16),
-            "uint8" => DynSolType::Uint(8),
-
-            "int256" => DynSolType::Int(256),
-            "int128" => DynSolType::Int(128),
-            "int64" => DynSolType::Int(64),
-            "int32" => DynSolType::Int(32),
-            "int16" => DynSolType::Int(16),
-            "int8" => DynSolType::Int(8),
-
-            // Boolean
-            "bool" => DynSolType::Bool,
-
-            // Strings and Bytes
-            "string" => DynSolType::String,
-            "bytes" => DynSolType::Bytes,
-
-            // Fixed bytes
-            "bytes1" => DynSolType::FixedBytes(1),
-            "bytes2" => DynSolType::FixedBytes(2),
-            "bytes3" => DynSolType::FixedBytes(3),
-            "bytes4" => DynSolType::FixedBytes(4),
-            "bytes8" => DynSolType::FixedBytes(8),
-            "bytes16" => DynSolType::FixedBytes(16),
-            "bytes32" => DynSolType::FixedBytes(32),
-            _ => return Err(DecodeError::UnsupportedType),
-        };
+        let type_info = DynSolType::parse(ty).map_err(|_| DecodeError::UnsupportedType)?;
 
         parameters.push(ParsedParameter {
             name: name.to_string(),
-            type_info: sol_type,
+            type_info,
             is_indexed,
         });
     }
@@ -108,6 +94,64 @@ pub(crate) fn parse_signature(sig: &str) -> Result<(String, Vec<ParsedParameter>
     Ok((name.to_string(), parameters))
 }
 
+/// Splits a parameter list on the commas that are not nested in a tuple.
+fn split_top_level(params: &str) -> Result<Vec<&str>, DecodeError> {
+    let mut parts = Vec::new();
+    let mut depth = 0usize;
+    let mut start = 0;
+
+    for (i, c) in params.char_indices() {
+        match c {
+            '(' => depth += 1,
+            ')' => depth = depth.checked_sub(1).ok_or(DecodeError::InvalidFormat)?,
+            ',' if depth == 0 => {
+                parts.push(&params[start..i]);
+                start = i + 1;
+            }
+            _ => (),
+        }
+    }
+    if depth != 0 {
+        return Err(DecodeError::InvalidFormat);
+    }
+    parts.push(&params[start..]);
+
+    Ok(parts)
+}
+
+/// Splits a parameter declaration into its type and the rest (`indexed` and name), the type
+/// ends at the first whitespace outside of a tuple, e.g. `(address, uint256)[] indexed orders`.
+fn split_type(param: &str) -> (&str, &str) {
+    let mut depth = 0usize;
+    for (i, c) in param.char_indices() {
+        match c {
+            '(' => depth += 1,
+            ')' => depth = depth.saturating_sub(1),
+            c if c.is_whitespace() && depth == 0 => return (&param[..i], &param[i..]),
+            _ => (),
+        }
+    }
+    (param, "")
+}
+
+/// Computes the 4-byte selector of a function from its signature.
+pub(crate) fn function_selector(sig: &str) -> Result<Selector, DecodeError> {
+    let hash = keccak256(canonical_signature(sig)?.as_bytes());
+    Ok(Selector::from_slice(&hash[..4]))
+}
+
+/// Returns the canonical form of a signature used for selectors and topics, i.e. without
+/// parameter names, `indexed` or whitespace: `transfer(address,uint256)`.
+pub(crate) fn canonical_signature(sig: &str) -> Result<String, DecodeError> {
+    let (name, params) = parse_signature(sig)?;
+    let types = params
+        .iter()
+        .map(|param| param.type_info.sol_type_name())
+        .collect::<Vec<_>>();
+
+    Ok(format!("{name}({})", types.join(",")))
+}
+
 impl DecodedLog {
     pub(crate) fn get_parameter(&self, name: &str) -> Option<&DynSolValue> {
         if let Some(param) = self.params.get(name) {
@@ -139,7 +183,7 @@ impl EventDefinition {
             }
         }
 
-        let keccak2_fixed_bytes = keccak256(sig.as_bytes());
+        let keccak2_fixed_bytes = keccak256(canonical_signature(sig)?.as_bytes());
         let src = keccak2_fixed_bytes.as_slice();
         let sig_hash = B256::from_slice(src);
 
@@ -149,6 +193,30 @@ impl EventDefinition {
             definitions,
         })
     }
+
+    /// Creates the definition of an event imported from a contract JSON ABI, unlike
+    /// [`EventDefinition::from_signature`] any solidity type is supported.
+    pub fn from_abi(event: &Event) -> Result<Self, DecodeError> {
+        let definitions = event
+            .inputs
+            .iter()
+            .enumerate()
+            .map(|(position, param)| {
+                let name = match param.name.is_empty() {
+                    true => position.to_string(),
+                    false => param.name.clone(),
+                };
+                (name, param.ty.clone(), param.indexed)
+            })
+            .collect();
+
+        Ok(EventDefinition {
+            name: event.name.clone(),
+            event: event.resolve().map_err(|_| DecodeError::UnsupportedType)?,
+            definitions,
+        })
+    }
+
     pub(crate) fn decode_log(&self, log: &LogData) -> Result<DecodedLog, DecodeError> {
         let decoded = self
             .event
@@ -200,13 +268,16 @@ impl EventDefinition {
 #[allow(dead_code)]
 pub(crate) struct DecodedCall {
     pub(crate) name: String,
-    pub(crate) parameters: Vec<DynSolValue>,
+    /// Decoded parameters as a json object keyed by parameter name
+    pub(crate) parameters: Value,
 }
 
 #[allow(dead_code)]
 pub(crate) struct CallDefinition {
     pub name: String,
     pub call: DynSolCall,
+    /// Parameters in signature order, tuple components are only known when imported from an ABI
+    pub parameters: Vec<Param>,
 }
 
 #[allow(dead_code)]
@@ -214,34 +285,79 @@ impl CallDefinition {
     pub fn from_signature(sig: &str) -> Result<Self, DecodeError> {
         let (name, params) = parse_signature(sig)?;
 
+        let mut types = Vec::with_capacity(params.len());
         let mut parameters = Vec::with_capacity(params.len());
         for param in params {
-            parameters.push(param.type_info);
+            parameters.push(Param {
+                ty: param.type_info.sol_type_name().into_owned(),
+                name: param.name,
+                components: Vec::new(),
+                internal_type: None,
+            });
+            types.push(param.type_info);
         }
 
         // Create the 4-byte selector
-        let selector = keccak256(sig.as_bytes())[..4]
-            .try_into()
-            .map_err(|_| DecodeError::UnableToDecode)?;
+        let selector = function_selector(sig)?;
 
         // TODO: handle returns later...
         let returns = DynSolReturns::new(Vec::new());
 
         Ok(CallDefinition {
             name: name.clone(),
-            call: DynSolCall::new(selector, parameters, Some(name), returns),
+            call: DynSolCall::new(selector, types, Some(name), returns),
+            parameters,
+        })
+    }
+
+    /// Creates the definition of a function imported from a contract JSON ABI, unlike
+    /// [`CallDefinition::from_signature`] any solidity type is supported and tuple parameters
+    /// are decoded with the names of their components.
+    pub fn from_abi(function: &Function) -> Result<Self, DecodeError> {
+        let parameters = function
+            .inputs
+            .iter()
+            .enumerate()
+            .map(|(position, param)| {
+                let mut param = param.clone();
+                if param.name.is_empty() {
+                    param.name = position.to_string();
+                }
+                param
+            })
+            .collect();
+
+        Ok(CallDefinition {
+            name: function.name.clone(),
+            call: function
+                .resolve()
+                .map_err(|_| DecodeError::UnsupportedType)?,
+            parameters,
         })
     }
 
+    /// Decodes call data arguments, `data` must not include the 4-byte selector.
     pub(crate) fn decode_call_data(&self, data: &[u8]) -> Result<DecodedCall, DecodeError> {
         let decoded: Vec<DynSolValue> = self
             .call
             .abi_decode_input(data, true)
             .map_err(|_| DecodeError::UnableToDecode)?;
 
+        let parameters = self
+            .parameters
+            .iter()
+            .zip(decoded)
+            .map(|(param, value)| {
+                (
+                    param.name.clone(),
+                    dyn_sol_to_json(&value, &param.components),
+                )
+            })
+            .collect::<Map<String, Value>>();
+
         Ok(DecodedCall {
             name: self.name.clone(),
-            parameters: decoded,
+            parameters: Value::Object(parameters),
         })
     }
 }
@@ -250,7 +366,8 @@ impl CallDefinition {
 mod tests {
     use super::*;
     use alloy_primitives::{hex, Bytes};
-    use alloy_primitives::{Address, U256};
+    use alloy_primitives::{Address, I256, U256};
+    use serde_json::json;
 
     #[test]
     fn test_transfer_event_decode() {
@@ -294,4 +411,203 @@ mod tests {
         assert!(!value_param.indexed);
         assert!(matches!(value_param.value, DynSolValue::Uint(val, 256) if val == value));
     }
+
+    #[test]
+    fn test_transfer_call_decode() {
+        let call_def =
+            CallDefinition::from_signature("transfer(address to,uint256 amount)").unwrap();
+
+        let to_addr = Address::from_slice(&hex!("9876543210987654321098765432109876543210"));
+        let amount = U256::from(1000u64);
+        let data = call_def
+            .call
+            .abi_encode_input_raw(&[
+                DynSolValue::Address(to_addr),
+                DynSolValue::Uint(amount, 256),
+            ])
+            .unwrap();
+
+        let decoded = call_def.decode_call_data(&data).unwrap();
+        assert_eq!(decoded.name, "transfer");
+        assert_eq!(
+            decoded.parameters,
+            json!({ "to": to_addr.to_string(), "amount": "0x3e8" })
+        );
+    }
+
+    #[test]
+    fn test_abi_call_decode() {
+        let function = serde_json::from_value::<Function>(json!({
+            "type": "function",
+            "name": "exactInputSingle",
+            "stateMutability": "payable",
+            "inputs": [
+                {
+                    "name": "params",
+                    "type": "tuple",
+                    "components": [
+                        { "name": "tokenIn", "type": "address" },
+                        { "name": "tokenOut", "type": "address" },
+                        { "name": "fee", "type": "uint24" },
+                        { "name": "amountIn", "type": "uint256" }
+                    ]
+                },
+                { "name": "limits", "type": "uint160[]" }
+            ],
+            "outputs": []
+        }))
+        .unwrap();
+        let call_def = CallDefinition::from_abi(&function).unwrap();
+        assert_eq!(call_def.call.selector(), function.selector());
+
+        let token = Address::repeat_byte(0x11);
+        let data = call_def
+            .call
+            .abi_encode_input_raw(&[
+                DynSolValue::Tuple(vec![
+                    DynSolValue::Address(token),
+                    DynSolValue::Address(token),
+                    DynSolValue::Uint(U256::from(3000), 24),
+                    DynSolValue::Uint(U256::from(1000), 256),
+                ]),
+                DynSolValue::Array(vec![DynSolValue::Uint(U256::from(1), 160)]),
+            ])
+            .unwrap();
+
+        let decoded = call_def.decode_call_data(&data).unwrap();
+        assert_eq!(
+            decoded.parameters,
+            json!({
+                "params": {
+                    "tokenIn": token.to_string(),
+                    "tokenOut": token.to_string(),
+                    "fee": "0xbb8",
+                    "amountIn": "0x3e8",
+                },
+                "limits": ["0x1"],
+            })
+        );
+    }
+
+    #[test]
+    fn test_abi_event_decode() {
+        let event = Event::parse(
+            "event Swap(address indexed sender, int256 amount0, uint160 sqrtPriceX96, int24 tick)",
+        )
+        .unwrap();
+        let event_def = EventDefinition::from_abi(&event).unwrap();
+        assert_eq!(event_def.event.topic_0(), Some(event.selector()));
+
+        let sender = Address::repeat_byte(0x22);
+        let body = DynSolValue::Tuple(vec![
+            DynSolValue::Int(I256::try_from(-5).unwrap(), 256),
+            DynSolValue::Uint(U256::from(7), 160),
+            DynSolValue::Int(I256::try_from(-10).unwrap(), 24),
+        ]);
+        let log = LogData::new_unchecked(
+            vec![event.selector(), sender.into_word()],
+            body.abi_encode_params().into(),
+        );
+
+        let decoded = event_def.decode_log(&log).unwrap();
+        assert_eq!(
+            decoded.get_parameter("sender"),
+            Some(&DynSolValue::Address(sender))
+        );
+        assert_eq!(
+            decoded.get_parameter("tick"),
+            Some(&DynSolValue::Int(I256::try_from(-10).unwrap(), 24))
+        );
+    }
+
+    #[test]
+    fn test_canonical_signature() {
+        assert_eq!(
+            canonical_signature(
+                "Transfer(address indexed from, address indexed to, uint256 value)"
+            )
+            .unwrap(),
+            "Transfer(address,address,uint256)"
+        );
+        assert_eq!(
+            canonical_signature("transfer(address,uint256)").unwrap(),
+            "transfer(address,uint256)"
+        );
+        assert_eq!(
+            canonical_signature("totalSupply()").unwrap(),
+            "totalSupply()"
+        );
+
+        assert!(matches!(
+            canonical_signature("transfer(address to"),
+            Err(DecodeError::InvalidFormat)
+        ));
+        assert!(matches!(
+            canonical_signature("(address to)"),
+            Err(DecodeError::InvalidFormat)
+        ));
+        assert!(matches!(
+            canonical_signature("transfer(address to,)"),
+            Err(DecodeError::InvalidParameter)
+        ));
+    }
+
+    #[test]
+    fn test_parse_signature_types() {
+        let (_, params) = parse_signature(
+            "Swap(address indexed sender, int256 amount0, uint160 sqrtPriceX96, int24 tick, bytes20 id, address[] path, uint256[2] range, (address, uint24)[] pools)",
+        )
+        .unwrap();
+        let types = params
+            .iter()
+            .map(|param| param.type_info.clone())
+            .collect::<Vec<_>>();
+        assert_eq!(
+            types,
+            vec![
+                DynSolType::Address,
+                DynSolType::Int(256),
+                DynSolType::Uint(160),
+                DynSolType::Int(24),
+                DynSolType::FixedBytes(20),
+                DynSolType::Array(Box::new(DynSolType::Address)),
+                DynSolType::FixedArray(Box::new(DynSolType::Uint(256)), 2),
+                DynSolType::Array(Box::new(DynSolType::Tuple(vec![
+                    DynSolType::Address,
+                    DynSolType::Uint(24)
+                ]))),
+            ]
+        );
+        assert_eq!(params[7].name, "pools");
+        assert!(params[0].is_indexed);
+
+        assert_eq!(
+            canonical_signature("multicall(uint256 deadline, bytes[] data)").unwrap(),
+            "multicall(uint256,bytes[])"
+        );
+        assert_eq!(
+            canonical_signature("fill((address,uint256)[] orders, uint)").unwrap(),
+            "fill((address,uint256)[],uint256)"
+        );
+        assert!(matches!(
+            canonical_signature("transfer(uint7 amount)"),
+            Err(DecodeError::UnsupportedType)
+        ));
+        assert!(matches!(
+            canonical_signature("fill((address,uint256 orders)"),
+            Err(DecodeError::InvalidFormat)
+        ));
+    }
+
+    #[test]
+    fn test_event_topic_uses_canonical_signature() {
+        let event_def = EventDefinition::from_signature(
+            "Transfer(address indexed from,address indexed to,uint256 value)",
+        )
+        .unwrap();
+        assert_eq!(
+            event_def.event.topic_0(),
+            Some(keccak256("Transfer(address,address,uint256)"))
+        );
+    }
 }
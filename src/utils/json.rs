@@ -1,3 +1,5 @@
+use alloy_dyn_abi::DynSolValue;
+use alloy_json_abi::Param;
 use serde_json::Value;
 
 pub(crate) fn resolve_path<'a>(path: &str, value: &'a Value) -> Option<&'a Value> {
@@ -5,17 +7,81 @@ pub(crate) fn resolve_path<'a>(path: &str, value: &'a Value) -> Option<&'a Value
     let parts = path.split('.').collect::<Vec<&str>>();
     let mut current = value;
 
-    // 2. Traverse through each part of the path
+    // 2. Traverse through each part of the path, numeric parts index into arrays
     for part in parts {
-        current = current.get(part)?;
+        current = match current {
+            Value::Array(items) => items.get(part.parse::<usize>().ok()?)?,
+            _ => current.get(part)?,
+        };
     }
 
     Some(current)
 }
 
+/// Converts a decoded solidity value to json so it can be evaluated with [`resolve_path`].
+/// Integers are represented as `0x` prefixed hex strings (signed integers as decimal strings),
+/// addresses as lowercase `0x` prefixed hex,
+/// tuples as json objects keyed by the names of their `components` when all are named (i.e.
+/// imported from an ABI) and as json arrays otherwise, arrays as json arrays.
+pub(crate) fn dyn_sol_to_json(value: &DynSolValue, components: &[Param]) -> Value {
+    match value {
+        DynSolValue::Bool(b) => Value::Bool(*b),
+        DynSolValue::Int(i, _) => Value::String(i.to_string()),
+        DynSolValue::Uint(u, _) => Value::String(format!("{u:#x}")),
+        DynSolValue::FixedBytes(word, size) => Value::String(format!(
+            "0x{}",
+            alloy_primitives::hex::encode(&word[..*size])
+        )),
+        DynSolValue::Address(address) => Value::String(format!("{address:#x}")),
+        DynSolValue::Function(function) => Value::String(function.to_string()),
+        DynSolValue::Bytes(bytes) => {
+            Value::String(format!("0x{}", alloy_primitives::hex::encode(bytes)))
+        }
+        DynSolValue::String(s) => Value::String(s.clone()),
+        // Components of an array of tuples describe its elements
+        DynSolValue::Array(values) | DynSolValue::FixedArray(values) => Value::Array(
+            values
+                .iter()
+                .map(|value| dyn_sol_to_json(value, components))
+                .collect(),
+        ),
+        DynSolValue::Tuple(values)
+            if values.len() == components.len()
+                && components.iter().all(|param| !param.name.is_empty()) =>
+        {
+            Value::Object(
+                values
+                    .iter()
+                    .zip(components)
+                    .map(|(value, param)| {
+                        (
+                            param.name.clone(),
+                            dyn_sol_to_json(value, &param.components),
+                        )
+                    })
+                    .collect(),
+            )
+        }
+        DynSolValue::Tuple(values) => Value::Array(
+            values
+                .iter()
+                .enumerate()
+                .map(|(i, value)| {
+                    let components = components
+                        .get(i)
+                        .map(|param| param.components.as_slice())
+                        .unwrap_or_default();
+                    dyn_sol_to_json(value, components)
+                })
+                .collect(),
+        ),
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
+    use alloy_primitives::{Address, U256};
     use serde_json::json;
 
     #[test]
@@ -45,5 +111,30 @@ mod tests {
             resolve_path("transactions.nothing_to_see_here", &data),
             None
         );
+        assert_eq!(resolve_path("logs.1", &data), Some(&json!("log2")));
+        assert_eq!(resolve_path("logs.2", &data), None);
+    }
+
+    #[test]
+    fn test_dyn_sol_to_json() {
+        let value = DynSolValue::Tuple(vec![
+            DynSolValue::Uint(U256::from(255), 256),
+            DynSolValue::Bool(true),
+            DynSolValue::Array(vec![DynSolValue::Address(Address::ZERO)]),
+        ]);
+
+        assert_eq!(
+            dyn_sol_to_json(&value, &[]),
+            json!(["0xff", true, [format!("{:#x}", Address::ZERO)]])
+        );
+
+        let components = ["amount", "flag", "recipients"].map(|name| Param {
+            name: name.to_string(),
+            ..Default::default()
+        });
+        assert_eq!(
+            dyn_sol_to_json(&value, &components),
+            json!({ "amount": "0xff", "flag": true, "recipients": [format!("{:#x}", Address::ZERO)] })
+        );
     }
 }
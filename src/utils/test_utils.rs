@@ -7,14 +7,25 @@ use std::str::FromStr;
 
 #[allow(dead_code)]
 pub fn generate_random_transaction(value: u64) -> AnyRpcTransaction {
-    let chain_id = 1;
-    let gas_limit: u64 = 10;
+    let from = Address::from(rand::thread_rng().gen::<[u8; 20]>());
     let max_fee_per_gas: u128 = rand::thread_rng()
         .gen_range(20_000_000_000u64..100_000_000_000u64)
         .into();
-    let max_priority_fee_per_gas: u128 = rand::thread_rng()
-        .gen_range(1_000_000_000u64..10_000_000_000u64)
-        .into();
+    generate_transaction(from, 10, value, max_fee_per_gas)
+}
+
+/// Generates a transaction with a random hash paying a tenth of `max_fee_per_gas` as priority
+/// fee, transactions of the same sender and nonce replace each other when they pay more.
+#[allow(dead_code)]
+pub fn generate_transaction(
+    from: Address,
+    nonce: u64,
+    value: u64,
+    max_fee_per_gas: u128,
+) -> AnyRpcTransaction {
+    let chain_id = 1;
+    let gas_limit: u64 = 10;
+    let max_priority_fee_per_gas = max_fee_per_gas / 10;
     let to = Address::from_str("0x8ba1f109551bD432803012645Ac136ddd64dBa72").unwrap();
     let value = U256::from(value);
     let authorization_list = vec![];
@@ -23,7 +34,7 @@ pub fn generate_random_transaction(value: u64) -> AnyRpcTransaction {
     // Construct the EIP-7702 transaction
     let eip_7702 = TxEip7702 {
         chain_id,
-        nonce: 10,
+        nonce,
         gas_limit,
         max_fee_per_gas,
         max_priority_fee_per_gas,
@@ -40,7 +51,7 @@ pub fn generate_random_transaction(value: u64) -> AnyRpcTransaction {
             .unwrap(),
         true,
     );
-    let hash = B256::default();
+    let hash = B256::from(rand::thread_rng().gen::<[u8; 32]>());
     let tx_envelope = TxEnvelope::Eip7702(Signed::new_unchecked(eip_7702, signature, hash));
 
     AnyRpcTransaction::new(Transaction {
@@ -49,6 +60,92 @@ pub fn generate_random_transaction(value: u64) -> AnyRpcTransaction {
         block_number: Some(1),
         transaction_index: Some(0),
         effective_gas_price: Some(20_000_000_000u128),
-        from: to,
+        from,
     })
 }
+
+/// Serves JSON-RPC over HTTP on a local port, answering every request, batched or not, with
+/// `handler` called with its method and params. `Err` values are sent as JSON-RPC errors.
+/// Returns the url of the server.
+#[cfg(test)]
+pub async fn serve_json_rpc<F>(handler: F) -> String
+where
+    F: Fn(&str, &serde_json::Value) -> Result<serde_json::Value, serde_json::Value>
+        + Send
+        + Sync
+        + 'static,
+{
+    use serde_json::{json, Value};
+    use std::sync::Arc;
+    use tokio::io::{AsyncBufReadExt, AsyncReadExt, AsyncWriteExt, BufReader};
+
+    let listener = tokio::net::TcpListener::bind("127.0.0.1:0").await.unwrap();
+    let url = format!("http://{}", listener.local_addr().unwrap());
+    let handler = Arc::new(handler);
+
+    tokio::spawn(async move {
+        while let Ok((socket, _)) = listener.accept().await {
+            let handler = handler.clone();
+            tokio::spawn(async move {
+                let (reader, mut writer) = socket.into_split();
+                let mut reader = BufReader::new(reader);
+                loop {
+                    // Headers, only the body length matters
+                    let mut content_length = 0;
+                    loop {
+                        let mut line = String::new();
+                        if reader.read_line(&mut line).await.unwrap_or(0) == 0 {
+                            return;
+                        }
+                        let line = line.trim_end();
+                        if line.is_empty() {
+                            break;
+                        }
+                        if let Some((name, value)) = line.split_once(':') {
+                            if name.eq_ignore_ascii_case("content-length") {
+                                content_length = value.trim().parse().unwrap_or(0);
+                            }
+                        }
+                    }
+
+                    let mut body = vec![0; content_length];
+                    if reader.read_exact(&mut body).await.is_err() {
+                        return;
+                    }
+
+                    let respond = |request: &Value| {
+                        let method = request["method"].as_str().unwrap_or_default();
+                        match handler(method, &request["params"]) {
+                            Ok(result) => {
+                                json!({ "jsonrpc": "2.0", "id": request["id"], "result": result })
+                            }
+                            Err(error) => {
+                                json!({ "jsonrpc": "2.0", "id": request["id"], "error": error })
+                            }
+                        }
+                    };
+                    let request: Value = serde_json::from_slice(&body).unwrap_or_default();
+                    let response = match &request {
+                        Value::Array(requests) => {
+                            Value::Array(requests.iter().map(respond).collect())
+                        }
+                        request => respond(request),
+                    }
+                    .to_string();
+
+                    let head = format!(
+                        "HTTP/1.1 200 OK\r\ncontent-type: application/json\r\ncontent-length: {}\r\n\r\n",
+                        response.len()
+                    );
+                    if writer.write_all(head.as_bytes()).await.is_err()
+                        || writer.write_all(response.as_bytes()).await.is_err()
+                    {
+                        return;
+                    }
+                }
+            });
+        }
+    });
+
+    url
+}